@@ -6,7 +6,7 @@
 // This allows `generic<T>` to query capabilities of `T` without explicit bounds,
 // provided that `T` has been registered beforehand.
 
-use std::any::{Any, TypeId};
+use std::any::TypeId;
 use std::collections::HashMap;
 use std::sync::RwLock;
 
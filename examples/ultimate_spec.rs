@@ -137,7 +137,7 @@ fn main() {
     let s = String::from("hello");
     let n = 42i32;
     let v: Vec<u8> = vec![1, 2, 3];
-    let f = 3.14f64;
+    let f = 2.5f64;
     println!("  String:  {}", overlapping_dispatch(&s));
     println!("  i32:     {}", overlapping_dispatch(&n));
     println!("  Vec<u8>: {}", overlapping_dispatch(&v));
@@ -16,28 +16,53 @@ pub enum BoolExpr {
     Cap(Type),
     And(Box<BoolExpr>, Box<BoolExpr>),
     Or(Box<BoolExpr>, Box<BoolExpr>),
+    Xor(Box<BoolExpr>, Box<BoolExpr>),
+    Implies(Box<BoolExpr>, Box<BoolExpr>),
     Not(Box<BoolExpr>),
 }
 
 impl Parse for BoolExpr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        parse_or(input)
+        parse_implies(input)
     }
 }
 
-// Recursive descent parser: Or -> And -> Unary -> Primary
+// Recursive descent parser: Implies -> Or -> Xor -> And -> Unary -> Primary
+// (`|`, `^`, `&` follow Rust's precedence; `=>` binds loosest, right-assoc)
+
+fn parse_implies(input: ParseStream) -> syn::Result<BoolExpr> {
+    let lhs = parse_or(input)?;
+
+    if input.peek(Token![=>]) {
+        input.parse::<Token![=>]>()?;
+        let rhs = parse_implies(input)?;
+        return Ok(BoolExpr::Implies(Box::new(lhs), Box::new(rhs)));
+    }
+    Ok(lhs)
+}
 
 fn parse_or(input: ParseStream) -> syn::Result<BoolExpr> {
-    let mut lhs = parse_and(input)?;
+    let mut lhs = parse_xor(input)?;
 
     while input.peek(Token![|]) {
         input.parse::<Token![|]>()?;
-        let rhs = parse_and(input)?;
+        let rhs = parse_xor(input)?;
         lhs = BoolExpr::Or(Box::new(lhs), Box::new(rhs));
     }
     Ok(lhs)
 }
 
+fn parse_xor(input: ParseStream) -> syn::Result<BoolExpr> {
+    let mut lhs = parse_and(input)?;
+
+    while input.peek(Token![^]) {
+        input.parse::<Token![^]>()?;
+        let rhs = parse_and(input)?;
+        lhs = BoolExpr::Xor(Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
 fn parse_and(input: ParseStream) -> syn::Result<BoolExpr> {
     let mut lhs = parse_unary(input)?;
 
@@ -67,10 +92,72 @@ fn parse_primary(input: ParseStream) -> syn::Result<BoolExpr> {
     } else {
         // Parse a type (Capability)
         let ty: Type = input.parse()?;
+        if input.peek(syn::token::Bracket) {
+            return parse_bracket_args(ty, input).map(BoolExpr::Cap);
+        }
         Ok(BoolExpr::Cap(ty))
     }
 }
 
+/// `From[&str]` -> `From<&'static str>`: a trait with a concrete generic
+/// argument, for `caps_check!(String: From[&str])`.
+///
+/// Elided reference lifetimes become `'static`, since a probe impl cannot
+/// name an elided lifetime.
+fn parse_bracket_args(ty: Type, input: ParseStream) -> syn::Result<Type> {
+    let content;
+    let bracket = syn::bracketed!(content in input);
+    let mut args = content.parse_terminated(Type::parse, Token![,])?;
+    for arg in args.iter_mut() {
+        fill_elided_lifetimes(arg);
+    }
+
+    let Type::Path(mut type_path) = ty else {
+        return Err(syn::Error::new(bracket.span.join(), "expected a trait path before `[...]`"));
+    };
+    let Some(last) = type_path.path.segments.last_mut() else {
+        return Err(syn::Error::new(bracket.span.join(), "expected a trait path before `[...]`"));
+    };
+    if !last.arguments.is_none() {
+        return Err(syn::Error::new_spanned(
+            &last.arguments,
+            "use either `Trait<..>` or `Trait[..]`, not both",
+        ));
+    }
+    last.arguments = syn::PathArguments::AngleBracketed(syn::parse_quote! { <#args> });
+    Ok(Type::Path(type_path))
+}
+
+/// Give every elided `&` in `ty` the `'static` lifetime.
+fn fill_elided_lifetimes(ty: &mut Type) {
+    match ty {
+        Type::Reference(reference) => {
+            if reference.lifetime.is_none() {
+                reference.lifetime = Some(syn::parse_quote! { 'static });
+            }
+            fill_elided_lifetimes(&mut reference.elem);
+        }
+        Type::Path(type_path) => {
+            for segment in type_path.path.segments.iter_mut() {
+                if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in args.args.iter_mut() {
+                        if let syn::GenericArgument::Type(inner) = arg {
+                            fill_elided_lifetimes(inner);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Slice(slice) => fill_elided_lifetimes(&mut slice.elem),
+        Type::Array(array) => fill_elided_lifetimes(&mut array.elem),
+        Type::Ptr(ptr) => fill_elided_lifetimes(&mut ptr.elem),
+        Type::Paren(paren) => fill_elided_lifetimes(&mut paren.elem),
+        Type::Group(group) => fill_elided_lifetimes(&mut group.elem),
+        Type::Tuple(tuple) => tuple.elems.iter_mut().for_each(fill_elided_lifetimes),
+        _ => {}
+    }
+}
+
 // =============================================================================
 // BoolExpr Utilities
 // =============================================================================
@@ -78,13 +165,19 @@ fn parse_primary(input: ParseStream) -> syn::Result<BoolExpr> {
 /// Convert BoolExpr to human-readable string
 pub fn bool_expr_to_string(expr: &BoolExpr) -> String {
     match expr {
-        BoolExpr::Cap(ty) => quote!(#ty).to_string().replace(' ', ""),
+        BoolExpr::Cap(ty) => quote!(#ty).to_string().replace(' ', "").replace("::tola_caps::", ""),
         BoolExpr::And(lhs, rhs) => {
             format!("({} & {})", bool_expr_to_string(lhs), bool_expr_to_string(rhs))
         }
         BoolExpr::Or(lhs, rhs) => {
             format!("({} | {})", bool_expr_to_string(lhs), bool_expr_to_string(rhs))
         }
+        BoolExpr::Xor(lhs, rhs) => {
+            format!("({} ^ {})", bool_expr_to_string(lhs), bool_expr_to_string(rhs))
+        }
+        BoolExpr::Implies(lhs, rhs) => {
+            format!("({} => {})", bool_expr_to_string(lhs), bool_expr_to_string(rhs))
+        }
         BoolExpr::Not(operand) => format!("!{}", bool_expr_to_string(operand)),
     }
 }
@@ -103,6 +196,16 @@ pub fn bool_expr_to_type(expr: &BoolExpr) -> TokenStream {
             let r = bool_expr_to_type(rhs);
             quote! { ::tola_caps::Or<#l, #r> }
         }
+        BoolExpr::Xor(lhs, rhs) => {
+            let l = bool_expr_to_type(lhs);
+            let r = bool_expr_to_type(rhs);
+            quote! { ::tola_caps::Xor<#l, #r> }
+        }
+        BoolExpr::Implies(lhs, rhs) => {
+            let l = bool_expr_to_type(lhs);
+            let r = bool_expr_to_type(rhs);
+            quote! { ::tola_caps::Implies<#l, #r> }
+        }
         BoolExpr::Not(operand) => {
             let o = bool_expr_to_type(operand);
             quote! { ::tola_caps::Not<#o> }
@@ -141,6 +244,16 @@ pub fn bool_expr_to_capability_type(expr: &BoolExpr) -> TokenStream {
             let r = bool_expr_to_capability_type(rhs);
             quote! { ::tola_caps::Or<#l, #r> }
         }
+        BoolExpr::Xor(lhs, rhs) => {
+            let l = bool_expr_to_capability_type(lhs);
+            let r = bool_expr_to_capability_type(rhs);
+            quote! { ::tola_caps::Xor<#l, #r> }
+        }
+        BoolExpr::Implies(lhs, rhs) => {
+            let l = bool_expr_to_capability_type(lhs);
+            let r = bool_expr_to_capability_type(rhs);
+            quote! { ::tola_caps::Implies<#l, #r> }
+        }
         BoolExpr::Not(operand) => {
             let o = bool_expr_to_capability_type(operand);
             quote! { ::tola_caps::Not<#o> }
@@ -177,10 +290,19 @@ fn is_all_builtin(expr: &BoolExpr) -> bool {
         }
         BoolExpr::And(lhs, rhs) => is_all_builtin(lhs) && is_all_builtin(rhs),
         BoolExpr::Or(lhs, rhs) => is_all_builtin(lhs) && is_all_builtin(rhs),
+        BoolExpr::Xor(lhs, rhs) => is_all_builtin(lhs) && is_all_builtin(rhs),
+        BoolExpr::Implies(lhs, rhs) => is_all_builtin(lhs) && is_all_builtin(rhs),
         BoolExpr::Not(operand) => is_all_builtin(operand),
     }
 }
 
+/// First trait in `expr` that AutoCaps does not cover, if any.
+pub fn first_custom_trait(expr: &BoolExpr) -> Option<&Type> {
+    let mut atoms = Vec::new();
+    collect_caps(expr, &mut atoms);
+    atoms.into_iter().find(|trait_ty| !is_builtin_trait(&quote!(#trait_ty).to_string()))
+}
+
 // =============================================================================
 // Unified caps_check! Implementation (Autoref Fallback)
 // =============================================================================
@@ -214,94 +336,125 @@ pub fn generate_unified_check(expr: &BoolExpr, ty: &Type) -> TokenStream {
     }
 
     // All built-in traits: use autoref dispatch for efficiency
+    // Probes run in the caller's scope, so `#ty` may name the caller's generics;
+    // the dispatch impls below only see their own `T` and read the results.
+    let mut atoms = Vec::new();
+    collect_caps(expr, &mut atoms);
+    let probes = atoms.iter().map(|trait_ty| generate_single_probe(trait_ty, ty));
+    let count = atoms.len();
+
     // Fallback: Probe only (for types without AutoCaps)
-    let probe_only_body = generate_probe_body(expr, ty);
+    let probe_only_body = map_caps(expr, &mut 0, &mut |i, _| quote! { __probes[#i] });
     // Inherent: Probe || AutoCaps (for types with AutoCaps)
-    let combined_body = generate_combined_body_inherent(expr, ty);
+    let combined_body = generate_combined_body_inherent(expr);
 
     quote! {
         {
             use ::core::marker::PhantomData;
 
+            let __probes: [bool; #count] = [#(#probes),*];
+
             struct __Wrapper<T: ?Sized>(PhantomData<T>);
 
             // Low priority: trait method on &Wrapper<T> (fallback for all T)
             // This is used when T does NOT implement AutoCaps
             // Uses Probe only - works for concrete types
-            trait __Fallback { fn __check(&self) -> bool; }
+            trait __Fallback { fn __check(&self, __probes: [bool; #count]) -> bool; }
             impl<T: ?Sized> __Fallback for &__Wrapper<T> {
                 #[inline]
-                fn __check(&self) -> bool { #probe_only_body }
+                fn __check(&self, __probes: [bool; #count]) -> bool { #probe_only_body }
             }
 
             // High priority: inherent method on Wrapper<T> where T: AutoCaps
             // Uses Probe || AutoCaps strategy
             impl<T: ?Sized + ::tola_caps::detect::AutoCaps> __Wrapper<T> {
                 #[inline]
-                fn __check(&self) -> bool { #combined_body }
+                fn __check(&self, __probes: [bool; #count]) -> bool { #combined_body }
             }
 
             // Method resolution: inherent method preferred over trait method
-            (&__Wrapper::<#ty>(PhantomData)).__check()
+            (&__Wrapper::<#ty>(PhantomData)).__check(__probes)
         }
     }
 }
 
-/// Generate combined body for inherent impl (T: AutoCaps).
-/// Uses Probe || AutoCaps strategy with correct NOT semantics.
-///
-/// For each atomic trait check X:
-///   result_X = Probe<#ty, X> || AutoCaps<T>::IS_X
-///
-/// For NOT: !X becomes !result_X (NOT applied after combination)
-/// For AND: X & Y becomes result_X && result_Y
-/// For OR: X | Y becomes result_X || result_Y
-fn generate_combined_body_inherent(expr: &BoolExpr, ty: &Type) -> TokenStream {
+/// Collect the atomic trait checks of `expr`, left to right.
+fn collect_caps<'a>(expr: &'a BoolExpr, out: &mut Vec<&'a Type>) {
+    match expr {
+        BoolExpr::Cap(trait_ty) => out.push(trait_ty),
+        BoolExpr::And(lhs, rhs)
+        | BoolExpr::Or(lhs, rhs)
+        | BoolExpr::Xor(lhs, rhs)
+        | BoolExpr::Implies(lhs, rhs) => {
+            collect_caps(lhs, out);
+            collect_caps(rhs, out);
+        }
+        BoolExpr::Not(operand) => collect_caps(operand, out),
+    }
+}
+
+/// Rebuild `expr` as a bool expression, rendering the `i`-th atom
+/// (in `collect_caps` order) with `leaf`.
+fn map_caps(
+    expr: &BoolExpr,
+    next: &mut usize,
+    leaf: &mut dyn FnMut(usize, &Type) -> TokenStream,
+) -> TokenStream {
     match expr {
         BoolExpr::Cap(trait_ty) => {
-            let trait_str = quote!(#trait_ty).to_string();
-            if is_builtin_trait(&trait_str) {
-                let const_name = format_ident!("IS_{}", to_screaming_snake_case(&trait_str));
-                // Combined: Probe<#ty> || AutoCaps<T>
-                quote! {
-                    {
-                        // Probe: detects if concrete type implements trait
-                        let __probe = {
-                            trait __ProbeFallback { const VAL: bool = false; }
-                            struct __Probe<X: ?Sized>(::core::marker::PhantomData<X>);
-                            impl<X: ?Sized> __ProbeFallback for __Probe<X> {}
-                            impl<X: ?Sized + #trait_ty> __Probe<X> { const VAL: bool = true; }
-                            __Probe::<#ty>::VAL
-                        };
-                        // AutoCaps: claimed capability (may be from generic bound)
-                        let __autocaps = <T as ::tola_caps::detect::AutoCaps>::#const_name;
-                        // Combine: either detection confirms the trait
-                        __probe || __autocaps
-                    }
-                }
-            } else {
-                // Custom trait: use probe only
-                generate_single_probe(trait_ty, ty)
-            }
+            let i = *next;
+            *next += 1;
+            leaf(i, trait_ty)
         }
         BoolExpr::And(lhs, rhs) => {
-            let l = generate_combined_body_inherent(lhs, ty);
-            let r = generate_combined_body_inherent(rhs, ty);
+            let l = map_caps(lhs, next, leaf);
+            let r = map_caps(rhs, next, leaf);
             quote! { (#l && #r) }
         }
         BoolExpr::Or(lhs, rhs) => {
-            let l = generate_combined_body_inherent(lhs, ty);
-            let r = generate_combined_body_inherent(rhs, ty);
+            let l = map_caps(lhs, next, leaf);
+            let r = map_caps(rhs, next, leaf);
             quote! { (#l || #r) }
         }
+        BoolExpr::Xor(lhs, rhs) => {
+            let l = map_caps(lhs, next, leaf);
+            let r = map_caps(rhs, next, leaf);
+            quote! { (#l ^ #r) }
+        }
+        BoolExpr::Implies(lhs, rhs) => {
+            let l = map_caps(lhs, next, leaf);
+            let r = map_caps(rhs, next, leaf);
+            quote! { (!#l || #r) }
+        }
         BoolExpr::Not(operand) => {
-            // CRITICAL: Apply NOT after the combined result!
-            let o = generate_combined_body_inherent(operand, ty);
+            let o = map_caps(operand, next, leaf);
             quote! { (!#o) }
         }
     }
 }
 
+/// Generate combined body for inherent impl (T: AutoCaps).
+/// Uses Probe || AutoCaps strategy with correct NOT semantics.
+///
+/// For each atomic trait check X:
+///   result_X = Probe<#ty, X> || AutoCaps<T>::IS_X
+///
+/// For NOT: !X becomes !result_X (NOT applied after combination)
+/// For AND: X & Y becomes result_X && result_Y
+/// For OR: X | Y becomes result_X || result_Y
+/// For XOR: X ^ Y becomes result_X ^ result_Y
+/// For IMPLIES: X => Y becomes !result_X || result_Y
+fn generate_combined_body_inherent(expr: &BoolExpr) -> TokenStream {
+    map_caps(expr, &mut 0, &mut |i, trait_ty| {
+        let trait_str = quote!(#trait_ty).to_string();
+        let const_name = format_ident!("IS_{}", to_screaming_snake_case(&trait_str));
+        // Combined: Probe<#ty> || AutoCaps<T>
+        quote! {
+            (__probes[#i] || <T as ::tola_caps::detect::AutoCaps>::#const_name)
+        }
+    })
+}
+
 /// Generate Probe-based check body (for any trait)
 fn generate_probe_body(expr: &BoolExpr, ty: &Type) -> TokenStream {
     match expr {
@@ -316,6 +469,16 @@ fn generate_probe_body(expr: &BoolExpr, ty: &Type) -> TokenStream {
             let r = generate_probe_body(rhs, ty);
             quote! { (#l || #r) }
         }
+        BoolExpr::Xor(lhs, rhs) => {
+            let l = generate_probe_body(lhs, ty);
+            let r = generate_probe_body(rhs, ty);
+            quote! { (#l ^ #r) }
+        }
+        BoolExpr::Implies(lhs, rhs) => {
+            let l = generate_probe_body(lhs, ty);
+            let r = generate_probe_body(rhs, ty);
+            quote! { (!#l || #r) }
+        }
         BoolExpr::Not(operand) => {
             let o = generate_probe_body(operand, ty);
             quote! { (!#o) }
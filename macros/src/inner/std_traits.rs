@@ -91,7 +91,8 @@ pub const CORE_TRAITS: &[&str] = &[
     // ==================== Generic Traits (Markers Only) ====================
     // Traits with generic parameters (e.g. From<T>).
     // We generate markers (IsFrom) but not automatic `Detect::IS_FROM` constants,
-    // as they require specific type parameters.
+    // as they require specific type parameters; `caps_check!(T: From[U])`
+    // probes a concrete `U` instead.
 ];
 
 pub const GENERIC_TRAITS: &[&str] = &[
@@ -177,6 +178,11 @@ fn marker_name(name: &str) -> syn::Ident {
     format_ident!("Is{}", name)
 }
 
+/// Generate negative marker name: Clone -> IsNotClone
+fn not_marker_name(name: &str) -> syn::Ident {
+    format_ident!("IsNot{}", name)
+}
+
 /// Generate fallback trait name: Clone -> CloneFallback
 fn fallback_name(name: &str) -> syn::Ident {
     format_ident!("{}Fallback", name)
@@ -264,31 +270,17 @@ pub fn expand_cap_on_type_impl(ty: &syn::Ident, generics: &syn::Generics) -> Tok
     process_list(ALLOC_TRAITS, quote!{ #[cfg(feature = "alloc")] }, ty, generics, &mut consts, &mut insert_chain_parts, false);
     process_list(STD_LIB_TRAITS, quote!{ #[cfg(feature = "std")] }, ty, generics, &mut consts, &mut insert_chain_parts, false);
 
-    // Build the InsertIf chain
-    // NOTE: For stability, the AutoCapSet Trie only includes unconditional (CORE) traits.
-    // Feature-gated traits (alloc/std) are excluded from the structural type to avoid
-    // conditional type definition complexities, but their IS_XXX constants work for direct checks.
-
-    let mut insert_chain = quote! { ::tola_caps::trie::Empty };
+    // Build the InsertIf chain. Feature-gated traits are appended as one block per
+    // feature, switched on tola-caps' own features.
     let (_, ty_generics, _) = generics.split_for_impl();
-
-    // Only process CORE traits for the Trie chain
-    let core_lists = [CORE_TRAITS, CORE_TRAITS_2, GENERIC_TRAITS];
-    for list in core_lists {
-         for desc in list {
-            let model = TraitModel::parse_desc(desc, true);
-            let name = model.name().to_string();
-            let marker = marker_name(&name);
-            let const_nm = const_name(&name);
-
-            insert_chain = quote! {
-                <#insert_chain as ::tola_caps::detect::InsertIf<
-                    ::tola_caps::detect::#marker,
-                    { <#ty #ty_generics as ::tola_caps::detect::AutoCaps>::#const_nm },
-                >>::Out
-            };
-         }
+    let krate = quote! { ::tola_caps };
+    let self_ty = quote! { #ty #ty_generics };
+    let mut insert_chain = quote! { ::tola_caps::trie::Empty };
+    for list in [CORE_TRAITS, CORE_TRAITS_2, GENERIC_TRAITS] {
+        insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, list, None);
     }
+    insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, ALLOC_TRAITS, Some("alloc"));
+    insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, STD_LIB_TRAITS, Some("std"));
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -343,6 +335,7 @@ pub fn expand_std_traits() -> TokenStream {
             let select = select_name(&name);
 
             // 1. Generate Capability Marker (unique Identity)
+            let marker_str = marker.to_string();
             let hash_64 = fnv1a_64(&name);
             let stream_type = build_hash_stream_64(hash_64);
             // Generate tiered IList Identity
@@ -353,10 +346,14 @@ pub fn expand_std_traits() -> TokenStream {
                 #[doc = concat!("Capability marker for `", #name, "` trait detection.")]
                 pub type #marker = ::tola_caps::primitives::identity::Marker<#stream_type>;
 
+                // Hidden from "other types implement `Capability`" lists,
+                // which would otherwise spell out the marker hash streams.
                 #cfg
+                #[diagnostic::do_not_recommend]
                 impl Capability for #marker {
                     type Stream = #stream_type;
                     type Identity = #identity_type;
+                    const NAME: &'static str = #marker_str;
 
                     type At<D: ::tola_caps::Peano> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
                     where Self::Stream: GetTail<D>;
@@ -375,7 +372,15 @@ pub fn expand_std_traits() -> TokenStream {
                 let detection_code = model.expand_detection(&cfg);
                 headers.push(detection_code);
 
-                // 4. Generate Select Trait
+                // 4. Generate Negative Marker (`Present` iff the trait is absent)
+                let not_marker = not_marker_name(&name);
+                headers.push(quote! {
+                    #cfg
+                    #[doc = concat!("Capability query for the absence of `", #name, "`: `Present` exactly when [`", stringify!(#marker), "`] is `Absent`.")]
+                    pub type #not_marker = crate::trie::Not<#marker>;
+                });
+
+                // 5. Generate Select Trait
                 headers.push(quote! {
                     #cfg
                     #[doc = concat!("Select between two types based on `", #name, "` capability.")]
@@ -445,6 +450,16 @@ pub fn expand_std_traits() -> TokenStream {
         // All generated code blocks
         #(#headers)*
 
+        /// Detection marker for an iterator's `Item` type.
+        ///
+        /// Implemented by every `Iterator<Item = Item>`, so it can be probed
+        /// like any other trait: `caps_check!(I: IteratorItemIs[u8])` on a
+        /// concrete type, or `#[specialize(for IteratorItemIs<u8>)]`, whose
+        /// impl can then use the iterator directly.
+        pub trait IteratorItemIs<Item>: core::iter::Iterator<Item = Item> {}
+
+        impl<I: core::iter::Iterator<Item = Item> + ?Sized, Item> IteratorItemIs<Item> for I {}
+
         /// Internal trait for types with automatically detected capabilities.
         #[doc(hidden)]
         pub trait AutoCaps {
@@ -503,22 +518,15 @@ pub fn expand_impl_auto_caps(input: proc_macro2::TokenStream) -> TokenStream {
     process_list_impl(ALLOC_TRAITS, quote!{ #[cfg(feature = "alloc")] }, &ty, &mut autocaps_impl_consts, &mut insert_chain_parts, false);
     process_list_impl(STD_LIB_TRAITS, quote!{ #[cfg(feature = "std")] }, &ty, &mut autocaps_impl_consts, &mut insert_chain_parts, false);
 
-    // Build the InsertIf chain
-    // NOTE: We only include traits in the Trie (AutoCapSet) that are unconditionally available (CORE),
-    // to avoid conditional compilation complexities in structural types.
+    // Build the InsertIf chain, with feature-gated traits appended per feature.
+    let krate = quote! { tola_caps };
+    let self_ty = quote! { #ty };
     let mut insert_chain = quote! { tola_caps::trie::Empty };
-
-    // Re-build chain using ONLY unconditional traits (empty cfg)
-    for (marker, const_nm, cfg) in insert_chain_parts {
-        if !cfg.is_empty() { continue; }
-
-        insert_chain = quote! {
-            <#insert_chain as tola_caps::detect::InsertIf<
-                tola_caps::detect::#marker,
-                { <#ty as tola_caps::detect::AutoCaps>::#const_nm },
-            >>::Out
-        };
+    for list in [CORE_TRAITS, CORE_TRAITS_2, GENERIC_TRAITS] {
+        insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, list, None);
     }
+    insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, ALLOC_TRAITS, Some("alloc"));
+    insert_chain = extend_insert_chain(&krate, &self_ty, insert_chain, STD_LIB_TRAITS, Some("std"));
 
     quote! {
         impl tola_caps::detect::AutoCaps for #ty {
@@ -555,13 +563,69 @@ impl TrieNode {
     }
 }
 
+/// The crate feature a trait's detection is gated on, if any.
+fn trait_feature(name: &str) -> Option<&'static str> {
+    let in_list = |list: &[&str]| {
+        list.iter().any(|desc| TraitModel::parse_desc(desc, true).name() == name)
+    };
+    if in_list(ALLOC_TRAITS) {
+        Some("alloc")
+    } else if in_list(STD_LIB_TRAITS) {
+        Some("std")
+    } else {
+        None
+    }
+}
+
+/// Choose between `yes` and `no` by tola-caps' own `feature`.
+///
+/// Expands to a `__cfg_*!` switch so the choice is made against tola-caps'
+/// features even when the tokens end up in a downstream crate.
+fn feature_gate(krate: &TokenStream, feature: &str, yes: TokenStream, no: TokenStream) -> TokenStream {
+    let switch = format_ident!("__cfg_{}", feature);
+    quote! { #krate::#switch!({ #yes } else { #no }) }
+}
+
+/// Append `InsertIf` steps for every trait in `list` to `chain`.
+///
+/// With a `feature`, the extended chain is only selected when tola-caps has that
+/// feature; otherwise `chain` is kept unchanged.
+fn extend_insert_chain(
+    krate: &TokenStream,
+    self_ty: &TokenStream,
+    chain: TokenStream,
+    list: &[&str],
+    feature: Option<&str>,
+) -> TokenStream {
+    let mut extended = chain.clone();
+    for desc in list {
+        let model = TraitModel::parse_desc(desc, true);
+        let name = model.name().to_string();
+        let marker = marker_name(&name);
+        let const_nm = const_name(&name);
+
+        extended = quote! {
+            <#extended as #krate::detect::InsertIf<
+                #krate::detect::#marker,
+                { <#self_ty as #krate::detect::AutoCaps>::#const_nm },
+            >>::Out
+        };
+    }
+    match feature {
+        Some(feature) => feature_gate(krate, feature, extended, chain),
+        None => extended,
+    }
+}
+
 /// Helper to generate the structural generic type for a TrieNode.
 ///
 /// Generates a nested Node16 structure where:
 /// - Internal nodes are Node16<...>
 /// - Leaf nodes use Bool::If<Leaf<M>, Empty> - NO InsertIf to avoid deep recursion
 /// - Single trait per leaf slot (no collision handling - hash is unique enough)
-fn generate_trie_structure(node: &TrieNode) -> TokenStream {
+///
+/// `cond` maps a trait name to the type-level `Bool` deciding its presence.
+fn generate_trie_structure(node: &TrieNode, cond: &mut dyn FnMut(&str) -> TokenStream) -> TokenStream {
     // Check if this is a pure leaf (no children, only traits)
     let has_children = node.children.iter().any(|c| c.is_some());
     let has_traits = !node.traits.is_empty();
@@ -581,7 +645,7 @@ fn generate_trie_structure(node: &TrieNode) -> TokenStream {
         // Internal node: generate Node16<...>
         let child_types = node.children.iter().map(|child| {
             match child {
-                Some(c) => generate_trie_structure(c),
+                Some(c) => generate_trie_structure(c, &mut *cond),
                 None => quote! { $crate::trie::Empty },
             }
         });
@@ -596,17 +660,19 @@ fn generate_trie_structure(node: &TrieNode) -> TokenStream {
         // Process traits in reverse so first trait is outermost
         for name in node.traits.iter().rev() {
             let marker = marker_name(name);
-            let const_nm = const_name(name);
+            let present = cond(name);
 
             // Use Bool::If to select between Leaf<M> and previous result
-            result = quote! {
-                <<() as $crate::primitives::SelectBool<
-                    { <$T as $crate::detect::AutoCaps>::#const_nm }
-                >>::Out as $crate::primitives::Bool>::If<
+            let leaf = quote! {
+                <#present as $crate::primitives::Bool>::If<
                     $crate::trie::Leaf<$crate::detect::#marker>,
                     #result
                 >
             };
+            result = match trait_feature(name) {
+                Some(feature) => feature_gate(&quote! { $crate }, feature, leaf, result),
+                None => leaf,
+            };
         }
 
         result
@@ -643,18 +709,69 @@ fn fnv1a_128(data: &str) -> u128 {
     hash
 }
 
+/// How a trait's presence on a reference type follows from its referent.
+enum RefFact<'a> {
+    /// Implemented for every reference of this kind.
+    Always,
+    /// Never implemented for references of this kind.
+    Never,
+    /// Implemented iff the referent implements the named trait.
+    Referent(&'a str),
+}
+
+/// Reference-specific facts for `&T` (`mutable == false`) and `&mut T`.
+///
+/// Traits without an entry fall back to ordinary detection on the reference type.
+fn ref_fact(name: &str, mutable: bool) -> Option<RefFact<'_>> {
+    use RefFact::*;
+    let fact = match (name, mutable) {
+        ("Clone" | "Copy", false) => Always,
+        ("Clone" | "Copy", true) => Never,
+        ("Sized" | "Unpin" | "Deref" | "Pointer", _) => Always,
+        ("DerefMut", false) => Never,
+        ("DerefMut", true) => Always,
+        // &T: Send requires T: Sync; &mut T: Send requires T: Send
+        ("Send", false) => Referent("Sync"),
+        ("Send", true) => Referent("Send"),
+        ("Sync", _) => Referent("Sync"),
+        ("UnwindSafe", false) | ("RefUnwindSafe", _) => Referent("RefUnwindSafe"),
+        ("UnwindSafe", true) => Never,
+        ("ToString", _) => Referent("Display"),
+        ("Debug" | "Display" | "Binary" | "LowerExp" | "LowerHex" | "Octal" | "UpperExp"
+            | "UpperHex" | "Eq" | "PartialEq" | "Ord" | "PartialOrd" | "Hash", _) => Referent(name),
+        ("Error", false) => Referent(name),
+        ("Iterator" | "ExactSizeIterator" | "DoubleEndedIterator" | "FusedIterator"
+            | "FmtWrite" | "Read" | "IoWrite" | "Seek" | "BufRead", true) => Referent(name),
+        _ => return None,
+    };
+    Some(fact)
+}
+
+/// Traits a tuple implements iff every member does (up to arity 12).
+fn is_structural(name: &str) -> bool {
+    matches!(
+        name,
+        "Clone" | "Copy" | "Send" | "Sync" | "Unpin" | "UnwindSafe" | "RefUnwindSafe"
+            | "Debug" | "Default" | "Eq" | "PartialEq" | "Ord" | "PartialOrd" | "Hash"
+    )
+}
+
 /// Generate impl_auto_caps! macro.
 ///
 /// Implements `AutoCaps` (constants) and `AutoCapSet` (Trie) for a type.
 /// The Trie is a 4-layer Node16 structure sorting traits by hash nibbles.
 pub fn expand_impl_auto_caps_macro() -> TokenStream {
     let mut autocaps_impl_consts = Vec::new();
+    let mut ref_consts = [Vec::new(), Vec::new()];
+    let mut tuple_consts = Vec::new();
     let mut trie_root = TrieNode::new();
 
     fn process_list_trie(
         list: &[&str],
         cfg: TokenStream,
         consts: &mut Vec<TokenStream>,
+        ref_consts: &mut [Vec<TokenStream>; 2],
+        tuple_consts: &mut Vec<TokenStream>,
         trie: &mut TrieNode
     ) {
          for desc in list {
@@ -663,33 +780,123 @@ pub fn expand_impl_auto_caps_macro() -> TokenStream {
             let fallback = fallback_name(&name);
             let const_nm = const_name(&name);
 
-            consts.push(quote! {
+            let detected = quote! {
                 #cfg
                 #[allow(unused_imports)]
                 const #const_nm: bool = {
                     use $crate::detect::#fallback;
                     $crate::detect::Detect::<$T>::#const_nm
                 };
-            });
+            };
 
-            // Insert into Trie if unconditional (no cfg)
-            if cfg.is_empty() {
-                 let nibbles = get_nibbles(&name);
-                 trie.insert(&nibbles, name.clone());
+            for (mutable, out) in [false, true].into_iter().zip(ref_consts.iter_mut()) {
+                out.push(match ref_fact(&name, mutable) {
+                    Some(RefFact::Always) => quote! { #cfg const #const_nm: bool = true; },
+                    Some(RefFact::Never) => quote! { #cfg const #const_nm: bool = false; },
+                    Some(RefFact::Referent(inner)) => {
+                        let inner_nm = const_name(inner);
+                        quote! {
+                            #cfg
+                            const #const_nm: bool = <$Inner as $crate::detect::AutoCaps>::#inner_nm;
+                        }
+                    }
+                    None => detected.clone(),
+                });
             }
+            tuple_consts.push(if is_structural(&name) {
+                quote! {
+                    #cfg
+                    const #const_nm: bool = true $(&& <$M as $crate::detect::AutoCaps>::#const_nm)+;
+                }
+            } else if name == "Sized" {
+                quote! { const #const_nm: bool = true; }
+            } else {
+                quote! {
+                    #cfg
+                    #[allow(unused_imports)]
+                    const #const_nm: bool = {
+                        use $crate::detect::#fallback;
+                        $crate::detect::Detect::<( $($M,)+ )>::#const_nm
+                    };
+                }
+            });
+            consts.push(detected);
+
+            // Feature-gated traits get switched leaves (see generate_trie_structure)
+            let nibbles = get_nibbles(&name);
+            trie.insert(&nibbles, name.clone());
          }
     }
 
     // Process lists. EXCLUDE GENERIC_TRAITS from consts (they have no Detect const).
-    process_list_trie(CORE_TRAITS, quote!{}, &mut autocaps_impl_consts, &mut trie_root);
-    process_list_trie(CORE_TRAITS_2, quote!{}, &mut autocaps_impl_consts, &mut trie_root);
+    process_list_trie(CORE_TRAITS, quote!{}, &mut autocaps_impl_consts, &mut ref_consts, &mut tuple_consts, &mut trie_root);
+    process_list_trie(CORE_TRAITS_2, quote!{}, &mut autocaps_impl_consts, &mut ref_consts, &mut tuple_consts, &mut trie_root);
     // Skip GENERIC_TRAITS for both consts (no IS_XXX) and Trie (no capability to check without generic)
 
-    process_list_trie(ALLOC_TRAITS, quote!{ #[cfg(feature = "alloc")] }, &mut autocaps_impl_consts, &mut trie_root);
-    process_list_trie(STD_LIB_TRAITS, quote!{ #[cfg(feature = "std")] }, &mut autocaps_impl_consts, &mut trie_root);
+    process_list_trie(ALLOC_TRAITS, quote!{ #[cfg(feature = "alloc")] }, &mut autocaps_impl_consts, &mut ref_consts, &mut tuple_consts, &mut trie_root);
+    process_list_trie(STD_LIB_TRAITS, quote!{ #[cfg(feature = "std")] }, &mut autocaps_impl_consts, &mut ref_consts, &mut tuple_consts, &mut trie_root);
 
     // Generate the layered Node16 Trie type
-    let trie_type = generate_trie_structure(&trie_root);
+    let trie_type = generate_trie_structure(&trie_root, &mut |name| {
+        let const_nm = const_name(name);
+        quote! {
+            <() as $crate::primitives::SelectBool<
+                { <$T as $crate::detect::AutoCaps>::#const_nm }
+            >>::Out
+        }
+    });
+
+    // Reference tries cannot read the referent's consts in a generic impl, so
+    // delegated traits are answered by evaluating the referent's own trie.
+    let [shared_consts, mut_consts] = ref_consts;
+    let ref_trie = |mutable: bool| {
+        let mut bounds = Vec::new();
+        let trie = generate_trie_structure(&trie_root, &mut |name| match ref_fact(name, mutable) {
+            Some(RefFact::Always) => quote! { $crate::primitives::Present },
+            Some(RefFact::Referent(inner)) => {
+                let marker = marker_name(inner);
+                // A gated marker may not exist, so its bound falls back to an
+                // always-present one; the leaf itself is switched off with it.
+                let query = match trait_feature(inner) {
+                    Some(feature) => feature_gate(
+                        &quote! { $crate },
+                        feature,
+                        quote! { $crate::detect::#marker },
+                        quote! { $crate::detect::IsSized },
+                    ),
+                    None => quote! { $crate::detect::#marker },
+                };
+                let bound = quote! { $crate::trie::Evaluate<#query> };
+                if !bounds.iter().any(|b: &TokenStream| b.to_string() == bound.to_string()) {
+                    bounds.push(bound);
+                }
+                quote! {
+                    <<$Inner as $crate::detect::AutoCapSet>::Out
+                        as $crate::trie::Evaluate<#query>>::Out
+                }
+            }
+            Some(RefFact::Never) | None => quote! { $crate::primitives::Absent },
+        });
+        (trie, bounds)
+    };
+    let (shared_trie, shared_bounds) = ref_trie(false);
+    let (mut_trie, mut_bounds) = ref_trie(true);
+
+    // Tuple tries AND the members' answers for structural traits.
+    let mut tuple_bounds = Vec::new();
+    let tuple_trie = generate_trie_structure(&trie_root, &mut |name| {
+        if is_structural(name) {
+            let marker = marker_name(name);
+            tuple_bounds.push(quote! { $crate::detect::MembersEvaluate<$crate::detect::#marker> });
+            quote! {
+                <$crate::hlist![$($M),+] as $crate::detect::MembersEvaluate<$crate::detect::#marker>>::Out
+            }
+        } else if name == "Sized" {
+            quote! { $crate::primitives::Present }
+        } else {
+            quote! { $crate::primitives::Absent }
+        }
+    });
 
     quote! {
         /// Implement `AutoCaps` and `AutoCapSet` for a concrete type.
@@ -715,6 +922,56 @@ pub fn expand_impl_auto_caps_macro() -> TokenStream {
                     #(#autocaps_impl_consts)*
                 }
             };
+            // Shared reference: reference facts plus delegation to the referent
+            ( @shared_ref [ $($G:tt)* ] $T:ty => $Inner:ty ) => {
+                impl< $($G)* > $crate::detect::AutoCaps for $T
+                where
+                    $Inner: $crate::detect::AutoCaps,
+                {
+                    #(#shared_consts)*
+                }
+
+                impl< $($G)* > $crate::detect::AutoCapSet for $T
+                where
+                    $Inner: $crate::detect::AutoCapSet,
+                    <$Inner as $crate::detect::AutoCapSet>::Out: #(#shared_bounds)+*,
+                {
+                    type Out = #shared_trie;
+                }
+            };
+            // Mutable reference: never Copy/Clone, otherwise as the referent
+            ( @mut_ref [ $($G:tt)* ] $T:ty => $Inner:ty ) => {
+                impl< $($G)* > $crate::detect::AutoCaps for $T
+                where
+                    $Inner: $crate::detect::AutoCaps,
+                {
+                    #(#mut_consts)*
+                }
+
+                impl< $($G)* > $crate::detect::AutoCapSet for $T
+                where
+                    $Inner: $crate::detect::AutoCapSet,
+                    <$Inner as $crate::detect::AutoCapSet>::Out: #(#mut_bounds)+*,
+                {
+                    type Out = #mut_trie;
+                }
+            };
+            // Tuple: structural traits hold iff they hold for every member
+            ( @tuple $($M:ident),+ ) => {
+                impl< $($M),+ > $crate::detect::AutoCaps for ( $($M,)+ )
+                where
+                    $($M: $crate::detect::AutoCaps,)+
+                {
+                    #(#tuple_consts)*
+                }
+
+                impl< $($M),+ > $crate::detect::AutoCapSet for ( $($M,)+ )
+                where
+                    $crate::hlist![$($M),+]: #(#tuple_bounds)+*,
+                {
+                    type Out = #tuple_trie;
+                }
+            };
             // Generic type with Trie: for containers like Vec<T>
             ( @generic [ $($G:tt)* ] $T:ty ) => {
                 impl< $($G)* > $crate::detect::AutoCaps for $T {
@@ -7,6 +7,9 @@ use quote::quote;
 pub enum TypeKind {
     Concrete,
     Generic(&'static str),
+    /// `&T` / `&mut T`: generics, referent type.
+    SharedRef(&'static str, &'static str),
+    MutRef(&'static str, &'static str),
 }
 
 use TypeKind::*;
@@ -57,8 +60,8 @@ pub const CORE_TYPES: &[(&str, TypeKind)] = &[
     ("[T; N]", Generic("T, const N: usize")),
 
     // Pointers and references
-    ("&T", Generic("T: ?Sized")),
-    ("&mut T", Generic("T: ?Sized")),
+    ("&T", SharedRef("T: ?Sized", "T")),
+    ("&mut T", MutRef("T: ?Sized", "T")),
     ("*const T", Generic("T: ?Sized")),
     ("*mut T", Generic("T: ?Sized")),
 
@@ -143,6 +146,26 @@ pub const ALLOC_TYPES: &[(&str, TypeKind)] = &[
     ("alloc::borrow::Cow<'static, str>", Concrete),
 ];
 
+/// Generate the impl_auto_caps! invocation for one type entry.
+fn impl_auto_caps_call(ty: TokenStream, kind: TypeKind) -> TokenStream {
+    match kind {
+        Concrete => quote! { impl_auto_caps!(#ty); },
+        Generic(g) => {
+            let generics: TokenStream = g.parse().expect("Failed to parse generics");
+            quote! { impl_auto_caps!(@generic_no_set [#generics] #ty); }
+        }
+        SharedRef(g, inner) | MutRef(g, inner) => {
+            let generics: TokenStream = g.parse().expect("Failed to parse generics");
+            let inner: TokenStream = inner.parse().expect("Failed to parse referent");
+            let arm = match kind {
+                SharedRef(..) => quote! { @shared_ref },
+                _ => quote! { @mut_ref },
+            };
+            quote! { impl_auto_caps!(#arm [#generics] #ty => #inner); }
+        }
+    }
+}
+
 /// Generate impl_auto_caps! calls for all types.
 pub fn expand_impl_std_types_macro() -> TokenStream {
     let mut impls = Vec::new();
@@ -156,15 +179,7 @@ pub fn expand_impl_std_types_macro() -> TokenStream {
     // Core types
     for (path, kind) in CORE_TYPES {
         let ty: TokenStream = path.parse().unwrap();
-        match kind {
-            Concrete => {
-                impls.push(quote! { impl_auto_caps!(#ty); });
-            }
-            Generic(g) => {
-                let generics: TokenStream = g.parse().unwrap();
-                impls.push(quote! { impl_auto_caps!(@generic_no_set [#generics] #ty); });
-            }
-        }
+        impls.push(impl_auto_caps_call(ty, *kind));
     }
 
     quote! {
@@ -184,15 +199,7 @@ pub fn expand_impl_alloc_types_macro() -> TokenStream {
 
     for (path, kind) in ALLOC_TYPES {
         let ty: TokenStream = path.parse().unwrap();
-        match kind {
-            Concrete => {
-                impls.push(quote! { impl_auto_caps!(#ty); });
-            }
-            Generic(g) => {
-                let generics: TokenStream = g.parse().unwrap();
-                impls.push(quote! { impl_auto_caps!(@generic_no_set [#generics] #ty); });
-            }
-        }
+        impls.push(impl_auto_caps_call(ty, *kind));
     }
 
     quote! {
@@ -278,15 +285,7 @@ pub fn expand_impl_std_lib_types_macro() -> TokenStream {
 
     for (path, kind) in STD_TYPES {
         let ty: TokenStream = path.parse().expect(&format!("Failed to parse type: {}", path));
-        match kind {
-            Concrete => {
-                impls.push(quote! { impl_auto_caps!(#ty); });
-            }
-            Generic(g) => {
-                let generics: TokenStream = g.parse().expect("Failed to parse generics");
-                impls.push(quote! { impl_auto_caps!(@generic_no_set [#generics] #ty); });
-            }
-        }
+        impls.push(impl_auto_caps_call(ty, *kind));
     }
 
     quote! {
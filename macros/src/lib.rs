@@ -159,6 +159,13 @@ pub fn make_routing_stream(input: TokenStream) -> TokenStream {
     user::capability::expand_make_routing_stream(input.into()).into()
 }
 
+/// Internal: 32-nibble routing stream for `#[capability(deep)]`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn make_deep_routing_stream(input: TokenStream) -> TokenStream {
+    user::capability::expand_make_deep_routing_stream(input.into()).into()
+}
+
 /// Implement `AutoCaps` and `AutoCapSet` for a type.
 ///
 /// # Usage
@@ -234,17 +241,84 @@ pub fn impl_byte_eq(_input: TokenStream) -> TokenStream {
 /// #[caps_bound(requires = CanRead & (CanWrite | CanAdmin), conflicts = CanGuest)]
 /// fn secure_op<C>(doc: Doc<C>) { ... }
 ///
+/// // `conflicts = EXPR` negates the whole expression:
+/// // `conflicts = A | B` rejects either (neither may be present),
+/// // `conflicts = A & B` rejects only both together.
+/// // `conflicts_all` / `conflicts_any` state that intent over a list:
+/// #[caps_bound(requires = CanRead, conflicts_all = [CanGuest, CanBan])] // !CanGuest & !CanBan
+/// fn moderate<C>(doc: Doc<C>) { ... }
+/// #[caps_bound(requires = CanRead, conflicts_any = [MemBackend, DiskBackend])] // !(Mem & Disk)
+/// fn sync<C>(doc: Doc<C>) { ... }
+///
+/// // Exactly one of two backends
+/// #[caps_bound(requires = MemBackend ^ DiskBackend)]
+/// fn open_store<C>(doc: Doc<C>) { ... }
+///
+/// // Dependency: if Signed is present, Verified must be too
+/// #[caps_bound(requires = Signed => Verified)]
+/// fn publish<C>(doc: Doc<C>) { ... }
+///
+/// // Set queries: `Superset<S>` holds when every capability of `S` is present
+/// type AdminCaps = caps![CanRead, CanWrite, CanAdmin];
+/// #[caps_bound(requires = Has<CanAudit> & Superset<AdminCaps>)]
+/// fn audit<C>(doc: Doc<C>) { ... }
+///
 /// // Transparent mode (auto-inject generic)
 /// #[caps_bound(CanRead, transparent)]
 /// fn simple_read(doc: Doc) { ... }
+///
+/// // A bare `Doc` return gets the same set: `fn id<__C>(d: Doc<__C>) -> Doc<__C>`
+/// #[caps_bound(CanRead, transparent)]
+/// fn id(doc: Doc) -> Doc { doc }
+///
+/// // In the signature, one-argument `with![X]` / `without![X]` use `__C` as the base.
+/// // The body is not rewritten: there, `with![Validated]` is a compile error;
+/// // name the injected set as `with![__C, Validated]`.
+/// #[caps_bound(requires = CanRead, with = Validated, transparent)]
+/// fn validate(doc: Doc) -> Doc<with![Validated]> { ... }
+///
+/// // Other carrier types than `Doc`
+/// #[caps_bound(CanRead, transparent, carrier = [Node, Artifact])]
+/// fn build(node: Node) -> Artifact { ... }
+///
+/// // A different requirement per generic
+/// #[caps_bound(A: CanRead, B: CanWrite)]
+/// fn copy<A, B>(from: Doc<A>, to: Doc<B>) { ... }
+///
+/// // Same requirement on several generics
+/// #[caps_bound(requires = CanRead, target = [A, B])]
+/// fn merge<A, B>(a: Doc<A>, b: Doc<B>) { ... }
+///
+/// // Custom note on the error when a requirement is not met
+/// #[caps_bound(requires = Linked, note = "call `resolve_links()` first")]
+/// fn render<C>(doc: Doc<C>) { ... }
+///
+/// // Trait methods (with a receiver, or required): `__C` is injected
+/// // when the method has no type parameter of its own. A receiver-less
+/// // fn with a body is expanded as a free function, so it cannot sit in
+/// // a trait or impl body.
+/// trait Store {
+///     #[caps_bound(requires = CanWrite)]
+///     fn flush(&self) { ... }
+/// }
+/// // called as `store.flush::<caps![CanWrite]>()`
 /// ```
 #[proc_macro_attribute]
 pub fn caps_bound(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as user::CapsArgs);
 
+    // Free functions get per-predicate diagnostic traits next to them
     let item_clone = item.clone();
     if let Ok(func) = syn::parse::<syn::ItemFn>(item_clone.clone()) {
-        return user::expand_caps_fn(args, func);
+        if func.sig.receiver().is_none() {
+            return user::expand_caps_fn(args, func);
+        }
+    }
+
+    // Methods (a receiver, or no body) cannot host helper items next to them
+    let item_clone = item.clone();
+    if let Ok(method) = syn::parse::<syn::TraitItemFn>(item_clone.clone()) {
+        return user::expand_caps_trait_fn(args, method);
     }
 
     let item_clone = item.clone();
@@ -264,7 +338,7 @@ pub fn caps_bound(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     syn::Error::new(
         proc_macro2::Span::call_site(),
-        "caps_bound supports fn, struct, enum, or impl",
+        "caps_bound supports fn, trait method, struct, enum, or impl",
     )
     .to_compile_error()
     .into()
@@ -296,6 +370,26 @@ pub fn caps(input: TokenStream) -> TokenStream {
     user::build_capset(&types).into()
 }
 
+/// Create a capability set type with at most `N` capabilities.
+///
+/// Listing more than `N` capabilities is a compile error, which keeps a set
+/// from growing unnoticed as features are added.
+///
+/// # Usage
+/// ```ignore
+/// type Embedded = caps_limited![4; CanRead, CanWrite, CanLog];
+///
+/// type TooMany = caps_limited![2; CanRead, CanWrite, CanLog];
+/// // error: caps_limited!: 3 capabilities listed, but the limit is 2
+/// ```
+#[proc_macro]
+pub fn caps_limited(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as user::CapsLimitedInput);
+    user::expand_caps_limited(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Create a capability set type (alias for `caps!`).
 #[proc_macro]
 pub fn cap_set(input: TokenStream) -> TokenStream {
@@ -309,18 +403,77 @@ pub fn cap_set(input: TokenStream) -> TokenStream {
     user::build_capset(&types).into()
 }
 
+/// Assert that two capability sets are equal, reporting the difference.
+///
+/// Equality is always checked by the trait solver, so `m::Read` and `Read`
+/// naming the same capability compare equal. When both sets are written out
+/// (`caps![...]`, `CapSetN<...>`, or a `With` chain), a mismatch fails with a
+/// message listing the missing and extra capabilities as spelled in the call.
+/// Sets the macro cannot see into (type aliases) report a plain `caps_eq!`
+/// failure.
+///
+/// # Usage
+/// ```ignore
+/// caps_diff!(caps![CanRead, CanWrite], caps![CanRead]);
+/// // error: capability set mismatch
+/// //          missing: [CanWrite]
+/// //          extra: []
+/// ```
+#[proc_macro]
+pub fn caps_diff(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as user::CapsDiffInput);
+    user::expand_caps_diff(input).into()
+}
+
 /// Batch define capabilities with auto-generated `Cap` suffix.
 ///
 /// # Usage
 /// ```ignore
 /// define_capabilities! {
 ///     LinksChecked => "Links have been checked",
-///     LinksResolved => "Links have been resolved",
+///     LinksResolved => "Links have been resolved" requires LinksChecked,
 ///     SvgOptimized => "SVG content has been optimized",
+///     SvgRaw => "SVG content is kept raw",
+///     conflicts SvgMode(SvgOptimized, SvgRaw),
 /// }
 /// // Generates: LinksCheckedCap, LinksResolvedCap, SvgOptimizedCap
 /// // Plus HasXxxCap and NotHasXxxCap traits for each
 /// ```
+///
+/// # Dependencies
+///
+/// `requires Dep` (or `requires (DepA, DepB)`) makes `HasDepCap` a supertrait
+/// of `HasXxxCap` and generates an `XxxCapDeps` check, which fails for any set
+/// holding `XxxCap` without its prerequisites.
+///
+/// # Conflicts
+///
+/// `conflicts SvgMode(SvgOptimized, SvgRaw)` generates `SvgModeExclusive`,
+/// which fails for any set holding more than one member. Each member's
+/// `HasXxxCap` carries it as a supertrait. The name is optional; an unnamed
+/// `conflicts(A, B)` gets `ABExclusive` plus a hash of the member list.
+///
+/// The check runs where one of those traits is required, not where the set
+/// is built: `caps![SvgOptimizedCap, SvgRawCap]` on its own compiles, and
+/// fails only once it reaches a `HasSvgOptimizedCap` (or `SvgModeExclusive`)
+/// bound.
+///
+/// # Groups
+///
+/// `group LinkCaps { LinksChecked => "...", LinksResolved => "..." }` defines
+/// the member capabilities as usual and adds
+/// `pub type LinkCaps = caps![LinksCheckedCap, LinksResolvedCap];`.
+///
+/// # Aliases
+///
+/// `tola_caps::capability_alias!(NewName = OldName)` re-exports a capability
+/// under a second name with the same identity.
+///
+/// # Mixing with `#[derive(Capability)]`
+///
+/// Each `XxxCap` is an ordinary `#[derive(Capability)]` struct; there is no
+/// separate marker trait. Generated and hand-derived capabilities share one
+/// `caps!` set, and `HasXxxCap` works on any set that holds `XxxCap`.
 #[proc_macro]
 pub fn define_capabilities(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as user::DefineCapabilitiesInput);
@@ -329,7 +482,7 @@ pub fn define_capabilities(input: TokenStream) -> TokenStream {
 
 /// Derive macro to automatically implement the `Capability` trait.
 ///
-/// Computes a BLAKE3 hash of the struct name and generates a `HashStream` type.
+/// Computes a 64-bit FNV-1a hash of the module path and struct name and generates a `HashStream` type.
 ///
 /// # Usage
 /// ```ignore
@@ -342,7 +495,78 @@ pub fn define_capabilities(input: TokenStream) -> TokenStream {
 /// // Now you can use:
 /// type MyCaps = caps![CanRead, CanWrite];
 /// ```
-#[proc_macro_derive(Capability)]
+///
+/// # Explicit Streams
+///
+/// `#[capability(stream = "X0 X1 XF")]` routes by the given nibbles (padded
+/// with `X0`) instead of the name hash. `#[capability(alias_of = OtherCap)]`
+/// reuses another capability's stream and identity, so both are treated as
+/// the same capability (e.g. for migration shims).
+///
+/// ```ignore
+/// #[derive(Capability)]
+/// #[capability(alias_of = legacy::CanRead)]
+/// struct CanRead;
+/// ```
+///
+/// # Comparison Depth
+///
+/// `#[capability(max_depth = 8)]` sets `CapabilityConfig::MaxDepth`, the
+/// number of nibbles compared when checking two capabilities for equality
+/// (default `D16`). Lower values compare faster but raise the collision risk.
+///
+/// `#[capability(deep)]` hashes the key to a 32-nibble (128-bit) stream and
+/// compares it at `D32`, for large capability counts where a 64-bit collision
+/// is a real risk. It costs compile time, and cannot be combined with
+/// `max_depth`. With an explicit `stream`, up to 32 nibbles may be given.
+///
+/// ```ignore
+/// #[derive(Capability)]
+/// #[capability(deep)]
+/// struct CanAudit;
+/// ```
+///
+/// # Namespaces
+///
+/// `#[capability(namespace = "acme-auth")]` mixes the namespace into the
+/// hashed `module_path::Name` key, so same-named capabilities at the same
+/// module path in different crates get distinct streams and identities.
+/// With an explicit `stream`, only the identity changes.
+///
+/// # Versions
+///
+/// `#[capability(version = 2)]` implements `VersionedCapability`, for
+/// `HasAtLeast<Cap, MIN>` queries. Combine with `alias_of` so every version
+/// shares one identity.
+///
+/// # Presence Traits
+///
+/// `#[capability(gen_traits)]` also emits the `HasXxxCap` / `NotHasXxxCap`
+/// traits that `define_capabilities!` generates, for supertrait-style bounds
+/// (`where C: HasCanReadCap`). A trailing `Cap` in the struct name is not
+/// repeated. Not available on const-parameter capabilities.
+///
+/// ```ignore
+/// #[derive(Capability)]
+/// #[capability(gen_traits)]
+/// struct CanRead;
+///
+/// fn read<C: HasCanReadCap>(doc: Doc<C>) { /* ... */ }
+/// ```
+///
+/// # Const Parameters
+///
+/// A struct with one const parameter lists the values to implement; each is
+/// a separate capability hashed from its full name:
+///
+/// ```ignore
+/// #[derive(Capability)]
+/// #[capability(values = [4096, 8192])]
+/// struct BufferSize<const N: usize>;
+///
+/// type Caps = caps![BufferSize<4096>];
+/// ```
+#[proc_macro_derive(Capability, attributes(capability))]
 pub fn derive_capability(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
     user::expand_derive_capability(input).into()
@@ -388,25 +612,61 @@ pub fn trait_autocaps(attr: TokenStream, item: TokenStream) -> TokenStream {
     user::expand_trait_autocaps(item)
 }
 
-/// Derive macro to create capability-tracked structs with PhantomData.
+/// Attribute macro that turns a struct into a capability holder.
 ///
-/// Automatically adds a `_caps: PhantomData<C>` field and conversion methods.
+/// Adds a `C = Empty` type parameter and a `_caps: PhantomData<C>` field
+/// (each only if the struct does not already have it), plus three methods:
+/// `with_cap::<NewCap>()` returns the value typed with `<C as With<NewCap>>::Out`,
+/// `try_with_cap::<NewCap>()` keeps `C` when it already has `NewCap`
+/// (`WithIfAbsent`), and `with_caps::<C2>()` retags it with any set.
 ///
 /// # Usage
 /// ```ignore
-/// #[derive(CapHolder)]
+/// use tola_caps::prelude::*;
+/// use tola_caps::cap_holder;
+/// use core::marker::PhantomData;
+///
+/// #[derive(Capability)] struct Parsed;
+///
+/// #[cap_holder]
 /// struct Doc {
 ///     content: String,
 /// }
 ///
-/// // Expands to:
-/// // struct Doc<C = Empty> {
-/// //     content: String,
-/// //     _caps: PhantomData<C>,
-/// // }
+/// // struct Doc<C = Empty> { content: String, _caps: PhantomData<C> }
+/// let doc: Doc = Doc { content: "# Title".into(), _caps: PhantomData };
+/// let doc: Doc<caps![Parsed]> = doc.with_cap::<Parsed>();
+/// assert_eq!(doc.content, "# Title");
+/// ```
+#[proc_macro_attribute]
+pub fn cap_holder(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let _ = attr;
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    user::capability::expand_cap_holder(item).into()
+}
+
+/// Derive the capability holder methods (`with_cap`, `try_with_cap`, `with_caps`).
+///
+/// A derive cannot add fields or generics, so the struct must already
+/// declare a `C` parameter and a `PhantomData<C>` field; use
+/// [`macro@cap_holder`] to have them added.
+///
+/// # Usage
+/// ```ignore
+/// use tola_caps::prelude::*;
+/// use tola_caps::CapHolder;
+/// use core::marker::PhantomData;
+///
+/// #[derive(Capability)] struct Parsed;
+///
+/// #[derive(CapHolder)]
+/// struct Doc<C = tola_caps::Empty> {
+///     content: String,
+///     caps: PhantomData<C>,
+/// }
 ///
-/// // Now use it with capabilities:
-/// let doc: Doc<caps![Parsed]> = doc.with_caps();
+/// let doc = Doc { content: String::new(), caps: PhantomData::<tola_caps::Empty> };
+/// let _: Doc<caps![Parsed]> = doc.with_cap::<Parsed>();
 /// ```
 #[proc_macro_derive(CapHolder)]
 pub fn derive_cap_holder(input: TokenStream) -> TokenStream {
@@ -444,6 +704,10 @@ pub fn derive_cap_holder(input: TokenStream) -> TokenStream {
 /// #[specialize(T: Clone, U: Copy)]
 /// impl<T, U> Pair<T, U> for (T, U) { ... }
 ///
+/// // Iterators by item type (`tola_caps::detect::IteratorItemIs`)
+/// #[specialize(for IteratorItemIs<u8>)]
+/// impl<T> Checksum for Bytes<T> { ... }
+///
 /// // Most specific - concrete type
 /// #[specialize]
 /// impl MyTrait for String {
@@ -463,6 +727,7 @@ pub fn specialize(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - Multi-level specialization chains (A < B < C < ...)
 /// - Custom trait-to-capability mapping via `#[map(MyTrait => IsMyTrait)]`
 /// - Overlap detection with helpful error messages
+/// - `#[specialize(priority = N)]` on an impl orders equally specific impls (lower wins)
 ///
 /// # Usage
 /// ```ignore
@@ -566,6 +831,11 @@ pub fn specialization_attr(attr: TokenStream, item: TokenStream) -> TokenStream
 /// trait MyTrait {}
 /// impl MyTrait for String {}
 /// assert!(caps_check!(String: MyTrait));
+///
+/// // Generic traits take their argument in brackets; elided `&` lifetimes
+/// // are read as `'static`
+/// assert!(caps_check!(String: From[&str]));
+/// assert!(!caps_check!(u8: From[u32]));
 /// ```
 #[proc_macro]
 pub fn caps_check(input: TokenStream) -> TokenStream {
@@ -573,6 +843,132 @@ pub fn caps_check(input: TokenStream) -> TokenStream {
     expand_caps_check(input).into()
 }
 
+/// `caps_check!` for generic type parameters.
+///
+/// `caps_check!(T: Clone)` on an unbounded generic `T` cannot see `T`'s
+/// impls and quietly answers `false`. This variant instead requires
+/// `T: AutoCaps` and fails to compile without it, pointing at the missing
+/// bound. Only the AutoCaps traits (Clone, Copy, Debug, Default, Send, Sync)
+/// are accepted.
+///
+/// ```ignore
+/// use tola_caps::caps_check_generic;
+/// use tola_caps::std_caps::AutoCaps;
+///
+/// fn is_clone<T: AutoCaps>() -> bool {
+///     caps_check_generic!(T: Clone & !Copy)
+/// }
+///
+/// fn broken<T>() -> bool {
+///     caps_check_generic!(T: Clone)
+///     // error: `T` has no capability info for `caps_check_generic!`
+/// }
+/// ```
+#[proc_macro]
+pub fn caps_check_generic(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as CapsCheckInput);
+    for check in &input.checks {
+        if let Some(trait_ty) = common::first_custom_trait(&check.expr) {
+            return syn::Error::new_spanned(
+                trait_ty,
+                "caps_check_generic! only supports Clone, Copy, Debug, Default, Send and Sync; \
+                 add the trait as a bound on the generic parameter instead",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+    let requires: Vec<_> = input
+        .checks
+        .iter()
+        .map(|c| {
+            let ty = &c.ty;
+            quote::quote! { __require::<#ty>(); }
+        })
+        .collect();
+    let check = expand_caps_check(input);
+    quote::quote! {
+        {
+            fn __require<__T: ?Sized + ::tola_caps::detect::CapsCheckGeneric>() {}
+            #(#requires)*
+            #check
+        }
+    }
+    .into()
+}
+
+/// Compile-time assertion that a capability set satisfies a boolean expression.
+///
+/// Takes the same expressions as `#[caps_bound]` (`&`, `|`, `^`, `!`, `=>`,
+/// `Has<..>`, `Superset<..>`); several `Set: EXPR` checks may be separated
+/// by commas. Expands to a `const _` item, so it costs nothing at runtime
+/// and cannot name the generics of an enclosing function.
+///
+/// ```ignore
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanWrite;
+/// #[derive(Capability)] struct CanAdmin;
+///
+/// type Editor = caps![CanRead, CanWrite];
+/// caps_assert!(Editor: CanRead & (CanWrite | CanAdmin));
+/// caps_assert!(Editor: CanWrite, caps![CanAdmin]: CanAdmin);
+/// ```
+///
+/// ```ignore
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanAdmin;
+///
+/// caps_assert!(caps![CanRead]: CanAdmin);
+/// ```
+#[proc_macro]
+pub fn caps_assert(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as CapsCheckInput);
+    expand_caps_assert(input, false).into()
+}
+
+/// Compile-time assertion that a capability set does *not* satisfy an
+/// expression; the negative form of [`caps_assert!`].
+///
+/// ```ignore
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanAdmin;
+///
+/// caps_refute!(caps![CanRead]: CanAdmin);
+/// caps_refute!(caps![CanRead]: CanRead & CanAdmin);
+/// ```
+#[proc_macro]
+pub fn caps_refute(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as CapsCheckInput);
+    expand_caps_assert(input, true).into()
+}
+
+/// Compile-time snapshot of a capability set's members.
+///
+/// Fails the build with a diff of missing and unexpected names when the
+/// members of `Set` (by [`Capability::NAME`]) differ from the listed ones.
+/// Order does not matter.
+///
+/// ```ignore
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanWrite;
+///
+/// type Editor = caps![CanRead, CanWrite];
+/// caps_snapshot!(Editor => ["CanWrite", "CanRead"]);
+/// ```
+#[proc_macro]
+pub fn caps_snapshot(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as CapsSnapshotInput);
+    expand_caps_snapshot(input).into()
+}
+
 /// Define a type capability marker.
 ///
 /// # Usage
@@ -610,6 +1006,26 @@ pub fn derive_trait_cap(input: TokenStream) -> TokenStream {
     user::derive_trait_cap(input)
 }
 
+/// Generate a capability from an existing trait.
+///
+/// For `capability_from_trait!(MyTrait)` generates:
+/// - `MyTraitCap` - capability ZST whose stream is hashed from the trait's path
+/// - `HasMyTraitCap` - bridge trait with `type Cap = MyTraitCap`, implemented
+///   for every `T: MyTrait`
+///
+/// # Example
+/// ```ignore
+/// trait Persist {}
+/// capability_from_trait!(Persist);
+///
+/// type Caps = caps![PersistCap];
+/// fn cap_of<T: HasPersistCap>() -> T::Cap { Default::default() }
+/// ```
+#[proc_macro]
+pub fn capability_from_trait(input: TokenStream) -> TokenStream {
+    user::capability_from_trait(input)
+}
+
 /// Attribute macro to automatically implement AutoCaps and AutoCapSet for a type.
 ///
 /// # Usage
@@ -732,3 +1148,94 @@ fn expand_caps_check(input: CapsCheckInput) -> proc_macro2::TokenStream {
         }
     }
 }
+
+// =============================================================================
+// caps_snapshot! Implementation
+// =============================================================================
+
+/// Input for caps_snapshot!: `Set => ["Name", ...]`
+struct CapsSnapshotInput {
+    set: syn::Type,
+    names: Vec<syn::LitStr>,
+}
+
+impl syn::parse::Parse for CapsSnapshotInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let set: syn::Type = input.parse()?;
+        input.parse::<syn::Token![=>]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let names = syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(&content)?;
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        }
+        Ok(CapsSnapshotInput { set, names: names.into_iter().collect() })
+    }
+}
+
+fn expand_caps_snapshot(input: CapsSnapshotInput) -> proc_macro2::TokenStream {
+    let set = &input.set;
+    let names = &input.names;
+    let set_str = quote::quote!(#set).to_string().replace(' ', "");
+
+    quote::quote! {
+        const _: () = {
+            const __DIFF: ::tola_caps::trie::TrieDumpBuf = ::tola_caps::trie::snapshot_diff(
+                #set_str,
+                <#set as ::tola_caps::trie::CapNames>::NAMES,
+                &[#(#names),*],
+            );
+            if !__DIFF.as_str().is_empty() {
+                ::core::panic!("{}", __DIFF.as_str());
+            }
+        };
+    }
+}
+
+// =============================================================================
+// caps_assert! / caps_refute! Implementation
+// =============================================================================
+
+fn expand_caps_assert(input: CapsCheckInput, refute: bool) -> proc_macro2::TokenStream {
+    let (macro_name, verb) = if refute {
+        ("caps_refute!", "holds")
+    } else {
+        ("caps_assert!", "does not hold")
+    };
+
+    let checks = input.checks.iter().map(|check| {
+        let set = &check.ty;
+        let query = common::bool_expr_to_type(&check.expr);
+        let query = if refute {
+            quote::quote! { ::tola_caps::Not<#query> }
+        } else {
+            query
+        };
+        let expr_str = common::bool_expr_to_string(&check.expr);
+        let message = format!("{} failed: `{}` {}", macro_name, expr_str, verb);
+        let label = format!("`{}` {} for this set", expr_str, verb);
+
+        quote::quote! {
+            const _: () = {
+                #[diagnostic::on_unimplemented(
+                    message = #message,
+                    label = #label,
+                    note = "Set: {Set}"
+                )]
+                trait __CapsAssert<Set> {}
+
+                impl<Set> __CapsAssert<Set> for ::tola_caps::Present {}
+
+                fn __caps_assert<Set, Query>()
+                where
+                    Set: ::tola_caps::Evaluate<Query>,
+                    <Set as ::tola_caps::Evaluate<Query>>::Out: __CapsAssert<Set>,
+                {
+                }
+                let _ = __caps_assert::<#set, #query>;
+            };
+        }
+    });
+
+    quote::quote! { #(#checks)* }
+}
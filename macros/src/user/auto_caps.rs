@@ -52,6 +52,10 @@ fn expand_cap_on_type(input: syn::DeriveInput) -> proc_macro2::TokenStream {
 }
 
 /// Expand #[cap] on a trait definition
+///
+/// Detection only names the trait in a bound (`T: Trait<..>`), never its
+/// items, so traits with generic methods, associated types or consts, or
+/// non-object-safe signatures work as well as marker traits.
 fn expand_cap_on_trait(trait_item: ItemTrait) -> proc_macro2::TokenStream {
     let trait_name = &trait_item.ident;
     let vis = &trait_item.vis;
@@ -136,6 +140,7 @@ fn expand_cap_on_trait(trait_item: ItemTrait) -> proc_macro2::TokenStream {
 
         // Wrapper type carries ALL generic parameters (detect type + trait generics)
         #[doc(hidden)]
+        #[allow(non_camel_case_types)]
         #vis struct #detect_wrapper #wrapper_impl_generics (
             core::marker::PhantomData<(#(#phantom_types),*)>
         );
@@ -358,6 +363,7 @@ fn expand_derive_trait_cap(input: DeriveTraitCapInput) -> proc_macro2::TokenStre
 
         // Local wrapper - avoids orphan rules and cross-module collision
         #[doc(hidden)]
+        #[allow(non_camel_case_types)]
         pub struct #wrapper<T: ?Sized>(core::marker::PhantomData<T>);
 
         #[doc = #fallback_doc]
@@ -374,6 +380,60 @@ fn expand_derive_trait_cap(input: DeriveTraitCapInput) -> proc_macro2::TokenStre
     }
 }
 
+// =============================================================================
+// capability_from_trait! Function Macro
+// =============================================================================
+
+/// Turn an existing trait into a capability.
+///
+/// ```ignore
+/// trait Persist {}
+/// capability_from_trait!(Persist);
+/// // Generates: PersistCap (hashed from `module::Persist`),
+/// //            HasPersistCap bridge implemented for every `T: Persist`
+/// ```
+pub fn capability_from_trait(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as syn::Path);
+    expand_capability_from_trait(path).into()
+}
+
+fn expand_capability_from_trait(path: syn::Path) -> proc_macro2::TokenStream {
+    let Some(last) = path.segments.last() else {
+        return syn::Error::new_spanned(&path, "expected a trait path").to_compile_error();
+    };
+    let trait_ident = &last.ident;
+    let cap_marker = format_ident!("{}Cap", trait_ident);
+    let bridge = format_ident!("Has{}Cap", trait_ident);
+
+    // The trait's path (as written) is the hashed key, so the capability
+    // cannot collide with a `#[derive(Capability)]` type of the same module.
+    let trait_path = quote!(#path).to_string().replace(' ', "");
+    let name = cap_marker.to_string();
+
+    let cap_doc = format!("Capability for the `{}` trait.", trait_path);
+    let bridge_doc = format!("Implemented for every `{}` type, naming [`{}`].", trait_path, name);
+
+    quote! {
+        #[doc = #cap_doc]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct #cap_marker;
+
+        ::tola_caps::__impl_capability!(@impl #cap_marker, #name,
+            key = concat!(module_path!(), "::", #trait_path),
+            stream = ::tola_caps::make_routing_stream!(concat!(module_path!(), "::", #trait_path)));
+
+        #[doc = #bridge_doc]
+        pub trait #bridge {
+            /// The capability standing for the trait.
+            type Cap: ::tola_caps::Capability;
+        }
+
+        impl<T: ?Sized + #path> #bridge for T {
+            type Cap = #cap_marker;
+        }
+    }
+}
+
 /// Convert CamelCase to SCREAMING_SNAKE_CASE
 fn to_screaming_snake_case(s: &str) -> String {
     let mut result = String::new();
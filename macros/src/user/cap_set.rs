@@ -1,13 +1,16 @@
 //! Capability set construction and batch definition macros
 //!
 //! - `caps!` / `cap_set!` - build capability set types
+//! - `caps_limited!` - `caps!` with a maximum number of capabilities
+//! - `caps_diff!` - compare two capability sets with a readable diff
 //! - `define_capabilities!` - batch define capabilities with doc strings
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
+    spanned::Spanned,
     Ident, LitStr, Token, Type,
 };
 
@@ -26,6 +29,47 @@ impl Parse for CapsInput {
     }
 }
 
+// =============================================================================
+// caps_limited! Input Parser
+// =============================================================================
+
+/// Input for caps_limited!: `N; Cap, Cap, ...`
+pub struct CapsLimitedInput {
+    pub limit: syn::LitInt,
+    pub types: Punctuated<Type, Token![,]>,
+}
+
+impl Parse for CapsLimitedInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let limit = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let types = Punctuated::parse_terminated(input)?;
+        Ok(CapsLimitedInput { limit, types })
+    }
+}
+
+/// `caps!` with a size check: errors on the first capability past the limit
+pub fn expand_caps_limited(input: CapsLimitedInput) -> syn::Result<TokenStream2> {
+    let limit: usize = input.limit.base10_parse()?;
+    let types: Vec<_> = input.types.into_iter().collect();
+
+    if let Some(excess) = types.get(limit) {
+        return Err(syn::Error::new_spanned(
+            excess,
+            format!(
+                "caps_limited!: {} capabilities listed, but the limit is {}\n\
+                 \n\
+                 Raise the limit or split the set.",
+                types.len(),
+                limit
+            ),
+        ));
+    }
+
+    check_duplicates(&types)?;
+    Ok(build_capset(&types))
+}
+
 /// Check for duplicate capabilities in the list
 pub fn check_duplicates(types: &[Type]) -> syn::Result<()> {
     use std::collections::HashSet;
@@ -62,14 +106,156 @@ pub fn build_capset(types: &[Type]) -> TokenStream2 {
     }
 }
 
+// =============================================================================
+// caps_diff! Input Parser
+// =============================================================================
+
+/// `caps_diff!(Expected, Actual)`
+pub struct CapsDiffInput {
+    pub expected: Type,
+    pub actual: Type,
+}
+
+impl Parse for CapsDiffInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expected: Type = input.parse()?;
+        let _comma: Token![,] = input.parse()?;
+        let actual: Type = input.parse()?;
+        let _trailing: Option<Token![,]> = input.parse()?;
+        Ok(CapsDiffInput { expected, actual })
+    }
+}
+
+/// Extract capability names from a set written out in source.
+///
+/// Understands `caps![...]` / `cap_set![...]`, `Empty`, `CapSetN<...>` and
+/// `<Set as With<Cap>>::Out` chains. Returns `None` for anything else
+/// (e.g. a type alias), whose members are not visible to a proc-macro.
+pub fn capset_names(ty: &Type) -> Option<Vec<String>> {
+    match ty {
+        Type::Paren(inner) => capset_names(&inner.elem),
+        Type::Group(inner) => capset_names(&inner.elem),
+        Type::Macro(m) => {
+            let name = m.mac.path.segments.last()?.ident.to_string();
+            if name != "caps" && name != "cap_set" {
+                return None;
+            }
+            let input: CapsInput = m.mac.parse_body().ok()?;
+            Some(input.types.iter().map(cap_name).collect())
+        }
+        Type::Path(tp) => match &tp.qself {
+            // <Set as With<Cap>>::Out
+            Some(qself) => {
+                let trait_seg = tp.path.segments.iter().nth(qself.position.checked_sub(1)?)?;
+                let out_seg = tp.path.segments.last()?;
+                if trait_seg.ident != "With" || out_seg.ident != "Out" {
+                    return None;
+                }
+                let syn::PathArguments::AngleBracketed(args) = &trait_seg.arguments else {
+                    return None;
+                };
+                let Some(syn::GenericArgument::Type(cap)) = args.args.first() else {
+                    return None;
+                };
+                let mut names = capset_names(&qself.ty)?;
+                names.push(cap_name(cap));
+                Some(names)
+            }
+            None => {
+                let seg = tp.path.segments.last()?;
+                let ident = seg.ident.to_string();
+                if ident == "Empty" {
+                    return Some(Vec::new());
+                }
+                ident.strip_prefix("CapSet")?.parse::<usize>().ok()?;
+                match &seg.arguments {
+                    syn::PathArguments::None => Some(Vec::new()),
+                    syn::PathArguments::AngleBracketed(args) => Some(
+                        args.args
+                            .iter()
+                            .filter_map(|arg| match arg {
+                                syn::GenericArgument::Type(t) => Some(cap_name(t)),
+                                _ => None,
+                            })
+                            .collect(),
+                    ),
+                    syn::PathArguments::Parenthesized(_) => None,
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Display name of a capability type (whitespace stripped)
+fn cap_name(ty: &Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Compute `(missing, extra)`: members only in `expected`, members only in `actual`
+pub fn capset_diff(expected: &[String], actual: &[String]) -> (Vec<String>, Vec<String>) {
+    let missing = expected.iter().filter(|n| !actual.contains(n)).cloned().collect();
+    let extra = actual.iter().filter(|n| !expected.contains(n)).cloned().collect();
+    (missing, extra)
+}
+
+/// Format the `caps_diff!` mismatch message
+pub fn capset_diff_message(missing: &[String], extra: &[String]) -> String {
+    format!(
+        "capability set mismatch\n  missing: [{}]\n  extra: [{}]",
+        missing.join(", "),
+        extra.join(", ")
+    )
+}
+
+pub fn expand_caps_diff(input: CapsDiffInput) -> TokenStream2 {
+    let CapsDiffInput { expected, actual } = input;
+
+    // The names are compared by spelling (`m::Read` is not `Read`), so the
+    // diff only words the error; the trait solver decides equality.
+    let diff = capset_names(&expected)
+        .zip(capset_names(&actual))
+        .map(|(exp, act)| capset_diff(&exp, &act))
+        .filter(|(missing, extra)| !missing.is_empty() || !extra.is_empty());
+    let Some((missing, extra)) = diff else {
+        return quote! {
+            ::tola_caps::caps_eq!(#expected, #actual);
+        };
+    };
+
+    let message = capset_diff_message(&missing, &extra);
+    let check = quote_spanned! {actual.span()=>
+        let _ = __caps_diff::<#expected, #actual>;
+    };
+    quote! {
+        const _: () = {
+            #[diagnostic::on_unimplemented(
+                message = #message,
+                label = "capability sets differ",
+                note = "members are listed as written in the macro call"
+            )]
+            trait __CapsDiff {}
+            impl __CapsDiff for ::tola_caps::Present {}
+
+            fn __caps_diff<L: ::tola_caps::SetEq<R>, R>()
+            where
+                <L as ::tola_caps::SetEq<R>>::Out: __CapsDiff,
+            {
+            }
+            #check
+        };
+    }
+}
+
 // =============================================================================
 // define_capabilities! Input Parser
 // =============================================================================
 
-/// Single capability definition: `Name => "doc string"`
+/// Single capability definition: `Name => "doc string" [requires Dep | requires (DepA, DepB)]`
 pub struct CapDef {
     pub name: Ident,
     pub doc: LitStr,
+    pub requires: Vec<Ident>,
 }
 
 impl Parse for CapDef {
@@ -77,19 +263,123 @@ impl Parse for CapDef {
         let name: Ident = input.parse()?;
         let _arrow: Token![=>] = input.parse()?;
         let doc: LitStr = input.parse()?;
-        Ok(CapDef { name, doc })
+
+        let mut requires = Vec::new();
+        if input.peek(Ident) && input.fork().parse::<Ident>()? == "requires" {
+            let _kw: Ident = input.parse()?;
+            if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                let deps = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                requires.extend(deps);
+            } else {
+                requires.push(input.parse()?);
+            }
+        }
+
+        Ok(CapDef { name, doc, requires })
     }
 }
 
-/// Multiple capability definitions separated by commas
+/// Conflict group: `conflicts(A, B, ...)` or `conflicts NAME(A, B, ...)` -
+/// at most one member may be present
+pub struct ConflictGroup {
+    pub name: Option<Ident>,
+    pub members: Vec<Ident>,
+}
+
+impl ConflictGroup {
+    /// `NAMEExclusive` for a named group. Unnamed groups join their members
+    /// and append a hash of the member list, so `conflicts(AB, C)` and
+    /// `conflicts(A, BC)` do not both become `ABCExclusive`.
+    fn trait_name(&self) -> Ident {
+        if let Some(name) = &self.name {
+            return format_ident!("{}Exclusive", name);
+        }
+        let members: Vec<_> = self.members.iter().map(|m| m.to_string()).collect();
+        let hash = super::capability::fnv1a_64(&members.join(",")) as u32;
+        format_ident!("{}Exclusive{:08X}", members.concat(), hash)
+    }
+}
+
+/// Named group: `group NAME { A => "...", B => "..." }` - defines its members
+/// and a `type NAME = caps![ACap, BCap];` alias
+pub struct CapGroup {
+    pub name: Ident,
+    pub members: Vec<Ident>,
+}
+
+/// One entry of `define_capabilities!`
+enum CapItem {
+    Cap(CapDef),
+    Conflicts(ConflictGroup),
+    Group(CapGroup, Vec<CapDef>),
+}
+
+impl Parse for CapItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Ident) && input.peek3(syn::token::Brace) {
+            let kw: Ident = input.parse()?;
+            if kw != "group" {
+                return Err(syn::Error::new_spanned(kw, "expected `Name => \"doc\"` or `group NAME { ... }`"));
+            }
+            let name: Ident = input.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let caps: Vec<CapDef> = Punctuated::<CapDef, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+            if caps.is_empty() {
+                return Err(syn::Error::new_spanned(name, "`group` needs at least one capability"));
+            }
+            let members = caps.iter().map(|c| c.name.clone()).collect();
+            return Ok(CapItem::Group(CapGroup { name, members }, caps));
+        }
+        let named_conflicts = input.peek2(Ident) && input.peek3(syn::token::Paren);
+        if input.peek(Ident) && (input.peek2(syn::token::Paren) || named_conflicts) {
+            let kw: Ident = input.parse()?;
+            if kw != "conflicts" {
+                return Err(syn::Error::new_spanned(kw, "expected `Name => \"doc\"` or `conflicts(A, B)`"));
+            }
+            let name = if named_conflicts { Some(input.parse()?) } else { None };
+            let content;
+            syn::parenthesized!(content in input);
+            let members: Vec<Ident> = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+            if members.len() < 2 {
+                return Err(syn::Error::new_spanned(kw, "`conflicts(...)` needs at least two capabilities"));
+            }
+            return Ok(CapItem::Conflicts(ConflictGroup { name, members }));
+        }
+        Ok(CapItem::Cap(input.parse()?))
+    }
+}
+
+/// Multiple capability definitions, conflict groups and named groups separated by commas
 pub struct DefineCapabilitiesInput {
-    pub caps: Punctuated<CapDef, Token![,]>,
+    pub caps: Vec<CapDef>,
+    pub conflicts: Vec<ConflictGroup>,
+    pub groups: Vec<CapGroup>,
 }
 
 impl Parse for DefineCapabilitiesInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let caps = Punctuated::parse_terminated(input)?;
-        Ok(DefineCapabilitiesInput { caps })
+        let items = Punctuated::<CapItem, Token![,]>::parse_terminated(input)?;
+        let mut caps = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut groups = Vec::new();
+        for item in items {
+            match item {
+                CapItem::Cap(cap) => caps.push(cap),
+                CapItem::Conflicts(group) => conflicts.push(group),
+                CapItem::Group(group, members) => {
+                    caps.extend(members);
+                    groups.push(group);
+                }
+            }
+        }
+        Ok(DefineCapabilitiesInput { caps, conflicts, groups })
     }
 }
 
@@ -98,137 +388,302 @@ impl Parse for DefineCapabilitiesInput {
 // =============================================================================
 
 pub fn expand_define_capabilities(input: DefineCapabilitiesInput) -> TokenStream2 {
-    let caps: Vec<_> = input.caps.into_iter().collect();
+    let defs = input.caps.iter().map(|cap| {
+        let exclusive: Vec<_> = input
+            .conflicts
+            .iter()
+            .filter(|g| g.members.contains(&cap.name))
+            .map(ConflictGroup::trait_name)
+            .collect();
+        expand_cap_def(cap, &exclusive)
+    });
+    let groups = input.conflicts.iter().map(expand_conflict_group);
+    let aliases = input.groups.iter().map(expand_cap_group);
+    quote! {
+        #(#defs)*
+        #(#groups)*
+        #(#aliases)*
+    }
+}
 
-    // Collect all capability names for cross-recursive impls
-    let cap_names: Vec<_> = caps.iter().map(|c| &c.name).collect();
-    let cap_structs: Vec<_> = cap_names
-        .iter()
-        .map(|n| format_ident!("{}Cap", n))
-        .collect();
-    let has_traits: Vec<_> = cap_names
-        .iter()
-        .map(|n| format_ident!("Has{}Cap", n))
-        .collect();
-    let not_has_traits: Vec<_> = cap_names
-        .iter()
-        .map(|n| format_ident!("NotHas{}Cap", n))
-        .collect();
+/// Expand `group NAME { ... }` into `pub type NAME = caps![...];`
+fn expand_cap_group(group: &CapGroup) -> TokenStream2 {
+    let name = &group.name;
+    let structs: Vec<_> = group.members.iter().map(|m| format_ident!("{}Cap", m)).collect();
+    let set = build_capset(&structs.iter().map(|s| syn::parse_quote!(#s)).collect::<Vec<Type>>());
+    let doc = format!("Capability group: {}", cap_list(&structs));
+    quote! {
+        #[doc = #doc]
+        pub type #name = #set;
+    }
+}
 
-    // Generate struct definitions, trait definitions, and base impls
-    let struct_defs: Vec<_> = caps
+/// Comma-separated, backticked `XxxCap` list for diagnostics
+fn cap_list(structs: &[Ident]) -> String {
+    structs
         .iter()
-        .zip(cap_structs.iter())
-        .zip(has_traits.iter())
-        .zip(not_has_traits.iter())
-        .map(|(((cap, struct_name), has_trait), not_has_trait)| {
-            let doc = &cap.doc;
-            let name_str = cap.name.to_string();
-            let struct_name_str = struct_name.to_string();
-
-            // Pre-compute diagnostic messages as string literals
-            let has_diag_message = format!(
-                "capability `{}` is required but not available in `{{Self}}`",
-                struct_name_str
-            );
-            let has_diag_label = format!("this transform requires `{}`", struct_name_str);
-            let has_doc_trait = format!(
-                "Trait to check if a capability set contains `{}`",
-                struct_name_str
-            );
-
-            let not_has_diag_message = format!(
-                "capability `{}` must NOT be present in `{{Self}}`",
-                struct_name_str
-            );
-            let not_has_diag_label = format!(
-                "this transform must run BEFORE `{}` is added",
-                struct_name_str
-            );
-            let not_has_doc_trait = format!(
-                "Trait to check if a capability set does NOT contain `{}`",
-                struct_name_str
-            );
-
-            let doc_marker = format!("Marker: {}", doc.value());
-
-            quote! {
-                #[doc = #doc_marker]
-                #[derive(Debug, Clone, Copy, Default)]
-                pub struct #struct_name;
-
-                impl sealed::Sealed for #struct_name {}
-
-                impl Capability for #struct_name {
-                    const NAME: &'static str = #name_str;
-                }
+        .map(|d| format!("`{}`", d))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
-                // HasXxxCap trait (presence check)
-                #[doc = #has_doc_trait]
-                #[diagnostic::on_unimplemented(
-                    message = #has_diag_message,
-                    label = #has_diag_label,
-                    note = "try adding the appropriate Transform earlier in the pipeline"
-                )]
-                pub trait #has_trait: Capabilities {}
-
-                // Base case: this cap at head
-                impl<Rest: Capabilities> #has_trait for (#struct_name, Rest) {}
-
-                // NotHasXxxCap trait (absence check)
-                #[doc = #not_has_doc_trait]
-                #[doc = ""]
-                #[doc = "Use with `#[requires_not]` to enforce ordering constraints."]
-                #[diagnostic::on_unimplemented(
-                    message = #not_has_diag_message,
-                    label = #not_has_diag_label,
-                    note = "this transform must run earlier in the pipeline"
-                )]
-                pub trait #not_has_trait: Capabilities {}
-
-                // Base case: empty set does not contain any cap
-                impl #not_has_trait for () {}
-            }
-        })
-        .collect();
+/// One condition of a check trait: `query` must evaluate to `Present`.
+///
+/// `witness` is a hidden trait implemented only for `Present` that carries
+/// the diagnostic, so a failed condition reports `message` instead of a bare
+/// `Present`/`Absent` mismatch. `message` may use `{Set}` for the offending set.
+struct Check {
+    witness: Ident,
+    query: TokenStream2,
+    message: String,
+    label: String,
+    note: String,
+}
+
+/// Emit a public check trait implemented for sets meeting every condition
+/// in `checks`. Each failed condition is reported on its own.
+fn expand_check_trait(check_trait: &Ident, doc: &str, checks: &[Check]) -> TokenStream2 {
+    let witnesses = checks.iter().map(|Check { witness, message, label, note, .. }| {
+        quote! {
+            #[doc(hidden)]
+            #[diagnostic::on_unimplemented(
+                message = #message,
+                label = #label,
+                note = #note
+            )]
+            pub trait #witness<Set> {}
+
+            impl<Set> #witness<Set> for ::tola_caps::Present {}
+        }
+    });
+    let bounds = checks.iter().map(|Check { witness, query, .. }| {
+        quote! {
+            C: ::tola_caps::Evaluate<#query>,
+            <C as ::tola_caps::Evaluate<#query>>::Out: #witness<C>,
+        }
+    });
+    quote! {
+        #[doc = #doc]
+        pub trait #check_trait {}
+
+        #(#witnesses)*
+
+        impl<C> #check_trait for C
+        where
+            #(#bounds)*
+        {}
+    }
+}
+
+/// Expand one capability: marker struct, `HasXxxCap`, `NotHasXxxCap`,
+/// and (with `requires`) the `XxxCapDeps` dependency check.
+///
+/// `exclusive` lists the conflict-group checks the capability belongs to;
+/// they become extra bounds on `HasXxxCap`.
+fn expand_cap_def(cap: &CapDef, exclusive: &[Ident]) -> TokenStream2 {
+    let struct_name = format_ident!("{}Cap", cap.name);
+    let has_trait = format_ident!("Has{}Cap", cap.name);
+    let not_has_trait = format_ident!("NotHas{}Cap", cap.name);
+    let struct_name_str = struct_name.to_string();
+    let doc = &cap.doc;
+
+    let dep_structs: Vec<_> = cap.requires.iter().map(|d| format_ident!("{}Cap", d)).collect();
+    let dep_has_traits: Vec<_> = cap.requires.iter().map(|d| format_ident!("Has{}Cap", d)).collect();
+    let dep_list = cap_list(&dep_structs);
 
-    // Generate cross-recursive impls for HasXxxCap
-    let has_cross_impls: Vec<_> = has_traits
+    let has_doc_trait = if dep_structs.is_empty() {
+        format!("Trait to check if a capability set contains `{}`", struct_name_str)
+    } else {
+        format!(
+            "Trait to check if a capability set contains `{}` (and its prerequisites {})",
+            struct_name_str, dep_list
+        )
+    };
+
+    // Dependency and conflict checks run first so a violation reports their
+    // message rather than the plain presence mismatch.
+    let deps_trait = (!dep_structs.is_empty()).then(|| format_ident!("{}Deps", struct_name));
+    let bounds: Vec<_> = deps_trait
         .iter()
-        .enumerate()
-        .flat_map(|(target_idx, target_trait)| {
-            cap_structs
-                .iter()
-                .enumerate()
-                .filter(move |(other_idx, _)| *other_idx != target_idx)
-                .map(move |(_, other_struct)| {
-                    quote! {
-                        impl<Rest: #target_trait> #target_trait for (#other_struct, Rest) {}
-                    }
-                })
-        })
+        .chain(exclusive)
+        .chain(&dep_has_traits)
         .collect();
 
-    // Cross-recursive impls for NotHasXxxCap
-    let not_has_cross_impls: Vec<_> = not_has_traits
-        .iter()
-        .enumerate()
-        .flat_map(|(target_idx, target_trait)| {
-            cap_structs
+    // One condition per prerequisite, so each missing one is named on its own.
+    let deps_check = match &deps_trait {
+        None => quote! {},
+        Some(deps_trait) => {
+            let checks: Vec<_> = dep_structs
                 .iter()
-                .enumerate()
-                .filter(move |(other_idx, _)| *other_idx != target_idx)
-                .map(move |(_, other_struct)| {
-                    quote! {
-                        impl<Rest: #target_trait> #target_trait for (#other_struct, Rest) {}
-                    }
+                .map(|dep| Check {
+                    witness: format_ident!("__{}Needs{}", struct_name, dep),
+                    query: quote! { ::tola_caps::Implies<#struct_name, #dep> },
+                    message: format!(
+                        "capability `{}` requires `{}`, which is missing from `{{Set}}`",
+                        struct_name_str, dep
+                    ),
+                    label: format!("`{}` was added without `{}`", struct_name_str, dep),
+                    note: format!("add `{}` before `{}`", dep, struct_name_str),
                 })
-        })
-        .collect();
+                .collect();
+            expand_check_trait(
+                deps_trait,
+                &format!(
+                    "Dependency check: a set holding `{}` must also hold {}",
+                    struct_name_str, dep_list
+                ),
+                &checks,
+            )
+        }
+    };
+
+    let presence = expand_presence_traits(
+        &struct_name,
+        &has_trait,
+        &not_has_trait,
+        &has_doc_trait,
+        &bounds,
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, Default, ::tola_caps::Capability)]
+        pub struct #struct_name;
+
+        #presence
+
+        #deps_check
+    }
+}
+
+/// `HasXxxCap` / `NotHasXxxCap` for `cap`, shared by `define_capabilities!`
+/// and `#[capability(gen_traits)]`.
+///
+/// `bounds` become supertraits of `HasXxxCap` (dependency and conflict checks).
+pub(crate) fn expand_presence_traits(
+    cap: &Ident,
+    has_trait: &Ident,
+    not_has_trait: &Ident,
+    has_doc_trait: &str,
+    bounds: &[&Ident],
+) -> TokenStream2 {
+    let cap_str = cap.to_string();
+
+    // Pre-compute diagnostic messages as string literals
+    let has_diag_message = format!(
+        "capability `{}` is required but not available in `{{Self}}`",
+        cap_str
+    );
+    let has_diag_label = format!("this transform requires `{}`", cap_str);
+
+    let not_has_diag_message = format!(
+        "capability `{}` must NOT be present in `{{Self}}`",
+        cap_str
+    );
+    let not_has_diag_label = format!(
+        "this transform must run BEFORE `{}` is added",
+        cap_str
+    );
+    let not_has_doc_trait = format!(
+        "Trait to check if a capability set does NOT contain `{}`",
+        cap_str
+    );
+
+    let supertraits = if bounds.is_empty() {
+        quote! {}
+    } else {
+        quote! { : #(#bounds)+* }
+    };
+    let has_bounds = if bounds.is_empty() {
+        quote! {}
+    } else {
+        quote! { C: #(#bounds)+*, }
+    };
 
     quote! {
-        #(#struct_defs)*
-        #(#has_cross_impls)*
-        #(#not_has_cross_impls)*
+        // HasXxxCap trait (presence check)
+        #[doc = #has_doc_trait]
+        #[diagnostic::on_unimplemented(
+            message = #has_diag_message,
+            label = #has_diag_label,
+            note = "try adding the appropriate Transform earlier in the pipeline"
+        )]
+        pub trait #has_trait #supertraits {}
+
+        impl<C> #has_trait for C
+        where
+            C: ::tola_caps::Evaluate<#cap, Out = ::tola_caps::Present>,
+            #has_bounds
+        {}
+
+        // NotHasXxxCap trait (absence check)
+        #[doc = #not_has_doc_trait]
+        #[diagnostic::on_unimplemented(
+            message = #not_has_diag_message,
+            label = #not_has_diag_label,
+            note = "this transform must run earlier in the pipeline"
+        )]
+        pub trait #not_has_trait {}
+
+        impl<C> #not_has_trait for C
+        where
+            C: ::tola_caps::Evaluate<#cap, Out = ::tola_caps::Absent>,
+        {}
+    }
+}
+
+/// Expand `conflicts(A, B, ...)` into its `Exclusive` check (see
+/// [`ConflictGroup::trait_name`]): at most one member may be present in a set.
+fn expand_conflict_group(group: &ConflictGroup) -> TokenStream2 {
+    let structs: Vec<_> = group.members.iter().map(|m| format_ident!("{}Cap", m)).collect();
+    let list = cap_list(&structs);
+    let check_trait = group.trait_name();
+    let check = Check {
+        witness: format_ident!("__{}Met", check_trait),
+        query: quote! {
+            ::tola_caps::AtMost<
+                ::tola_caps::primitives::stream::D1,
+                ::tola_caps::hlist![#(#structs),*]
+            >
+        },
+        message: format!("capabilities {} are mutually exclusive, but `{{Set}}` holds more than one", list),
+        label: "conflicting capabilities in the same set".into(),
+        note: "remove one of the conflicting capabilities before adding the other".into(),
+    };
+    expand_check_trait(
+        &check_trait,
+        &format!("Conflict check: at most one of {} may be present", list),
+        &[check],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(src: &str) -> Vec<String> {
+        capset_names(&syn::parse_str::<Type>(src).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_capset_names_forms() {
+        assert_eq!(names("caps![A, B]"), ["A", "B"]);
+        assert_eq!(names("tola_caps::Empty"), Vec::<String>::new());
+        assert_eq!(names("CapSet2<A, B>"), ["A", "B"]);
+        assert_eq!(names("<<Empty as With<A>>::Out as With<B>>::Out"), ["A", "B"]);
+        assert!(capset_names(&syn::parse_str::<Type>("MyCaps").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_removed_capability_is_named() {
+        let expected = names("caps![CanRead, CanWrite, CanExec]");
+        let actual = names("caps![CanRead, CanExec]");
+        let (missing, extra) = capset_diff(&expected, &actual);
+        assert_eq!(missing, ["CanWrite"]);
+        assert!(extra.is_empty());
+        assert_eq!(
+            capset_diff_message(&missing, &extra),
+            "capability set mismatch\n  missing: [CanWrite]\n  extra: []"
+        );
     }
 }
@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::DeriveInput;
+use quote::{format_ident, quote};
+use syn::{parse::Parser, DeriveInput};
 
 
 /// #[derive(Capability)] generates a call to the declarative macro bridge.
@@ -10,18 +10,323 @@ use syn::DeriveInput;
 /// 1. #[derive(Capability)] (proc-macro) -> generates __impl_capability! call
 /// 2. __impl_capability! (decl-macro) -> passes concat!() to proc-macro
 /// 3. make_routing_stream! / __internal_make_identity! (proc-macro) -> receives expanded string
+///
+/// `#[capability(stream = "X0 X1 XF")]` or `#[capability(alias_of = Other)]`
+/// replace the name hash with an explicit routing stream.
+/// `#[capability(namespace = "acme-auth")]` mixes a namespace into the hashed
+/// key (stream and identity), isolating same-named capabilities.
+/// `#[capability(version = 2)]` adds a `VersionedCapability` impl.
+/// `#[capability(values = [4096, 8192])]` on `struct BufferSize<const N: usize>`
+/// implements one capability per listed `N`, each hashed from its full name
+/// (`BufferSize<4096>`).
+/// `#[capability(gen_traits)]` also emits `HasXxxCap` / `NotHasXxxCap`, as
+/// `define_capabilities!` does.
 pub fn expand_derive_capability(input: DeriveInput) -> TokenStream2 {
+    let attr = match parse_capability_attr(&input) {
+        Ok(attr) => attr,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    if !input.generics.params.is_empty() || attr.values.is_some() {
+        return expand_const_capabilities(&input, attr);
+    }
+
     let ident = &input.ident;
     let ident_str = ident.to_string();
 
+    let max_depth = attr.max_depth.map(|depth| {
+        let depth = format_ident!("D{}", depth);
+        quote! { , max_depth = ::tola_caps::primitives::stream::#depth }
+    });
+
+    let namespace = attr.namespace.map(|ns| quote! { , namespace = #ns });
+
+    let version = attr.version.map(|version| {
+        let peano = format_ident!("D{}", version);
+        quote! {
+            impl ::tola_caps::VersionedCapability for #ident {
+                const VERSION: u32 = #version;
+                type Version = ::tola_caps::primitives::stream::#peano;
+            }
+        }
+    });
+
     // Generate a call to the declarative macro bridge.
     // The concat!(module_path!(), ...) will be expanded BEFORE the inner proc-macros run.
+    let bridge = match attr.stream {
+        None if attr.deep => quote! {
+            ::tola_caps::__impl_capability!(#ident, #ident_str #namespace, deep);
+        },
+        None => quote! {
+            ::tola_caps::__impl_capability!(#ident, #ident_str #namespace #max_depth);
+        },
+        Some(StreamOverride::Nibbles(nibbles)) => {
+            let stream = nibble_stream(&nibbles);
+            quote! {
+                ::tola_caps::__impl_capability!(
+                    #ident, #ident_str,
+                    stream = #stream
+                    #namespace
+                    #max_depth
+                );
+            }
+        }
+        Some(StreamOverride::AliasOf(other)) => quote! {
+            ::tola_caps::__impl_capability!(#ident, #ident_str, alias_of = #other);
+        },
+    };
+
+    let traits = attr.gen_traits.then(|| presence_traits(ident));
+
     quote! {
-        ::tola_caps::__impl_capability!(#ident, #ident_str);
+        #bridge
+        #version
+        #traits
+    }
+}
+
+/// `HasXxxCap` / `NotHasXxxCap` for `#[capability(gen_traits)]`; a trailing
+/// `Cap` in the struct name is not doubled (`CanReadCap` -> `HasCanReadCap`).
+fn presence_traits(ident: &syn::Ident) -> TokenStream2 {
+    let name = ident.to_string();
+    let base = name.strip_suffix("Cap").filter(|b| !b.is_empty()).unwrap_or(&name);
+    let has_trait = format_ident!("Has{}Cap", base);
+    let not_has_trait = format_ident!("NotHas{}Cap", base);
+    let has_doc = format!("Trait to check if a capability set contains `{}`", name);
+    super::cap_set::expand_presence_traits(ident, &has_trait, &not_has_trait, &has_doc, &[])
+}
+
+/// One capability per `values = [...]` entry of a single-const-parameter struct
+fn expand_const_capabilities(input: &DeriveInput, attr: CapabilityAttr) -> TokenStream2 {
+    let ident = &input.ident;
+    let params = &input.generics.params;
+    let single_const = params.len() == 1 && matches!(params[0], syn::GenericParam::Const(_));
+    let Some(values) = attr.values.filter(|_| single_const) else {
+        return syn::Error::new_spanned(
+            ident,
+            "generic capabilities need exactly one const parameter and \
+             `#[capability(values = [...])]` listing the values to implement",
+        )
+        .to_compile_error();
+    };
+    if attr.gen_traits {
+        return syn::Error::new_spanned(ident, "`gen_traits` is not supported on generic capabilities")
+            .to_compile_error();
+    }
+    if attr.version.is_some() || matches!(attr.stream, Some(StreamOverride::AliasOf(_))) {
+        return syn::Error::new_spanned(ident, "`values` cannot be combined with `version` or `alias_of`")
+            .to_compile_error();
+    }
+
+    let max_depth = attr.max_depth.map(|depth| {
+        let depth = format_ident!("D{}", depth);
+        quote! { , max_depth = ::tola_caps::primitives::stream::#depth }
+    });
+    let namespace = attr.namespace.map(|ns| quote! { , namespace = #ns });
+    let stream = match attr.stream {
+        Some(StreamOverride::Nibbles(nibbles)) => {
+            let stream = nibble_stream(&nibbles);
+            Some(quote! { , stream = #stream })
+        }
+        _ => None,
+    };
+    // Hashed deep streams take the `deep` bridge arm, which sets the depth itself
+    let (max_depth, deep) = if attr.deep && stream.is_none() {
+        (None, Some(quote! { , deep }))
+    } else {
+        (max_depth, None)
+    };
+
+    let impls = values.iter().map(|value| {
+        let digits = match value {
+            syn::Lit::Int(lit) => lit.base10_digits().to_string(),
+            syn::Lit::Bool(lit) => lit.value.to_string(),
+            syn::Lit::Char(lit) => format!("{:?}", lit.value()),
+            _ => unreachable!("checked when parsing `values`"),
+        };
+        let name = format!("{}<{}>", ident, digits);
+        quote! {
+            ::tola_caps::__impl_capability!(#ident<#value>, #name #stream #namespace #max_depth #deep);
+        }
+    });
+
+    quote! { #(#impls)* }
+}
+
+/// Explicit routing from `#[capability(...)]`
+enum StreamOverride {
+    /// `stream = "X0 X1 XF"`, padded with `X0` to 16 nibbles
+    Nibbles(Vec<u8>),
+    /// `alias_of = OtherCap`: reuse another capability's stream and identity
+    AliasOf(syn::Type),
+}
+
+/// Parsed `#[capability(...)]` options
+#[derive(Default)]
+struct CapabilityAttr {
+    stream: Option<StreamOverride>,
+    /// `max_depth = N`: nibbles compared for collision resolution (`CapabilityConfig`)
+    max_depth: Option<u32>,
+    /// `namespace = "..."`: mixed into the hashed key
+    namespace: Option<syn::LitStr>,
+    /// `version = N`: implements `VersionedCapability`
+    version: Option<u32>,
+    /// `values = [..]`: const parameter values to implement
+    values: Option<Vec<syn::Lit>>,
+    /// `deep`: 32-nibble stream compared at `D32`
+    deep: bool,
+    /// `gen_traits`: emit `HasXxxCap` / `NotHasXxxCap`
+    gen_traits: bool,
+}
+
+fn parse_capability_attr(input: &DeriveInput) -> syn::Result<CapabilityAttr> {
+    let mut result = CapabilityAttr::default();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("capability")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("deep") {
+                result.deep = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("gen_traits") {
+                result.gen_traits = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("max_depth") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                let depth: u32 = lit.base10_parse()?;
+                if depth == 0 || depth > 64 {
+                    return Err(syn::Error::new_spanned(lit, "max_depth must be between 1 and 64"));
+                }
+                result.max_depth = Some(depth);
+                return Ok(());
+            }
+            if meta.path.is_ident("namespace") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                if lit.value().is_empty() {
+                    return Err(syn::Error::new_spanned(lit, "namespace must not be empty"));
+                }
+                result.namespace = Some(lit);
+                return Ok(());
+            }
+            if meta.path.is_ident("version") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                let version: u32 = lit.base10_parse()?;
+                if version > 64 {
+                    return Err(syn::Error::new_spanned(lit, "version must be between 0 and 64"));
+                }
+                result.version = Some(version);
+                return Ok(());
+            }
+            if meta.path.is_ident("values") {
+                let value = meta.value()?;
+                let content;
+                syn::bracketed!(content in value);
+                let lits = syn::punctuated::Punctuated::<syn::Lit, syn::Token![,]>::parse_terminated(&content)?;
+                for lit in &lits {
+                    if !matches!(lit, syn::Lit::Int(_) | syn::Lit::Bool(_) | syn::Lit::Char(_)) {
+                        return Err(syn::Error::new_spanned(lit, "expected an integer, `bool` or `char` value"));
+                    }
+                }
+                if lits.is_empty() {
+                    return Err(meta.error("`values` must list at least one value"));
+                }
+                result.values = Some(lits.into_iter().collect());
+                return Ok(());
+            }
+            if result.stream.is_some() {
+                return Err(meta.error("only one of `stream` / `alias_of` may be given"));
+            }
+            if meta.path.is_ident("stream") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                result.stream = Some(StreamOverride::Nibbles(parse_nibbles(&lit)?));
+                Ok(())
+            } else if meta.path.is_ident("alias_of") {
+                result.stream = Some(StreamOverride::AliasOf(meta.value()?.parse()?));
+                Ok(())
+            } else {
+                Err(meta.error("expected `stream = \"...\"`, `alias_of = Type`, `namespace = \"...\"`, `max_depth = N`, `deep`, `gen_traits`, `version = N` or `values = [..]`"))
+            }
+        })?;
+    }
+
+    if result.max_depth.is_some() && matches!(result.stream, Some(StreamOverride::AliasOf(_))) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`alias_of` uses the aliased capability's max_depth",
+        ));
+    }
+
+    if result.namespace.is_some() && matches!(result.stream, Some(StreamOverride::AliasOf(_))) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`alias_of` uses the aliased capability's identity; drop `namespace`",
+        ));
+    }
+
+    if result.deep {
+        if result.max_depth.is_some() || matches!(result.stream, Some(StreamOverride::AliasOf(_))) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`deep` always compares 32 nibbles; drop `max_depth` / `alias_of`",
+            ));
+        }
+        result.max_depth = Some(32);
+    }
+
+    if let Some(StreamOverride::Nibbles(nibbles)) = &mut result.stream {
+        let len = if result.deep { 32 } else { 16 };
+        if nibbles.len() > len {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "stream has more than 16 nibbles; add `deep` for up to 32",
+            ));
+        }
+        nibbles.resize(len, 0);
+    }
+
+    Ok(result)
+}
+
+/// Parse `"X0 X1 XF"` (the `X` prefix is optional) into up to 32 nibbles;
+/// padding happens once `deep` is known.
+fn parse_nibbles(lit: &syn::LitStr) -> syn::Result<Vec<u8>> {
+    let mut nibbles = Vec::new();
+    for word in lit.value().split_whitespace() {
+        let digit = word.strip_prefix('X').unwrap_or(word);
+        let nibble = u8::from_str_radix(digit, 16)
+            .ok()
+            .filter(|_| digit.len() == 1)
+            .ok_or_else(|| syn::Error::new_spanned(lit, format!("invalid nibble `{}`, expected X0..XF", word)))?;
+        nibbles.push(nibble);
+    }
+    if nibbles.is_empty() || nibbles.len() > 32 {
+        return Err(syn::Error::new_spanned(lit, "stream must have 1 to 16 nibbles (32 with `deep`)"));
+    }
+    Ok(nibbles)
+}
+
+/// `HashStream16`, or `HashStream32` for 32 nibbles
+fn nibble_stream(nibbles: &[u8]) -> TokenStream2 {
+    if nibbles.len() == 32 {
+        quote! { ::tola_caps::primitives::stream::HashStream32<#(#nibbles),*> }
+    } else {
+        quote! { ::tola_caps::primitives::stream::HashStream16<#(#nibbles),*> }
     }
 }
 
 // Logic for make_routing_stream macro
+/// `HashStream32` of the key for `#[capability(deep)]`, hashed by
+/// `const_utils::hash_nibble_deep` (input may be a literal or `concat!`).
+pub fn expand_make_deep_routing_stream(input: TokenStream2) -> TokenStream2 {
+    let nibbles = (0..32u8).map(|i| quote! {
+        { ::tola_caps::primitives::const_utils::hash_nibble_deep(#input, #i) }
+    });
+    quote! {
+        ::tola_caps::primitives::stream::HashStream32<#(#nibbles),*>
+    }
+}
+
 pub fn expand_make_routing_stream(input: TokenStream2) -> TokenStream2 {
     // 1. Try to parse as string literal first (direct case)
     if let Ok(lit) = syn::parse2::<syn::LitStr>(input.clone()) {
@@ -65,7 +370,7 @@ pub fn expand_make_routing_stream(input: TokenStream2) -> TokenStream2 {
 }
 
 // Helpers needed
-fn fnv1a_64(s: &str) -> u64 {
+pub(crate) fn fnv1a_64(s: &str) -> u64 {
     let mut hash: u64 = 0xcbf29ce484222325;
     for b in s.as_bytes() {
         hash ^= *b as u64;
@@ -83,7 +388,7 @@ fn fnv1a_64(s: &str) -> u64 {
 /// - >64 chars:  IListSampled (head32 + mid16 + tail16)
 ///
 /// Example:
-/// "ab" (2 chars) → IList8<IList<C<'a'>, IList<C<'b'>, INil>>>
+/// "ab" (2 chars) → IList8<IList<Byte<X6, X1>, IList<Byte<X6, X2>, INil>>>
 /// "very_long_module_path::VeryLongTypeName" (>64) → IListSampled<...sampled...>
 pub fn expand_make_identity(input: TokenStream2) -> TokenStream2 {
     // Try to parse as string literal
@@ -121,12 +426,20 @@ pub fn generate_identity_tiered(s: &str) -> TokenStream2 {
         ("IListSampled", result)
     };
 
-    // Build IList from right to left
+    // Build IList from right to left.
+    // Each char becomes a nibble-pair `Byte` so identities can be compared
+    // with `IdentityEq` (non-ASCII maps to '?', as in `str_char_at`).
     let mut ilist = quote! { ::tola_caps::primitives::const_utils::INil };
     for ch in sampled.iter().rev() {
+        let byte = if ch.is_ascii() { *ch as u8 } else { b'?' };
+        let hi = format_ident!("X{:X}", byte >> 4);
+        let lo = format_ident!("X{:X}", byte & 0xF);
         ilist = quote! {
             ::tola_caps::primitives::const_utils::IList<
-                ::tola_caps::primitives::const_utils::C<#ch>,
+                ::tola_caps::primitives::identity::Byte<
+                    ::tola_caps::primitives::nibble::#hi,
+                    ::tola_caps::primitives::nibble::#lo
+                >,
                 #ilist
             >
         };
@@ -144,14 +457,16 @@ fn generate_identity_const_fn(input: TokenStream2) -> TokenStream2 {
     let mut ilist = quote! { ::tola_caps::primitives::const_utils::INil };
 
     for i in (0usize..64).rev() {
-        let idx = syn::Index::from(i);
         ilist = quote! {
             ::tola_caps::primitives::const_utils::IList<
-                ::tola_caps::primitives::const_utils::C<{
-                    let s = #input;
-                    let indices = ::tola_caps::primitives::const_utils::sample_indices_64(s.len());
-                    ::tola_caps::primitives::const_utils::str_char_at(s, indices[#idx])
-                }>,
+                ::tola_caps::primitives::identity::Byte<
+                    <() as ::tola_caps::primitives::stream::SelectNibble<{
+                        ::tola_caps::primitives::const_utils::sampled_nibble(#input, #i, 0)
+                    }>>::Out,
+                    <() as ::tola_caps::primitives::stream::SelectNibble<{
+                        ::tola_caps::primitives::const_utils::sampled_nibble(#input, #i, 1)
+                    }>>::Out
+                >,
                 #ilist
             >
         };
@@ -202,22 +517,50 @@ pub fn expand_make_identity_bytes(input: TokenStream2) -> TokenStream2 {
     }
 }
 
-/// Derive macro that adds a phantom capability field to a struct.
-pub fn expand_derive_cap_holder(input: syn::DeriveInput) -> TokenStream2 {
-    let name = &input.ident;
-    let vis = &input.vis;
-    let attrs = &input.attrs;
+/// `#[cap_holder]`: add a `C = Empty` parameter and a `_caps: PhantomData<C>`
+/// field to a struct (each only if absent), plus the [`cap_holder_impl`] methods.
+pub fn expand_cap_holder(mut item: syn::ItemStruct) -> TokenStream2 {
+    let cap = format_ident!("C");
 
-    // Get existing generics
-    let mut generics = input.generics.clone();
+    if !has_type_param(&item.generics, &cap) {
+        item.generics.params.push(syn::parse_quote!(C = ::tola_caps::Empty));
+    }
 
-    // Add C generic with default
-    let cap_generic: syn::GenericParam = syn::parse_quote!(C = ::tola_caps::Empty);
-    generics.params.push(cap_generic);
+    if phantom_field(&item.fields, &cap).is_none() {
+        match &mut item.fields {
+            syn::Fields::Named(named) => {
+                named.named.push(syn::parse_quote! {
+                    _caps: ::core::marker::PhantomData<C>
+                });
+            }
+            syn::Fields::Unnamed(unnamed) => {
+                unnamed.unnamed.push(syn::Field::parse_unnamed
+                    .parse2(quote! { ::core::marker::PhantomData<C> })
+                    .expect("PhantomData<C> is a valid field"));
+            }
+            syn::Fields::Unit => {
+                item.fields = syn::Fields::Named(syn::parse_quote!({
+                    _caps: ::core::marker::PhantomData<C>
+                }));
+                item.semi_token = None;
+            }
+        }
+    }
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let methods = match cap_holder_impl(&item.ident, &item.generics, &item.fields) {
+        Ok(methods) => methods,
+        Err(err) => return err.to_compile_error(),
+    };
 
-    // Extract fields from struct
+    quote! {
+        #item
+        #methods
+    }
+}
+
+/// `#[derive(CapHolder)]`: the [`cap_holder_impl`] methods for a struct that
+/// already declares `C` and a `PhantomData<C>` field.
+pub fn expand_derive_cap_holder(input: syn::DeriveInput) -> TokenStream2 {
     let fields = match &input.data {
         syn::Data::Struct(data) => &data.fields,
         _ => {
@@ -226,45 +569,107 @@ pub fn expand_derive_cap_holder(input: syn::DeriveInput) -> TokenStream2 {
         }
     };
 
-    // Generate field definitions with added _caps field
-    let (field_defs, field_names, _field_types): (Vec<_>, Vec<_>, Vec<_>) = match fields {
-        syn::Fields::Named(named) => {
-            let defs: Vec<_> = named.named.iter().map(|f| {
-                let name = &f.ident;
-                let ty = &f.ty;
-                let vis = &f.vis;
-                let attrs = &f.attrs;
-                quote! { #(#attrs)* #vis #name: #ty }
-            }).collect();
-            let names: Vec<_> = named.named.iter().map(|f| f.ident.clone().unwrap()).collect();
-            let types: Vec<_> = named.named.iter().map(|f| f.ty.clone()).collect();
-            (defs, names, types)
-        }
-        _ => {
-            return syn::Error::new_spanned(&input, "CapHolder only supports named fields")
-                .to_compile_error();
-        }
-    };
+    let cap = format_ident!("C");
+    if !has_type_param(&input.generics, &cap) || phantom_field(fields, &cap).is_none() {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "CapHolder needs a `C` type parameter and a `PhantomData<C>` field; \
+             use `#[cap_holder]` to add them",
+        )
+        .to_compile_error();
+    }
 
-    // Generate the conversion helper methods
-    let field_copies: Vec<_> = field_names.iter().map(|n| quote! { #n: self.#n }).collect();
+    cap_holder_impl(&input.ident, &input.generics, fields).unwrap_or_else(|err| err.to_compile_error())
+}
 
-    quote! {
-        #(#attrs)*
-        #vis struct #name #impl_generics #where_clause {
-            #(#field_defs,)*
-            _caps: ::core::marker::PhantomData<C>,
+fn has_type_param(generics: &syn::Generics, ident: &syn::Ident) -> bool {
+    generics.type_params().any(|p| &p.ident == ident)
+}
+
+/// The field whose type is `PhantomData<C>`.
+fn phantom_field(fields: &syn::Fields, cap: &syn::Ident) -> Option<syn::Member> {
+    fields.iter().enumerate().find_map(|(i, field)| {
+        let syn::Type::Path(type_path) = &field.ty else { return None };
+        let last = type_path.path.segments.last()?;
+        let syn::PathArguments::AngleBracketed(args) = &last.arguments else { return None };
+        let is_phantom = last.ident == "PhantomData"
+            && args.args.len() == 1
+            && matches!(&args.args[0], syn::GenericArgument::Type(syn::Type::Path(p)) if p.path.is_ident(cap));
+        is_phantom.then(|| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        })
+    })
+}
+
+/// `with_caps::<C2>()` (retag with any set) and `with_cap::<NewCap>()`
+/// (add one capability) on a capability holder.
+fn cap_holder_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &syn::Fields,
+) -> syn::Result<TokenStream2> {
+    let cap = format_ident!("C");
+    let phantom = phantom_field(fields, &cap)
+        .ok_or_else(|| syn::Error::new_spanned(name, "missing `PhantomData<C>` field"))?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The type arguments of `name`, with `C` replaced by `replacement`
+    let retag = |replacement: TokenStream2| {
+        let args = generics.params.iter().map(|param| match param {
+            syn::GenericParam::Type(t) if t.ident == cap => replacement.clone(),
+            syn::GenericParam::Type(t) => {
+                let ident = &t.ident;
+                quote! { #ident }
+            }
+            syn::GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+                quote! { #lifetime }
+            }
+            syn::GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote! { #ident }
+            }
+        });
+        quote! { #name<#(#args),*> }
+    };
+    let retagged = retag(quote! { __C2 });
+    let with_out = retag(quote! { <__NewCap as ::tola_caps::AddTo<C>>::Out });
+    let try_with_out = retag(quote! { <__NewCap as ::tola_caps::AddIfAbsentTo<C>>::Out });
+
+    let moves = fields.iter().enumerate().map(|(i, field)| {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        };
+        if member == phantom {
+            quote! { #member: ::core::marker::PhantomData }
+        } else {
+            quote! { #member: self.#member }
         }
+    });
 
-        impl<C> #name<C> {
-            /// Convert to a new capability set (internal use).
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Retag with another capability set, keeping every other field.
             #[inline]
-            pub fn with_caps<C2>(self) -> #name<C2> {
+            pub fn with_caps<__C2>(self) -> #retagged {
                 #name {
-                    #(#field_copies,)*
-                    _caps: ::core::marker::PhantomData,
+                    #(#moves,)*
                 }
             }
+
+            /// Add `__NewCap` to the capability set.
+            #[inline]
+            pub fn with_cap<__NewCap: ::tola_caps::AddTo<C>>(self) -> #with_out {
+                self.with_caps()
+            }
+
+            /// Add `__NewCap` unless the set already has it (`WithIfAbsent`).
+            #[inline]
+            pub fn try_with_cap<__NewCap: ::tola_caps::AddIfAbsentTo<C>>(self) -> #try_with_out {
+                self.with_caps()
+            }
         }
-    }
+    })
 }
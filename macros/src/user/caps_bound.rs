@@ -1,6 +1,6 @@
 //! Capability constraint macro: #[caps_bound]
 //!
-//! Add capability constraints to functions, structs, enums, and impl blocks.
+//! Add capability constraints to functions, trait methods, structs, enums, and impl blocks.
 //!
 //! # Syntax (Unified)
 //!
@@ -9,6 +9,18 @@
 //! #[caps_bound(C: Parsed & Validated)]
 //! fn process<C>(doc: Doc<C>) { ... }
 //!
+//! // Each `T: expr` bounds only its own generic
+//! #[caps_bound(A: CanRead, B: CanWrite)]
+//! fn copy<A, B>(from: Doc<A>, to: Doc<B>) { ... }
+//!
+//! // Apply the same bound to several generics
+//! #[caps_bound(requires = CanRead, target = [A, B])]
+//! fn merge<A, B>(a: Doc<A>, b: Doc<B>) { ... }
+//!
+//! // Require a whole named set (`Superset<S>`: every capability of S)
+//! #[caps_bound(C: CanAudit & Superset<AdminCaps>)]
+//! fn audit<C>(doc: Doc<C>) { ... }
+//!
 //! // Add capabilities with 'with'
 //! #[caps_bound(C: Parsed, with(Validated))]
 //! fn validate<C>(doc: Doc<C>) -> Doc<with![C, Validated]> { ... }
@@ -16,6 +28,19 @@
 //! // Remove capabilities with 'without'
 //! #[caps_bound(C: Admin, without(Admin))]
 //! fn drop_admin<C>(user: User<C>) -> User<without![C, Admin]> { ... }
+//!
+//! // `conflicts = EXPR` requires `!(EXPR)`: `A | B` means neither, `A & B` not both.
+//! // conflicts_any / conflicts_all spell these out over a list.
+//! #[caps_bound(C: Draft, conflicts_any = [Signed, Sealed])]   // !(Signed & Sealed)
+//! #[caps_bound(C: Draft, conflicts_all = [Signed, Sealed])]   // !Signed & !Sealed
+//!
+//! // Transparent injection into carriers other than `Doc`
+//! #[caps_bound(CanRead, transparent, carrier = [Node, Artifact])]
+//! fn build(node: Node) -> Artifact { ... }
+//!
+//! // Custom guidance in the error note (functions only)
+//! #[caps_bound(C: Linked, note = "call `resolve_links()` first")]
+//! fn render<C>(doc: Doc<C>) { ... }
 //! ```
 
 use proc_macro::TokenStream;
@@ -23,24 +48,35 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Ident, ItemFn, Token, Type,
+    Ident, ItemFn, Token, TraitItemFn, Type,
 };
 
 use crate::common::{bool_expr_to_string, bool_expr_to_type, BoolExpr, peek_generic_constraint};
 
 // Keywords excluded from generic constraint detection
-const CAPS_BOUND_KEYWORDS: &[&str] = &["with", "without", "transparent", "requires", "conflicts", "target"];
+const CAPS_BOUND_KEYWORDS: &[&str] = &[
+    "with", "without", "transparent", "requires", "conflicts", "conflicts_any", "conflicts_all", "target", "note",
+    "carrier",
+];
 
 // =============================================================================
 // CapsArgs - Attribute Arguments Parser
 // =============================================================================
 
 pub struct CapsArgs {
-    pub predicates: Vec<BoolExpr>,
+    /// Predicates with the generic they bound (`T: expr`), or `None` for the
+    /// attribute-wide ones (`requires`, `conflicts*`, positional)
+    pub predicates: Vec<(Option<Ident>, BoolExpr)>,
     pub with_caps: Vec<Type>,
     pub without_caps: Vec<Type>,
     pub transparent: bool,
-    pub target: Option<syn::Ident>,
+    /// Generics the attribute-wide bounds apply to (`target = ..`); empty
+    /// means "pick a default"
+    pub targets: Vec<syn::Ident>,
+    /// Overrides the diagnostic note on unmet requirements
+    pub note: Option<syn::LitStr>,
+    /// Type names rewritten by transparent injection; empty means `Doc`
+    pub carriers: Vec<syn::Ident>,
 }
 
 impl Parse for CapsArgs {
@@ -49,7 +85,9 @@ impl Parse for CapsArgs {
         let mut with_caps = Vec::new();
         let mut without_caps = Vec::new();
         let mut transparent = false;
-        let mut target = None;
+        let mut targets = Vec::new();
+        let mut note = None;
+        let mut carriers = Vec::new();
 
         while !input.is_empty() {
             // 1. New unified syntax: T: BoolExpr (generic constraint)
@@ -57,8 +95,7 @@ impl Parse for CapsArgs {
                 let t: Ident = input.parse()?;
                 input.parse::<Token![:]>()?;
                 let expr: BoolExpr = input.parse()?;
-                target = Some(t);
-                predicates.push(expr);
+                predicates.push((Some(t), expr));
             }
             // 2. Legacy: key = value syntax (for backwards compatibility)
             else if input.peek(Ident) && input.peek2(Token![=]) {
@@ -66,10 +103,21 @@ impl Parse for CapsArgs {
                 input.parse::<Token![=]>()?;
 
                 if key == "requires" {
-                    predicates.push(input.parse()?);
+                    predicates.push((None, input.parse()?));
                 } else if key == "conflicts" {
                     let conflict: BoolExpr = input.parse()?;
-                    predicates.push(BoolExpr::Not(Box::new(conflict)));
+                    predicates.push((None, BoolExpr::Not(Box::new(conflict))));
+                } else if key == "conflicts_any" {
+                    // Not all present: Not<A & B & ...>
+                    let all = conjunction(parse_conflict_list(input)?);
+                    predicates.push((None, BoolExpr::Not(Box::new(all))));
+                } else if key == "conflicts_all" {
+                    // All absent: !A & !B & ...
+                    let absent = parse_conflict_list(input)?
+                        .into_iter()
+                        .map(|e| BoolExpr::Not(Box::new(e)))
+                        .collect();
+                    predicates.push((None, conjunction(absent)));
                 } else if key == "with" {
                     with_caps.push(input.parse()?);
                 } else if key == "without" {
@@ -78,8 +126,17 @@ impl Parse for CapsArgs {
                     let val: syn::LitBool = input.parse()?;
                     transparent = val.value;
                 } else if key == "target" {
-                    // Legacy target = C syntax - still supported
-                    target = Some(input.parse()?);
+                    // target = C (repeatable) or target = [A, B]
+                    for t in parse_ident_list(input)? {
+                        push_target(&mut targets, t);
+                    }
+                } else if key == "carrier" {
+                    // carrier = Node (repeatable) or carrier = [Node, Artifact]
+                    for c in parse_ident_list(input)? {
+                        push_target(&mut carriers, c);
+                    }
+                } else if key == "note" {
+                    note = Some(input.parse()?);
                 }
             }
             // 3. Check for grouping: with(...) / without(...)
@@ -112,7 +169,7 @@ impl Parse for CapsArgs {
             // 5. Positional boolean expression (requires / !conflicts) - no target specified
             else {
                 let expr: BoolExpr = input.parse()?;
-                predicates.push(expr);
+                predicates.push((None, expr));
             }
 
             if input.peek(Token![,]) {
@@ -125,36 +182,129 @@ impl Parse for CapsArgs {
             with_caps,
             without_caps,
             transparent,
-            target,
+            targets,
+            note,
+            carriers,
         })
     }
 }
 
+/// `[A, B, ...]` or a single identifier
+fn parse_ident_list(input: ParseStream) -> syn::Result<Vec<Ident>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let idents = syn::punctuated::Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+        Ok(idents.into_iter().collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
+
+/// `[A, B, ...]` or a single operand, for `conflicts_any` / `conflicts_all`
+fn parse_conflict_list(input: ParseStream) -> syn::Result<Vec<BoolExpr>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let exprs = syn::punctuated::Punctuated::<BoolExpr, Token![,]>::parse_terminated(&content)?;
+        if exprs.is_empty() {
+            return Err(content.error("expected at least one capability"));
+        }
+        Ok(exprs.into_iter().collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
+
+/// Left-nested `&` of a non-empty list
+fn conjunction(exprs: Vec<BoolExpr>) -> BoolExpr {
+    exprs
+        .into_iter()
+        .reduce(|acc, e| BoolExpr::And(Box::new(acc), Box::new(e)))
+        .expect("conflict list is non-empty")
+}
+
+fn push_target(targets: &mut Vec<Ident>, target: Ident) {
+    if !targets.contains(&target) {
+        targets.push(target);
+    }
+}
+
+/// The generics the attribute-wide items (`requires`, `conflicts*`, `with`,
+/// `without`) bound: `target = ..`, else the generics named by `T: expr`,
+/// else the first type parameter. `None` when there is nothing to bound.
+fn bound_params(args: &CapsArgs, generics: &syn::Generics) -> Option<Vec<Ident>> {
+    if let Some(params) = named_params(args) {
+        return Some(params);
+    }
+    let first = generics.params.iter().find_map(|p| {
+        if let syn::GenericParam::Type(t) = p {
+            Some(t.ident.clone())
+        } else {
+            None
+        }
+    })?;
+    Some(vec![first])
+}
+
+/// Generics named in the attribute itself (`target = ..`, else `T: expr`)
+fn named_params(args: &CapsArgs) -> Option<Vec<Ident>> {
+    if !args.targets.is_empty() {
+        return Some(args.targets.clone());
+    }
+    let mut params = Vec::new();
+    for param in args.predicates.iter().filter_map(|(param, _)| param.clone()) {
+        push_target(&mut params, param);
+    }
+    (!params.is_empty()).then_some(params)
+}
+
+/// The generics one predicate bounds: its own `T`, else `bound_params`
+fn predicate_params<'a>(param: &'a Option<Ident>, bound_params: &'a [Ident]) -> &'a [Ident] {
+    match param {
+        Some(param) => std::slice::from_ref(param),
+        None => bound_params,
+    }
+}
+
+/// Error for an item without a generic to put the bounds on
+fn missing_generic(span: impl ToTokens, hint: &str) -> TokenStream2 {
+    syn::Error::new_spanned(
+        span,
+        format!("caps_bound needs a capability-carrying generic; {}", hint),
+    )
+    .to_compile_error()
+}
+
 // =============================================================================
 // Predicate Generation
 // =============================================================================
 
 /// Generate predicates from args (for structs, enums, impl blocks)
-pub fn generate_predicates(args: &CapsArgs, bound_param: &syn::Ident) -> Vec<TokenStream2> {
+pub fn generate_predicates(args: &CapsArgs, bound_params: &[Ident]) -> Vec<TokenStream2> {
     let mut output_tokens = Vec::new();
 
-    for pred in &args.predicates {
+    for (param, pred) in &args.predicates {
         let type_expr = bool_expr_to_type(pred);
-        output_tokens.push(quote! {
-            #bound_param: ::tola_caps::Require<#type_expr>
-        });
+        for bound_param in predicate_params(param, bound_params) {
+            output_tokens.push(quote! {
+                #bound_param: ::tola_caps::Require<#type_expr>
+            });
+        }
     }
 
-    for cap in &args.with_caps {
-        output_tokens.push(quote! {
-            #bound_param: ::tola_caps::With<#cap>
-        });
-    }
+    for bound_param in bound_params {
+        for cap in &args.with_caps {
+            output_tokens.push(quote! {
+                #bound_param: ::tola_caps::With<#cap>
+            });
+        }
 
-    for cap in &args.without_caps {
-        output_tokens.push(quote! {
-            #bound_param: ::tola_caps::Without<#cap>
-        });
+        for cap in &args.without_caps {
+            output_tokens.push(quote! {
+                #bound_param: ::tola_caps::Without<#cap>
+            });
+        }
     }
 
     output_tokens
@@ -163,45 +313,61 @@ pub fn generate_predicates(args: &CapsArgs, bound_param: &syn::Ident) -> Vec<Tok
 /// Generate unique traits for each predicate to support custom diagnostic messages
 pub fn generate_predicate_traits(
     args: &CapsArgs,
-    bound_param: &syn::Ident,
+    bound_params: &[syn::Ident],
     fn_name: &syn::Ident,
 ) -> (Vec<TokenStream2>, Vec<TokenStream2>) {
     let mut bounds = Vec::new();
     let mut definitions = Vec::new();
+    let note = args.note.as_ref().map(syn::LitStr::value).unwrap_or_else(|| {
+        "Check if you are missing a required capability or possess a conflicting one.".to_string()
+    });
 
-    for (i, pred) in args.predicates.iter().enumerate() {
+    for (i, (param, pred)) in args.predicates.iter().enumerate() {
         let type_expr = bool_expr_to_type(pred);
         let msg_str = bool_expr_to_string(pred);
 
         // Generate a unique name for this requirement trait
         let trait_name = format_ident!("__Req_{}_{}", fn_name, i);
+        let check_name = format_ident!("__ReqCheck_{}_{}", fn_name, i);
 
         let message = format!("Capability requirement failed: {}", msg_str);
         let label = format!("This capability set violates requirement '{}'", msg_str);
 
-        // Generate the definition
+        // Generate the definition. The trait is a witness implemented only for
+        // `Present`, so it is the leaf obligation rustc reports on failure.
         definitions.push(quote! {
             #[allow(non_camel_case_types)]
             #[diagnostic::on_unimplemented(
                 message = #message,
                 label = #label,
-                note = "Check if you are missing a required capability or possess a conflicting one."
+                note = "Set: {Set}",
+                note = #note
             )]
-            pub trait #trait_name {
+            pub trait #trait_name<Set> {
                  const MSG: &'static str = #msg_str;
             }
 
-            impl<C> #trait_name for C
+            impl<Set> #trait_name<Set> for ::tola_caps::Present {}
+
+            // Generic over the query, so a query naming a set alias (e.g.
+            // `Superset<AdminCaps>`) is only normalized at the call site.
+            #[allow(non_camel_case_types)]
+            pub trait #check_name<Query> {}
+
+            impl<Set, Query> #check_name<Query> for Set
             where
-                C: ::tola_caps::Evaluate<#type_expr>,
-                <C as ::tola_caps::Evaluate<#type_expr>>::Out: ::tola_caps::IsTrue<C, #type_expr>
-            {}
+                Set: ::tola_caps::Evaluate<Query>,
+                <Set as ::tola_caps::Evaluate<Query>>::Out: #trait_name<Set>,
+            {
+            }
         });
 
-        // Generate the bound
-        bounds.push(quote! {
-            #bound_param: #trait_name
-        });
+        // Generate the bounds, once per generic the predicate applies to
+        for bound_param in predicate_params(param, bound_params) {
+            bounds.push(quote! {
+                #bound_param: #check_name<#type_expr>
+            });
+        }
     }
 
     (bounds, definitions)
@@ -235,86 +401,139 @@ fn find_insert_position(
     pos
 }
 
-pub fn expand_caps_fn(args: CapsArgs, mut func: ItemFn) -> TokenStream {
-    let generic_param = format_ident!("__C");
-    let fn_name = func.sig.ident.clone();
+/// Insert `__C` into the signature's generics and thread it into carrier
+/// (`Doc` by default) arguments and a bare carrier return type
+fn inject_transparent_param(sig: &mut syn::Signature, generic_param: &Ident, carriers: &[Ident]) {
+    let insert_pos = find_insert_position(&sig.generics.params);
+    sig.generics
+        .params
+        .insert(insert_pos, syn::parse_quote!(#generic_param));
+
+    for arg in &mut sig.inputs {
+        if let syn::FnArg::Typed(pat_type) = arg {
+            fill_implicit_base(&mut pat_type.ty, generic_param);
+            if let Some(last_seg) = carrier_segment(&mut pat_type.ty, carriers) {
+                if let syn::PathArguments::None = last_seg.arguments {
+                    last_seg.arguments = syn::PathArguments::AngleBracketed(
+                        syn::parse_quote!(<#generic_param>),
+                    );
+                } else if let syn::PathArguments::AngleBracketed(ga) = &mut last_seg.arguments {
+                    ga.args.push(syn::parse_quote!(#generic_param));
+                }
+            }
+        }
+    }
 
-    if args.transparent {
-        let insert_pos = find_insert_position(&func.sig.generics.params);
-        func.sig
-            .generics
-            .params
-            .insert(insert_pos, syn::parse_quote!(#generic_param));
+    // One-argument `with![X]` / `without![X]` in the return type get `__C`
+    // as their base (`Doc<with![X]>` becomes `Doc<with![__C, X]>`); only a
+    // bare carrier is given `<__C>`. The body is not rewritten.
+    if let syn::ReturnType::Type(_, ty) = &mut sig.output {
+        fill_implicit_base(ty, generic_param);
+        if let Some(last_seg) = carrier_segment(ty, carriers) {
+            if let syn::PathArguments::None = last_seg.arguments {
+                last_seg.arguments =
+                    syn::PathArguments::AngleBracketed(syn::parse_quote!(<#generic_param>));
+            }
+        }
+    }
+}
 
-        for arg in &mut func.sig.inputs {
-            if let syn::FnArg::Typed(pat_type) = arg {
-                if let Type::Path(type_path) = &mut *pat_type.ty {
-                    if let Some(last_seg) = type_path.path.segments.last_mut() {
-                        if last_seg.ident == "Doc" {
-                            if let syn::PathArguments::None = last_seg.arguments {
-                                last_seg.arguments = syn::PathArguments::AngleBracketed(
-                                    syn::parse_quote!(<#generic_param>),
-                                );
-                            } else if let syn::PathArguments::AngleBracketed(ga) =
-                                &mut last_seg.arguments
-                            {
-                                ga.args.push(syn::parse_quote!(#generic_param));
-                            }
+/// Rewrite one-argument `with![X]` / `without![X]` inside `ty` to take
+/// `__C` as their base set
+fn fill_implicit_base(ty: &mut Type, generic_param: &Ident) {
+    match ty {
+        Type::Macro(type_macro) => {
+            let mac = &mut type_macro.mac;
+            let is_set_op = mac
+                .path
+                .segments
+                .last()
+                .is_some_and(|seg| seg.ident == "with" || seg.ident == "without");
+            let single = mac
+                .parse_body_with(syn::punctuated::Punctuated::<Type, Token![,]>::parse_terminated)
+                .is_ok_and(|args| args.len() == 1);
+            if is_set_op && single {
+                let tokens = &mac.tokens;
+                mac.tokens = quote!(#generic_param, #tokens);
+            }
+        }
+        Type::Path(type_path) => {
+            for seg in &mut type_path.path.segments {
+                if let syn::PathArguments::AngleBracketed(ga) = &mut seg.arguments {
+                    for arg in &mut ga.args {
+                        if let syn::GenericArgument::Type(inner) = arg {
+                            fill_implicit_base(inner, generic_param);
                         }
                     }
                 }
             }
         }
+        Type::Reference(r) => fill_implicit_base(&mut r.elem, generic_param),
+        Type::Paren(p) => fill_implicit_base(&mut p.elem, generic_param),
+        Type::Tuple(t) => {
+            for elem in &mut t.elems {
+                fill_implicit_base(elem, generic_param);
+            }
+        }
+        _ => {}
     }
+}
 
-    let bound_param = if let Some(target) = args.target.clone() {
-        target
-    } else if args.transparent {
-        generic_param.clone()
+/// The last path segment of `ty` if it names a carrier
+fn carrier_segment<'a>(ty: &'a mut Type, carriers: &[Ident]) -> Option<&'a mut syn::PathSegment> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last_mut().filter(|seg| {
+            if carriers.is_empty() {
+                seg.ident == "Doc"
+            } else {
+                carriers.contains(&seg.ident)
+            }
+        }),
+        _ => None,
+    }
+}
+
+pub fn expand_caps_fn(args: CapsArgs, mut func: ItemFn) -> TokenStream {
+    let generic_param = format_ident!("__C");
+    let fn_name = func.sig.ident.clone();
+
+    if args.transparent {
+        inject_transparent_param(&mut func.sig, &generic_param, &args.carriers);
+    }
+
+    let bound_params = if args.transparent && named_params(&args).is_none() {
+        vec![generic_param.clone()]
+    } else if let Some(params) = bound_params(&args, &func.sig.generics) {
+        params
     } else {
-        func.sig
-            .generics
-            .params
-            .iter()
-            .filter_map(|p| {
-                if let syn::GenericParam::Type(t) = p {
-                    Some(t.ident.clone())
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap_or_else(|| format_ident!("C"))
+        let error = missing_generic(
+            &func.sig.ident,
+            "add `<C>` or use `transparent` with a Doc argument",
+        );
+        return quote! { #error #func }.into();
     };
 
     // Generate predicate traits and bounds
-    let (pred_bounds, pred_defs) = generate_predicate_traits(&args, &bound_param, &fn_name);
+    let (pred_bounds, pred_defs) = generate_predicate_traits(&args, &bound_params, &fn_name);
 
-    if let Some(wc) = &mut func.sig.generics.where_clause {
-        for b in pred_bounds {
-            wc.predicates.push(syn::parse_quote!(#b));
-        }
-    } else {
-        let mut wc: syn::WhereClause = syn::parse_quote!(where);
-        for b in pred_bounds {
-            wc.predicates.push(syn::parse_quote!(#b));
-        }
-        func.sig.generics.where_clause = Some(wc);
+    let where_clause = func.sig.generics.make_where_clause();
+    for b in pred_bounds {
+        where_clause.predicates.push(syn::parse_quote!(#b));
     }
 
     // Add With/Without bounds
-    let where_clause = func.sig.generics.make_where_clause();
-
-    for cap in &args.with_caps {
-        where_clause
-            .predicates
-            .push(syn::parse_quote!(#bound_param: ::tola_caps::With<#cap>));
-    }
+    for bound_param in &bound_params {
+        for cap in &args.with_caps {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#bound_param: ::tola_caps::With<#cap>));
+        }
 
-    for cap in &args.without_caps {
-        where_clause
-            .predicates
-            .push(syn::parse_quote!(#bound_param: ::tola_caps::Without<#cap>));
+        for cap in &args.without_caps {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#bound_param: ::tola_caps::Without<#cap>));
+        }
     }
 
     // Output: definitions + function
@@ -326,26 +545,12 @@ pub fn expand_caps_fn(args: CapsArgs, mut func: ItemFn) -> TokenStream {
 }
 
 pub fn expand_caps_struct(args: CapsArgs, mut item: syn::ItemStruct) -> TokenStream {
-    let bound_param = if let Some(target) = args.target.clone() {
-        target
-    } else {
-        item.generics
-            .params
-            .iter()
-            .filter_map(|p| {
-                if let syn::GenericParam::Type(t) = p {
-                    Some(t.ident.clone())
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap_or_else(|| format_ident!("C"))
+    let Some(bound_params) = bound_params(&args, &item.generics) else {
+        let error = missing_generic(&item.ident, "add `<C>`");
+        return quote! { #error #item }.into();
     };
-
-    let predicates = generate_predicates(&args, &bound_param);
     let where_clause = item.generics.make_where_clause();
-    for pred in predicates {
+    for pred in generate_predicates(&args, &bound_params) {
         where_clause.predicates.push(syn::parse_quote!(#pred));
     }
 
@@ -353,26 +558,12 @@ pub fn expand_caps_struct(args: CapsArgs, mut item: syn::ItemStruct) -> TokenStr
 }
 
 pub fn expand_caps_enum(args: CapsArgs, mut item: syn::ItemEnum) -> TokenStream {
-    let bound_param = if let Some(target) = args.target.clone() {
-        target
-    } else {
-        item.generics
-            .params
-            .iter()
-            .filter_map(|p| {
-                if let syn::GenericParam::Type(t) = p {
-                    Some(t.ident.clone())
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap_or_else(|| format_ident!("C"))
+    let Some(bound_params) = bound_params(&args, &item.generics) else {
+        let error = missing_generic(&item.ident, "add `<C>`");
+        return quote! { #error #item }.into();
     };
-
-    let predicates = generate_predicates(&args, &bound_param);
     let where_clause = item.generics.make_where_clause();
-    for pred in predicates {
+    for pred in generate_predicates(&args, &bound_params) {
         where_clause.predicates.push(syn::parse_quote!(#pred));
     }
 
@@ -380,28 +571,44 @@ pub fn expand_caps_enum(args: CapsArgs, mut item: syn::ItemEnum) -> TokenStream
 }
 
 pub fn expand_caps_impl(args: CapsArgs, mut item: syn::ItemImpl) -> TokenStream {
-    let bound_param = if let Some(target) = args.target.clone() {
-        target
+    let Some(bound_params) = bound_params(&args, &item.generics) else {
+        let error = missing_generic(&item.self_ty, "add `<C>`");
+        return quote! { #error #item }.into();
+    };
+    let where_clause = item.generics.make_where_clause();
+    for pred in generate_predicates(&args, &bound_params) {
+        where_clause.predicates.push(syn::parse_quote!(#pred));
+    }
+
+    item.into_token_stream().into()
+}
+
+/// Trait (or impl) methods cannot host the per-predicate diagnostic traits that
+/// free functions get, so the bounds go through `Require` like structs and impls.
+/// Without a target or a type parameter to bind, `__C` is injected.
+pub fn expand_caps_trait_fn(args: CapsArgs, mut method: TraitItemFn) -> TokenStream {
+    let generic_param = format_ident!("__C");
+
+    let bound_params = if let Some(params) = named_params(&args) {
+        params
+    } else if args.transparent {
+        inject_transparent_param(&mut method.sig, &generic_param, &args.carriers);
+        vec![generic_param]
+    } else if let Some(params) = bound_params(&args, &method.sig.generics) {
+        params
     } else {
-        item.generics
+        let insert_pos = find_insert_position(&method.sig.generics.params);
+        method.sig
+            .generics
             .params
-            .iter()
-            .filter_map(|p| {
-                if let syn::GenericParam::Type(t) = p {
-                    Some(t.ident.clone())
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap_or_else(|| format_ident!("C"))
+            .insert(insert_pos, syn::parse_quote!(#generic_param));
+        vec![generic_param]
     };
 
-    let predicates = generate_predicates(&args, &bound_param);
-    let where_clause = item.generics.make_where_clause();
-    for pred in predicates {
+    let where_clause = method.sig.generics.make_where_clause();
+    for pred in generate_predicates(&args, &bound_params) {
         where_clause.predicates.push(syn::parse_quote!(#pred));
     }
 
-    item.into_token_stream().into()
+    method.into_token_stream().into()
 }
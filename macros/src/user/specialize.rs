@@ -58,15 +58,18 @@
 //! ## Features
 //!
 //! - `default fn` / `default type` - fine-grained control over what can be specialized
-//! - Associated type specialization
+//! - Associated type and const specialization
+//! - Methods with parameters (forwarded to the selected impl as a tuple)
+//! - Negative bounds via `NotXxx` (e.g. `T: NotClone`, backed by `IsNotClone`)
 //! - Multi-level specialization chains (A < B < C < ...)
 //! - Custom trait-to-capability mapping via `#[map(MyTrait => IsMyTrait)]`
 //! - Overlap detection with helpful error messages
+//! - Explicit ordering of equally specific impls via `#[specialize(priority = N)]`
 //! - Inherent impl specialization via `specialize_inherent!`
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     braced, parenthesized,
     parse::{Parse, ParseStream},
@@ -81,8 +84,9 @@ use std::collections::HashMap;
 use super::specialize_common::{
     builtin_trait_map, compute_specificity, path_to_string, type_to_string,
     extract_all_bounds, build_and_expression,
-    impl_struct_name, type_struct_name, marker_trait_name,
-    standard_capability_bounds, bound_to_capability, bound_to_capability_with_fallback,
+    impl_struct_name, type_struct_name, const_struct_name, marker_trait_name,
+    standard_capability_bounds, bound_to_capability, bound_to_capability_with_fallback, direct_trait_bound,
+    strip_negative_bounds, get_simple_trait_name,
 };
 
 // =============================================================================
@@ -176,13 +180,21 @@ pub struct SpecImplBlock {
     pub items: Vec<SpecImplItem>,
     /// Computed specificity (lower = more specific)
     pub specificity: u32,
+    /// `#[specialize(priority = N)]`: replaces `specificity` for ordering
+    pub priority: Option<u32>,
+}
+
+impl SpecImplBlock {
+    /// Ordering key: the explicit priority if given, else the specificity
+    pub fn rank(&self) -> u32 {
+        self.priority.unwrap_or(self.specificity)
+    }
 }
 
 /// Items that can appear in an impl block
 pub enum SpecImplItem {
     Method(SpecMethodImpl),
     Type(SpecTypeImpl),
-    #[allow(dead_code)]
     Const(SpecConstImpl),
 }
 
@@ -202,7 +214,6 @@ pub struct SpecTypeImpl {
 }
 
 /// Associated const implementation
-#[allow(dead_code)]
 pub struct SpecConstImpl {
     pub is_default: bool,
     pub name: Ident,
@@ -403,6 +414,18 @@ impl Parse for TraitConstDef {
 
 impl Parse for SpecImplBlock {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut priority = None;
+        for attr in input.call(Attribute::parse_outer)? {
+            if !attr.path().is_ident("specialize") {
+                return Err(syn::Error::new(attr.span(), "expected `#[specialize(priority = N)]`"));
+            }
+            let args: SpecializeAttr = attr.parse_args()?;
+            if args.priority.is_none() {
+                return Err(syn::Error::new(attr.span(), "expected `#[specialize(priority = N)]`"));
+            }
+            priority = args.priority;
+        }
+
         input.parse::<Token![impl]>()?;
 
         let mut generics: Generics = input.parse()?;
@@ -444,6 +467,7 @@ impl Parse for SpecImplBlock {
             self_ty,
             items,
             specificity,
+            priority,
         })
     }
 }
@@ -623,13 +647,14 @@ pub fn check_overlaps(impls: &[SpecImplBlock]) -> syn::Result<()> {
 
             if impls_overlap(impl_a, impl_b) {
                 // Check if one is strictly more specific
-                if impl_a.specificity == impl_b.specificity {
+                if impl_a.rank() == impl_b.rank() {
                     return Err(syn::Error::new(
                         impl_b.self_ty.span(),
                         format!(
                             "Ambiguous specialization: impls have equal specificity.\n\
                              Neither impl is strictly more specific than the other.\n\
-                             Add more trait bounds to one impl to disambiguate."
+                             Add more trait bounds to one impl, or order them with \
+                             `#[specialize(priority = N)]` (lower wins)."
                         ),
                     ));
                 }
@@ -657,10 +682,23 @@ fn impls_overlap(a: &SpecImplBlock, b: &SpecImplBlock) -> bool {
         }
     }
 
+    // Opposite capability bounds (`Clone` vs `NotClone`) never hold together
+    if has_opposite_bounds(a, b) || has_opposite_bounds(b, a) {
+        return false;
+    }
+
     // Check if self types can unify
     types_can_unify(&a.self_ty, &b.self_ty, &a.generics, &b.generics)
 }
 
+/// Whether `a` bounds `Xxx` where `b` bounds `NotXxx`
+fn has_opposite_bounds(a: &SpecImplBlock, b: &SpecImplBlock) -> bool {
+    a.bounds.iter().any(|pos| {
+        let negated = format!("Not{}", get_simple_trait_name(&pos.path));
+        b.bounds.iter().any(|neg| get_simple_trait_name(&neg.path) == negated)
+    })
+}
+
 /// Check if two types can potentially unify
 fn types_can_unify(a: &Type, b: &Type, gen_a: &Generics, gen_b: &Generics) -> bool {
     let a_str = type_to_string(a);
@@ -713,7 +751,7 @@ pub fn expand_specialize(input: SpecializeInput) -> TokenStream2 {
 
     // Sort impls by specificity (most specific first)
     let mut sorted_impls = input.impls;
-    sorted_impls.sort_by_key(|imp| imp.specificity);
+    sorted_impls.sort_by_key(|imp| imp.rank());
 
     // Generate code
     let trait_def = input.trait_def.as_ref().map(|td| generate_trait_def(td));
@@ -742,12 +780,17 @@ fn generate_trait_def(td: &TraitDef) -> TokenStream2 {
                 ReceiverKind::SelfMutRef => quote! { &mut self },
             };
             let params: Vec<_> = m.sig.params.iter().map(|(n, t)| quote! { #n: #t }).collect();
+            let inputs = if m.sig.receiver == ReceiverKind::None {
+                quote! { #(#params),* }
+            } else {
+                quote! { #receiver #(, #params)* }
+            };
             let ret = m.sig.return_type.as_ref().map(|r| quote! { -> #r }).unwrap_or_default();
 
             if let Some(body) = &m.default_body {
-                quote! { fn #method_name(#receiver #(, #params)*) #ret { #body } }
+                quote! { fn #method_name(#inputs) #ret { #body } }
             } else {
-                quote! { fn #method_name(#receiver #(, #params)*) #ret; }
+                quote! { fn #method_name(#inputs) #ret; }
             }
         }
         TraitItem::Type(t) => {
@@ -758,11 +801,9 @@ fn generate_trait_def(td: &TraitDef) -> TokenStream2 {
             } else {
                 quote! { : #(#bounds)+* }
             };
-            if let Some(default) = &t.default {
-                quote! { type #type_name #bounds_tokens = #default; }
-            } else {
-                quote! { type #type_name #bounds_tokens; }
-            }
+            // Defaults are unstable on associated types; the dispatch impl
+            // seeds its selection chain with it instead.
+            quote! { type #type_name #bounds_tokens; }
         }
         TraitItem::Const(c) => {
             let const_name = &c.name;
@@ -784,43 +825,55 @@ fn generate_trait_def(td: &TraitDef) -> TokenStream2 {
 
 fn generate_impl_structs(
     impls: &[SpecImplBlock],
-    _trait_map: &HashMap<String, TokenStream2>,
+    trait_map: &HashMap<String, TokenStream2>,
 ) -> TokenStream2 {
     let structs: Vec<_> = impls.iter().enumerate().flat_map(|(idx, imp)| {
         imp.items.iter().filter_map(move |item| {
             match item {
                 SpecImplItem::Method(m) => {
                     let struct_name = impl_struct_name(idx, &m.name);
-                    let body = &m.body;
+                    let self_ty = &imp.self_ty;
                     let ret = m.sig.return_type.as_ref()
                         .map(|r| quote! { #r })
                         .unwrap_or(quote! { () });
+                    let names: Vec<_> = m.sig.params.iter().map(|(n, _)| n).collect();
+                    let tys: Vec<_> = m.sig.params.iter().map(|(_, t)| quote! { #t }).collect();
 
                     // Generate different impl based on whether method has receiver
                     if m.sig.receiver == ReceiverKind::None {
                         // Static method - use StaticMethodImpl
+                        let body = &m.body;
                         Some(quote! {
                             #[doc(hidden)]
                             #[allow(non_camel_case_types)]
                             pub struct #struct_name;
 
-                            impl ::tola_caps::spec::dispatch::StaticMethodImpl<#ret> for #struct_name {
+                            impl ::tola_caps::spec::dispatch::StaticMethodImpl<#ret, (#(#tys,)*)> for #struct_name {
                                 #[inline(always)]
-                                fn call() -> #ret {
+                                fn call((#(#names,)*): (#(#tys,)*)) -> #ret {
                                     #body
                                 }
                             }
                         })
                     } else {
-                        // Instance method - use MethodImpl
+                        // Instance method - use MethodImpl, keeping the impl's
+                        // generics and bounds so the body can rely on them.
+                        // `Self`/`self` are rewritten since `call` is a free fn.
+                        let generics = strip_negative_bounds(&imp.generics, trait_map);
+                        let (impl_generics, _, where_clause) = generics.split_for_impl();
+                        let ret = replace_self(ret, self_ty);
+                        let tys: Vec<_> = tys.into_iter().map(|t| replace_self(t, self_ty)).collect();
+                        let body = replace_self(m.body.clone(), self_ty);
                         Some(quote! {
                             #[doc(hidden)]
                             #[allow(non_camel_case_types)]
                             pub struct #struct_name;
 
-                            impl<T: ?Sized> ::tola_caps::spec::dispatch::MethodImpl<T, #ret> for #struct_name {
+                            impl #impl_generics ::tola_caps::spec::dispatch::MethodImpl<#self_ty, #ret, (#(#tys,)*)>
+                                for #struct_name #where_clause
+                            {
                                 #[inline(always)]
-                                fn call(_value: &T) -> #ret {
+                                fn call(__self: &#self_ty, (#(#names,)*): (#(#tys,)*)) -> #ret {
                                     #body
                                 }
                             }
@@ -841,7 +894,23 @@ fn generate_impl_structs(
                         }
                     })
                 }
-                _ => None,
+                SpecImplItem::Const(c) => {
+                    // Only trait impls dispatch consts
+                    let trait_name = &imp.trait_path.as_ref()?.segments.last()?.ident;
+                    let struct_name = const_struct_name(idx, trait_name, &c.name);
+                    let ty = &c.ty;
+                    let value = &c.value;
+
+                    Some(quote! {
+                        #[doc(hidden)]
+                        #[allow(non_camel_case_types)]
+                        pub struct #struct_name;
+
+                        impl ::tola_caps::spec::dispatch::ConstImpl<#ty> for #struct_name {
+                            const VALUE: #ty = #value;
+                        }
+                    })
+                }
             }
         })
     }).collect();
@@ -849,6 +918,84 @@ fn generate_impl_structs(
     quote! { #(#structs)* }
 }
 
+/// Rewrite `Self` to `self_ty` (as `<self_ty>` before `::`) and the `self`
+/// receiver to `__self`, so a method body can move into a selector struct's
+/// `call`. A `self` in a path (`self::helper()`) is left alone.
+fn replace_self(tokens: TokenStream2, self_ty: &Type) -> TokenStream2 {
+    use proc_macro2::{Group, Spacing, TokenTree};
+
+    // `::` is a joint `:` followed by another `:`
+    fn is_path_sep(tt: Option<&TokenTree>, next: Option<&TokenTree>) -> bool {
+        matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint)
+            && matches!(next, Some(TokenTree::Punct(p)) if p.as_char() == ':')
+    }
+
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut out = TokenStream2::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        let before_path = is_path_sep(tokens.get(i + 1), tokens.get(i + 2));
+        let after_path = i >= 2 && is_path_sep(tokens.get(i - 2), tokens.get(i - 1));
+        // `<Self>::` / `<Self as Trait>`: already a qualified-path type
+        let in_angle = i >= 1 && matches!(&tokens[i - 1], TokenTree::Punct(p) if p.as_char() == '<');
+        match tt {
+            TokenTree::Ident(ident) if ident == "Self" => {
+                out.extend(if before_path && !in_angle {
+                    quote! { <#self_ty> }
+                } else {
+                    quote! { #self_ty }
+                });
+            }
+            TokenTree::Ident(ident) if ident == "self" && !before_path && !after_path => {
+                out.extend(quote! { __self });
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                new_group.set_span(group.span());
+                out.extend(core::iter::once(TokenTree::Group(new_group)));
+            }
+            other => out.extend(core::iter::once(other.clone())),
+        }
+    }
+    out
+}
+
+/// Name elided lifetimes (`&T`, `'_`) as `'__spec`, for use under a
+/// `for<'__spec>` bound. Returns whether anything was named.
+fn name_elided_lifetimes(tokens: TokenStream2) -> (TokenStream2, bool) {
+    use proc_macro2::{Group, TokenTree};
+
+    let mut out = TokenStream2::new();
+    let mut named = false;
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '&' => {
+                out.extend(core::iter::once(TokenTree::Punct(p)));
+                if !matches!(iter.peek(), Some(TokenTree::Punct(next)) if next.as_char() == '\'') {
+                    out.extend(quote! { '__spec });
+                    named = true;
+                }
+            }
+            TokenTree::Punct(p) if p.as_char() == '\''
+                && matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "_") =>
+            {
+                iter.next();
+                out.extend(quote! { '__spec });
+                named = true;
+            }
+            TokenTree::Group(group) => {
+                let (inner, inner_named) = name_elided_lifetimes(group.stream());
+                named |= inner_named;
+                let mut new_group = Group::new(group.delimiter(), inner);
+                new_group.set_span(group.span());
+                out.extend(core::iter::once(TokenTree::Group(new_group)));
+            }
+            other => out.extend(core::iter::once(other)),
+        }
+    }
+    (out, named)
+}
+
 fn generate_dispatch_impl(
     impls: &[SpecImplBlock],
     trait_map: &HashMap<String, TokenStream2>,
@@ -859,6 +1006,11 @@ fn generate_dispatch_impl(
     };
 
     let trait_name = &td.name;
+    let generic_self: Type = syn::parse_quote!(T);
+
+    // `Selection: MethodImpl<T, ..>` bounds for instance methods; they hold
+    // once `T` is concrete and the chain resolves to a single impl struct.
+    let mut method_bounds = Vec::new();
 
     // Build selection chains for each method
     let method_chains: Vec<_> = td.items.iter().filter_map(|item| {
@@ -919,7 +1071,8 @@ fn generate_dispatch_impl(
                 ReceiverKind::SelfMutRef => quote! { &mut self },
             };
             let params: Vec<_> = m.sig.params.iter().map(|(n, t)| quote! { #n: #t }).collect();
-            let _ret_decl = m.sig.return_type.as_ref().map(|r| quote! { -> #r }).unwrap_or_default();
+            let arg_names: Vec<_> = m.sig.params.iter().map(|(n, _)| n).collect();
+            let arg_tys: Vec<_> = m.sig.params.iter().map(|(_, t)| quote! { #t }).collect();
 
             // Generate the call expression based on whether we have a receiver
             let call_expr = if m.sig.receiver == ReceiverKind::None {
@@ -966,18 +1119,42 @@ fn generate_dispatch_impl(
 
                 // Call via StaticMethodImpl
                 quote! {
-                    <#static_selection as ::tola_caps::spec::dispatch::StaticMethodImpl<#ret>>::call()
+                    <#static_selection as ::tola_caps::spec::dispatch::StaticMethodImpl<#ret, (#(#arg_tys,)*)>>::call(
+                        (#(#arg_names,)*)
+                    )
                 }
             } else {
-                // For methods with self, use the original selection approach
+                // For methods with self, call through the selected impl struct
+                let bound_ret = replace_self(ret.clone(), &generic_self);
+                let bound_args = replace_self(quote! { (#(#arg_tys,)*) }, &generic_self);
+                let (bound, higher_ranked) = name_elided_lifetimes(quote! {
+                    ::tola_caps::spec::dispatch::MethodImpl<T, #bound_ret, #bound_args>
+                });
+                let for_lifetime = higher_ranked.then(|| quote! { for<'__spec> });
+                method_bounds.push(quote! { #for_lifetime #selection: #bound });
+
+                let value = match m.sig.receiver {
+                    ReceiverKind::SelfRef => quote! { self },
+                    ReceiverKind::SelfMutRef => quote! { &*self },
+                    _ => quote! { &self },
+                };
                 quote! {
-                    <#selection as ::tola_caps::spec::dispatch::MethodImpl<Self, #ret>>::call(&self)
+                    <#selection as ::tola_caps::spec::dispatch::MethodImpl<Self, #ret, (#(#arg_tys,)*)>>::call(
+                        #value,
+                        (#(#arg_names,)*)
+                    )
                 }
             };
 
+            let inputs = if m.sig.receiver == ReceiverKind::None {
+                quote! { #(#params),* }
+            } else {
+                quote! { #receiver #(, #params)* }
+            };
+
             Some(quote! {
                 #[inline(always)]
-                fn #method_name(#receiver #(, #params)*) -> #ret {
+                fn #method_name(#inputs) -> #ret {
                     #call_expr
                 }
             })
@@ -991,7 +1168,10 @@ fn generate_dispatch_impl(
         if let TraitItem::Type(t) = item {
             let type_name = &t.name;
 
-            let mut selection = quote! { () };
+            // Base of the chain: the trait's declared default, if any
+            let mut selection = t.default.as_ref()
+                .map(|default| quote! { #default })
+                .unwrap_or(quote! { () });
 
             for (_idx, imp) in impls.iter().enumerate().rev() {
                 let type_impl = imp.items.iter().find_map(|i| {
@@ -1033,14 +1213,95 @@ fn generate_dispatch_impl(
         }
     }).collect();
 
+    // Build selection chains for associated consts.
+    // Selector structs for trait defaults go outside the impl block.
+    let mut const_defaults = Vec::new();
+    let mut const_chains = Vec::new();
+    for item in &td.items {
+        let TraitItem::Const(c) = item else {
+            continue;
+        };
+        let const_name = &c.name;
+        let ty = &c.ty;
+
+        let specialized = impls.iter().any(|imp| imp.items.iter().any(|i| {
+            matches!(i, SpecImplItem::Const(c2) if c2.name == *const_name)
+        }));
+        // Not specialized anywhere: the trait default applies as-is
+        if !specialized {
+            continue;
+        }
+
+        // Base of the chain: the trait's default value, if any
+        let mut selection = c.default.as_ref().map(|default| {
+            let struct_name = format_ident!("__SpecConstDefault_{}_{}", trait_name, const_name);
+            const_defaults.push(quote! {
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                pub struct #struct_name;
+
+                impl ::tola_caps::spec::dispatch::ConstImpl<#ty> for #struct_name {
+                    const VALUE: #ty = #default;
+                }
+            });
+            quote! { #struct_name }
+        });
+
+        for (idx, imp) in impls.iter().enumerate().rev() {
+            let has_const = imp.items.iter().any(|i| {
+                matches!(i, SpecImplItem::Const(c2) if c2.name == *const_name)
+            });
+            if !has_const {
+                continue;
+            }
+            let const_struct = const_struct_name(idx, trait_name, const_name);
+
+            let conditions: Vec<_> = imp.bounds.iter()
+                .filter_map(|bound| bound_to_capability(bound, trait_map))
+                .collect();
+
+            if conditions.is_empty() {
+                selection = Some(quote! { #const_struct });
+            } else {
+                let condition = build_and_expression(conditions);
+                let Some(prev) = selection else {
+                    return syn::Error::new(
+                        const_name.span(),
+                        format!(
+                            "`const {}` has no fallback: give it a default in the trait or an impl without bounds",
+                            const_name
+                        ),
+                    ).to_compile_error();
+                };
+                selection = Some(quote! {
+                    ::tola_caps::spec::dispatch::ConstSelect<
+                        ::tola_caps::std_caps::Cap<T>,
+                        #condition,
+                        #const_struct,
+                        #prev
+                    >
+                });
+            }
+        }
+
+        const_chains.push(quote! {
+            const #const_name: #ty =
+                <#selection as ::tola_caps::spec::dispatch::ConstImpl<#ty>>::VALUE;
+        });
+    }
+
     // Standard bounds using shared utility
     let standard_bounds = standard_capability_bounds();
 
     quote! {
+        #(#const_defaults)*
+
         impl<T: ::tola_caps::std_caps::AutoCapSet> #trait_name for T
         where
             #standard_bounds,
+            #(#method_bounds,)*
         {
+            #(#const_chains)*
             #(#type_chains)*
             #(#method_chains)*
         }
@@ -1087,6 +1348,8 @@ pub struct SpecializeAttr {
     /// New per-generic constraints (for `T: Clone, U: Copy` syntax)
     pub constraints: Vec<GenericConstraint>,
     pub mappings: Vec<CapabilityMapping>,
+    /// `priority = N`, only meaningful on impls inside `specialization!`
+    pub priority: Option<u32>,
 }
 
 impl Parse for SpecializeAttr {
@@ -1095,9 +1358,10 @@ impl Parse for SpecializeAttr {
         let mut bounds = Vec::new();
         let mut constraints = Vec::new();
         let mut mappings = Vec::new();
+        let mut priority = None;
 
         if input.is_empty() {
-            return Ok(SpecializeAttr { is_default, bounds, constraints, mappings });
+            return Ok(SpecializeAttr { is_default, bounds, constraints, mappings, priority });
         }
 
         // Check for `default` keyword first
@@ -1183,6 +1447,12 @@ impl Parse for SpecializeAttr {
                         let mapping: CapabilityMapping = syn::parse2(ml.tokens)?;
                         mappings.push(mapping);
                     }
+                    Meta::NameValue(nv) if nv.path.is_ident("priority") => {
+                        let Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) = &nv.value else {
+                            return Err(syn::Error::new(nv.value.span(), "expected an integer priority"));
+                        };
+                        priority = Some(lit.base10_parse()?);
+                    }
                     _ => {}
                 }
             }
@@ -1191,7 +1461,7 @@ impl Parse for SpecializeAttr {
             }
         }
 
-        Ok(SpecializeAttr { is_default, bounds, constraints, mappings })
+        Ok(SpecializeAttr { is_default, bounds, constraints, mappings, priority })
     }
 }
 
@@ -1232,6 +1502,14 @@ pub fn expand_specialize_attr(attr: TokenStream, item: TokenStream) -> TokenStre
 }
 
 fn expand_specialize_attr_impl(args: SpecializeAttr, input: ItemImpl) -> TokenStream2 {
+    if args.priority.is_some() {
+        return syn::Error::new(
+            input.impl_token.span,
+            "`priority` orders impls inside `specialization!`; standalone `#[specialize]` impls are not ranked",
+        )
+        .to_compile_error();
+    }
+
     let self_ty = &input.self_ty;
     let generics = &input.generics;
     let is_generic = !generics.params.is_empty();
@@ -1241,20 +1519,27 @@ fn expand_specialize_attr_impl(args: SpecializeAttr, input: ItemImpl) -> TokenSt
     let trait_map = builtin_trait_map();
 
     // Build where clause additions based on legacy `for` bounds (applies to T)
-    let mut additional_bounds: Vec<_> = args.bounds.iter().map(|bound| {
+    let mut additional_bounds = Vec::new();
+    let mut uses_cap_set = false;
+    for bound in &args.bounds {
+        if let Some(direct) = direct_trait_bound(bound) {
+            additional_bounds.push(quote! { T: #direct });
+            continue;
+        }
+
         // Map trait names to capability markers using shared utility
         let cap_marker = bound_to_capability_with_fallback(bound, &trait_map);
-
-        quote! {
+        uses_cap_set = true;
+        additional_bounds.push(quote! {
             ::tola_caps::std_caps::Cap<T>: ::tola_caps::capability::Evaluate<#cap_marker, Out = ::tola_caps::Present>
-        }
-    }).collect();
+        });
+    }
 
     // Collect all generic parameters that need AutoCapSet bound
     let mut auto_cap_set_bounds = Vec::new();
 
     // For legacy bounds (applies to T)
-    if !args.bounds.is_empty() {
+    if uses_cap_set {
         auto_cap_set_bounds.push(quote! { T: ::tola_caps::std_caps::AutoCapSet });
     }
 
@@ -1306,22 +1591,14 @@ fn expand_specialize_attr_impl(args: SpecializeAttr, input: ItemImpl) -> TokenSt
         let items = &input.items;
         let attrs = &input.attrs;
 
-        // Combine all bounds: AutoCapSet bounds + capability bounds + existing where clause
-        let all_bounds: Vec<_> = auto_cap_set_bounds.iter()
-            .chain(additional_bounds.iter())
-            .collect();
-
-        // Merge existing where clause with new bounds
-        let where_clause = if let Some(existing) = existing_where {
-            let existing_preds = &existing.predicates;
-            quote! {
-                where #existing_preds, #(#all_bounds),*
-            }
-        } else {
-            quote! {
-                where #(#all_bounds),*
-            }
-        };
+        // Keep the existing predicates verbatim (lifetime and higher-ranked
+        // ones included) and append AutoCapSet + capability bounds
+        let mut where_clause = existing_where
+            .cloned()
+            .unwrap_or_else(|| syn::parse_quote! { where });
+        for bound in auto_cap_set_bounds.iter().chain(additional_bounds.iter()) {
+            where_clause.predicates.push(syn::parse_quote! { #bound });
+        }
 
         quote! {
             #(#attrs)*
@@ -1367,7 +1644,7 @@ fn expand_specialize_inherent_impl(input: SpecializeInput) -> TokenStream2 {
 
     // Sort impls by specificity
     let mut sorted_impls = input.impls;
-    sorted_impls.sort_by_key(|imp| imp.specificity);
+    sorted_impls.sort_by_key(|imp| imp.rank());
 
     // For inherent impls, generate conditional methods
     let mut methods = Vec::new();
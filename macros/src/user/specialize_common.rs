@@ -27,9 +27,28 @@ pub fn builtin_trait_map() -> HashMap<String, TokenStream2> {
     map.insert("Ord".to_string(), quote! { ::tola_caps::detect::IsOrd });
     map.insert("Hash".to_string(), quote! { ::tola_caps::detect::IsHash });
     map.insert("Display".to_string(), quote! { ::tola_caps::detect::IsDisplay });
+
+    // NotXxx -> IsNotXxx (Present exactly when IsXxx is Absent)
+    let negated: Vec<_> = map.keys().cloned().collect();
+    for name in negated {
+        let marker = format_ident!("IsNot{}", name);
+        map.insert(format!("Not{}", name), quote! { ::tola_caps::detect::#marker });
+    }
     map
 }
 
+/// Bounds that are checked as plain trait bounds instead of through the
+/// capability set, because they carry a type argument the set cannot encode
+/// (e.g. `IteratorItemIs<u8>` -> `::tola_caps::detect::IteratorItemIs<u8>`)
+pub fn direct_trait_bound(bound: &TraitBound) -> Option<TokenStream2> {
+    let last = bound.path.segments.last()?;
+    if last.ident != "IteratorItemIs" {
+        return None;
+    }
+    let args = &last.arguments;
+    Some(quote! { ::tola_caps::detect::IteratorItemIs #args })
+}
+
 /// Get the simple (last segment) name from a path
 pub fn get_simple_trait_name(path: &Path) -> String {
     let full_path = path_to_string(path);
@@ -57,6 +76,36 @@ pub fn bound_to_capability(
     trait_to_capability(&simple_name, trait_map)
 }
 
+/// `NotXxx` bounds (e.g. `T: NotClone`) are dispatch conditions, not traits
+pub fn is_negative_bound(bound: &TraitBound, trait_map: &HashMap<String, TokenStream2>) -> bool {
+    let simple_name = get_simple_trait_name(&bound.path);
+    simple_name.starts_with("Not") && trait_map.contains_key(&simple_name)
+}
+
+/// Remove `NotXxx` bounds so the generics can be emitted as real Rust
+pub fn strip_negative_bounds(
+    generics: &Generics,
+    trait_map: &HashMap<String, TokenStream2>,
+) -> Generics {
+    let keep = |bound: &syn::TypeParamBound| match bound {
+        syn::TypeParamBound::Trait(tb) => !is_negative_bound(tb, trait_map),
+        _ => true,
+    };
+
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds = param.bounds.iter().filter(|b| keep(b)).cloned().collect();
+    }
+    if let Some(where_clause) = &mut generics.where_clause {
+        for pred in where_clause.predicates.iter_mut() {
+            if let syn::WherePredicate::Type(pt) = pred {
+                pt.bounds = pt.bounds.iter().filter(|b| keep(b)).cloned().collect();
+            }
+        }
+    }
+    generics
+}
+
 /// Generate a default capability marker for unknown traits
 /// Convention: Trait "Foo" maps to "IsFoo"
 pub fn default_capability_for_trait(trait_name: &str) -> TokenStream2 {
@@ -354,6 +403,14 @@ pub fn impl_struct_name(impl_idx: usize, method_name: &Ident) -> Ident {
     format_ident!("__SpecImpl{}_{}", impl_idx, method_name)
 }
 
+/// Generate const struct name for associated const specialization.
+///
+/// Keyed by trait so two `specialization!` blocks in one module can
+/// specialize consts of the same name.
+pub fn const_struct_name(impl_idx: usize, trait_name: &Ident, const_name: &Ident) -> Ident {
+    format_ident!("__SpecConst{}_{}_{}", impl_idx, trait_name, const_name)
+}
+
 /// Generate type struct name for type specialization
 pub fn type_struct_name(impl_idx: usize, type_name: &Ident) -> Ident {
     format_ident!("__SpecType{}_{}", impl_idx, type_name)
@@ -5,8 +5,8 @@
 //! - Concrete types: `Cap<T>` provides type-level capability queries
 //! - Generic functions: Use `caps_check!(T: Clone)` macro instead
 
-use crate::primitives::Bool;
-use crate::trie::{Capability, InsertAt};
+use crate::primitives::{Bool, BoolAnd, Present};
+use crate::trie::{Capability, Evaluate, HCons, HNil, InsertAt};
 use crate::primitives::stream::D0;
 
 // =============================================================================
@@ -22,6 +22,18 @@ pub trait AutoCapSet {
 /// Access T's capability set. Requires T: AutoCapSet.
 pub type Cap<T> = <T as AutoCapSet>::Out;
 
+/// Bound checked by `caps_check_generic!`: the type carries AutoCaps info.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no capability info for `caps_check_generic!`",
+    label = "generic type without an `AutoCaps` bound",
+    note = "caps_check requires a concrete type; add `{Self}: AutoCaps` at the call site",
+    note = "`AutoCaps` is `tola_caps::std_caps::AutoCaps`"
+)]
+pub trait CapsCheckGeneric {}
+
+impl<T: ?Sized + super::AutoCaps> CapsCheckGeneric for T {}
+
 // =============================================================================
 // InsertIf - Conditional insertion based on const bool
 // =============================================================================
@@ -56,6 +68,30 @@ where
     type Out = B::If<<S as InsertAt<Cap, D0>>::Out, S>;
 }
 
+// =============================================================================
+// MembersEvaluate - AND a query over several types' capability sets
+// =============================================================================
+
+/// Evaluate `Q` against the capability set of every type in an HList.
+///
+/// `Out` is `Present` only if all members have `Q`; used for tuples.
+pub trait MembersEvaluate<Q> {
+    type Out: Bool;
+}
+
+impl<Q> MembersEvaluate<Q> for HNil {
+    type Out = Present;
+}
+
+impl<Q, H, T> MembersEvaluate<Q> for HCons<H, T>
+where
+    H: AutoCapSet,
+    Cap<H>: Evaluate<Q>,
+    T: MembersEvaluate<Q>,
+{
+    type Out = <<Cap<H> as Evaluate<Q>>::Out as BoolAnd<T::Out>>::Out;
+}
+
 // =============================================================================
 // Macros (generated by proc-macros)
 // =============================================================================
@@ -75,6 +111,19 @@ macros::define_impl_std_lib_types_macro!();
 
 impl_std_types!();
 
+impl_auto_caps!(@tuple T0);
+impl_auto_caps!(@tuple T0, T1);
+impl_auto_caps!(@tuple T0, T1, T2);
+impl_auto_caps!(@tuple T0, T1, T2, T3);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6, T7);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6, T7, T8);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, TA);
+impl_auto_caps!(@tuple T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, TA, TB);
+
 #[cfg(feature = "alloc")]
 impl_alloc_types!();
 
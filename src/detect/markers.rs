@@ -18,6 +18,7 @@ pub struct IsClone;
 impl Capability for IsClone {
     type Stream = Cons<X0, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsClone";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
@@ -28,6 +29,7 @@ pub struct IsCopy;
 impl Capability for IsCopy {
     type Stream = Cons<X1, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsCopy";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
@@ -38,6 +40,7 @@ pub struct IsDebug;
 impl Capability for IsDebug {
     type Stream = Cons<X2, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsDebug";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
@@ -48,6 +51,7 @@ pub struct IsDefault;
 impl Capability for IsDefault {
     type Stream = Cons<X3, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsDefault";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
@@ -58,6 +62,7 @@ pub struct IsSend;
 impl Capability for IsSend {
     type Stream = Cons<X4, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsSend";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
@@ -68,6 +73,7 @@ pub struct IsSync;
 impl Capability for IsSync {
     type Stream = Cons<X5, ConstStream<X0>>;
     type Identity = TypeMarker<Self>;
+    const NAME: &'static str = "IsSync";
     type At<D> = <<Self::Stream as GetTail<D>>::Out as HashStream>::Head
     where
         Self::Stream: GetTail<D>;
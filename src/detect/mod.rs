@@ -26,4 +26,4 @@ pub mod autocaps;
 
 macros::define_std_traits!();
 
-pub use autocaps::{AutoCapSet, Cap, InsertIf, InsertIfType};
+pub use autocaps::{AutoCapSet, Cap, CapsCheckGeneric, InsertIf, InsertIfType, MembersEvaluate};
@@ -137,6 +137,9 @@ pub mod detect {
     pub trait AutoCapSet {
         type Out;
     }
+    #[doc(hidden)]
+    pub trait CapsCheckGeneric {}
+    impl<T: ?Sized + AutoCaps> CapsCheckGeneric for T {}
     // Use PhantomData to consume T
     pub type Cap<T> = PhantomData<T>;
     pub struct InsertIf<S, Cap, const B: bool>(PhantomData<(S, Cap)>);
@@ -157,15 +160,17 @@ pub mod syntax_macros;
 
 // Re-export core types from trie and primitives at crate root
 pub use trie::*;
-pub use primitives::bool::{Bool, Present, Absent, BoolAnd, BoolOr, BoolNot};
+pub use primitives::bool::{Bool, Present, Absent, BoolAnd, BoolOr, BoolNot, BoolXor, ConstBool};
 pub use primitives::nibble::{
-    Nibble, NibbleEq,
+    Nibble, NibbleEq, NibbleCmp,
     X0, X1, X2, X3, X4, X5, X6, X7,
     X8, X9, XA, XB, XC, XD, XE, XF,
 };
 pub use primitives::stream::{
-    HashStream, GetTail, ConstStream, AltStream, Cons,
+    HashStream, GetTail, ConstStream, AltStream, Cons, WithDepth, StreamDepth,
     Z, S, DefaultMaxDepth, StreamEq, StreamEqDispatch, D0, D16, Peano,
+    PeanoAdd, PeanoVal, PeanoLessEq, PeanoEq, PeanoLess, PeanoCmp,
+    Ordering3, OrdThen, LessT, EqualT, GreaterT, StreamPrefix,
     HashStream16,
 };
 
@@ -179,7 +184,43 @@ pub mod capability {
 }
 
 // Re-export proc-macros
-pub use macros::{cap, caps, caps_bound, caps_check, specialize, specialize_inherent, specialization, derive_trait_cap, Capability, AutoCaps, trait_autocaps, define_type_cap, name_stream, make_routing_stream, make_identity_bytes, __internal_make_identity};
+pub use macros::{cap, caps, caps_limited, caps_assert, caps_refute, caps_snapshot, caps_bound, caps_diff, caps_check, caps_check_generic, define_capabilities, specialize, specialize_inherent, specialization, derive_trait_cap, capability_from_trait, Capability, AutoCaps, trait_autocaps, define_type_cap, name_stream, make_routing_stream, make_deep_routing_stream, make_identity_bytes, __internal_make_identity};
+
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::cap_holder;
+/// use core::marker::PhantomData;
+///
+/// #[derive(Capability)] struct Parsed;
+///
+/// #[cap_holder]
+/// struct Doc {
+///     content: String,
+/// }
+///
+/// let doc: Doc = Doc { content: "# Title".into(), _caps: PhantomData };
+/// let doc: Doc<caps![Parsed]> = doc.with_cap::<Parsed>();
+/// assert_eq!(doc.content, "# Title");
+/// ```
+pub use macros::cap_holder;
+
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::CapHolder;
+/// use core::marker::PhantomData;
+///
+/// #[derive(Capability)] struct Parsed;
+///
+/// #[derive(CapHolder)]
+/// struct Doc<C = tola_caps::Empty> {
+///     content: String,
+///     caps: PhantomData<C>,
+/// }
+///
+/// let doc = Doc { content: String::new(), caps: PhantomData::<tola_caps::Empty> };
+/// let _: Doc<caps![Parsed]> = doc.with_cap::<Parsed>();
+/// ```
+pub use macros::CapHolder;
 
 // =============================================================================
 // Declarative Macro Bridge for #[derive(Capability)]
@@ -196,12 +237,81 @@ pub use macros::{cap, caps, caps_bound, caps_check, specialize, specialize_inher
 #[doc(hidden)]
 macro_rules! __impl_capability {
     ($ty:ty, $name:expr) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!(module_path!(), "::", $name),
+            stream = $crate::make_routing_stream!(concat!(module_path!(), "::", $name)));
+    };
+    // #[capability(max_depth = N)]: the depth travels with the stream
+    ($ty:ty, $name:expr, max_depth = $depth:ty) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!(module_path!(), "::", $name),
+            stream = $crate::WithDepth<$crate::make_routing_stream!(concat!(module_path!(), "::", $name)), $depth>);
+    };
+    // #[capability(namespace = "...")]: namespace mixed into the hashed key
+    ($ty:ty, $name:expr, namespace = $ns:literal) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!($ns, "@", module_path!(), "::", $name),
+            stream = $crate::make_routing_stream!(concat!($ns, "@", module_path!(), "::", $name)));
+    };
+    ($ty:ty, $name:expr, namespace = $ns:literal, max_depth = $depth:ty) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!($ns, "@", module_path!(), "::", $name),
+            stream = $crate::WithDepth<$crate::make_routing_stream!(concat!($ns, "@", module_path!(), "::", $name)), $depth>);
+    };
+    // #[capability(deep)]: 32-nibble stream, compared at D32
+    ($ty:ty, $name:expr, deep) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!(module_path!(), "::", $name),
+            stream = $crate::make_deep_routing_stream!(concat!(module_path!(), "::", $name)));
+    };
+    ($ty:ty, $name:expr, namespace = $ns:literal, deep) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!($ns, "@", module_path!(), "::", $name),
+            stream = $crate::make_deep_routing_stream!(concat!($ns, "@", module_path!(), "::", $name)));
+    };
+    // #[capability(stream = "...")]: explicit routing, identity still from the name
+    ($ty:ty, $name:expr, stream = $stream:ty) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!(module_path!(), "::", $name),
+            stream = $stream);
+    };
+    ($ty:ty, $name:expr, stream = $stream:ty, max_depth = $depth:ty) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!(module_path!(), "::", $name),
+            stream = $crate::WithDepth<$stream, $depth>);
+    };
+    ($ty:ty, $name:expr, stream = $stream:ty, namespace = $ns:literal) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!($ns, "@", module_path!(), "::", $name),
+            stream = $stream);
+    };
+    ($ty:ty, $name:expr, stream = $stream:ty, namespace = $ns:literal, max_depth = $depth:ty) => {
+        $crate::__impl_capability!(@impl $ty, $name,
+            key = concat!($ns, "@", module_path!(), "::", $name),
+            stream = $crate::WithDepth<$stream, $depth>);
+    };
+    // #[capability(alias_of = Other)]: same stream (and so depth) and identity as `Other`
+    ($ty:ty, $name:expr, alias_of = $other:ty) => {
+        impl $crate::Capability for $ty {
+            type Stream = <$other as $crate::Capability>::Stream;
+
+            type Identity = <$other as $crate::Capability>::Identity;
+
+            const NAME: &'static str = $name;
+
+            type At<D: $crate::Peano> = <<Self::Stream as $crate::GetTail<D>>::Out as $crate::HashStream>::Head
+            where Self::Stream: $crate::GetTail<D>;
+        }
+    };
+    (@impl $ty:ty, $name:expr, key = $key:expr, stream = $stream:ty) => {
         impl $crate::Capability for $ty {
             // Stream: hash-based routing for trie navigation
-            type Stream = $crate::make_routing_stream!(concat!(module_path!(), "::", $name));
+            type Stream = $stream;
 
             // Identity: Type-level character list for exact comparison
-            type Identity = $crate::__make_identity_from_str!(concat!(module_path!(), "::", $name));
+            type Identity = $crate::__make_identity_from_str!($key);
+
+            const NAME: &'static str = $name;
 
             type At<D: $crate::Peano> = <<Self::Stream as $crate::GetTail<D>>::Out as $crate::HashStream>::Head
             where Self::Stream: $crate::GetTail<D>;
@@ -218,13 +328,55 @@ macro_rules! __make_identity_from_str {
     };
 }
 
+// =============================================================================
+// Feature Switches for Generated Code
+// =============================================================================
+//
+// `#[cfg]` cannot gate part of a type expression, and generated code expanded in
+// a downstream crate would test that crate's features instead of ours. These pick
+// a branch by tola-caps' own features: `__cfg_std!({ yes } else { no })`.
+
+/// Internal feature switch - expands `yes` when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __cfg_std {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($yes)* };
+}
+
+/// Internal feature switch - expands `no` when the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __cfg_std {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($no)* };
+}
+
+/// Internal feature switch - expands `yes` when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __cfg_alloc {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($yes)* };
+}
+
+/// Internal feature switch - expands `no` when the `alloc` feature is disabled.
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __cfg_alloc {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($no)* };
+}
+
 /// Common items for the capability system.
 pub mod prelude {
     pub use crate::trie::{
         // Core Traits
-        Capability, Evaluate, With, Inspect,
+        Capability, Evaluate, With, Inspect, InspectNames,
         // Set Operations
         SetUnion, SetIntersect, SupersetOf,
+        // Queries
+        Implies, RequireAll, RequireAny, Superset, ComplementOf,
     };
     pub use crate::detect::AutoCaps;
     #[cfg(feature = "detect")]
@@ -235,7 +387,7 @@ pub mod prelude {
         IsEq, IsPartialEq, IsOrd, IsPartialOrd, IsHash,
         IsDisplay, IsSized, IsUnpin,
     };
-    pub use macros::{caps, caps_bound, caps_check, Capability};
-    // Note: with!, union!, intersect!, check! are #[macro_export] so they're at crate root
+    pub use macros::{caps, caps_limited, caps_assert, caps_refute, caps_snapshot, caps_bound, caps_check, caps_check_generic, Capability};
+    // Note: with!, with_all!, union!, intersect!, diff!, check! are #[macro_export] so they're at crate root
 }
 
@@ -8,6 +8,8 @@ use crate::spec::dispatch::StaticMethodImpl;
 pub trait Bool: 'static {
     const VALUE: bool;
     /// Type-level conditional: If<Then, Else> (General Type Selector)
+    ///
+    /// `Then` for `Present`, `Else` for `Absent`; neither needs to be a `Bool`.
     type If<Then, Else>;
 
     /// Type-level boolean conditional: Then<T, E> where T, E are Bool.
@@ -21,12 +23,12 @@ pub trait Bool: 'static {
     type Or<Other: Bool>: Bool;
 
     /// Call a static method based on this boolean value.
-    /// If true (Present), calls Then::call().
-    /// If false (Absent), calls Else::call().
-    fn static_dispatch<Then, Else, Output>() -> Output
+    /// If true (Present), calls Then::call(args).
+    /// If false (Absent), calls Else::call(args).
+    fn static_dispatch<Then, Else, Output, Args>(args: Args) -> Output
     where
-        Then: StaticMethodImpl<Output>,
-        Else: StaticMethodImpl<Output>;
+        Then: StaticMethodImpl<Output, Args>,
+        Else: StaticMethodImpl<Output, Args>;
 }
 
 /// Type-level True.
@@ -46,12 +48,12 @@ impl Bool for Present {
     type Or<Other: Bool> = Present;
 
     #[inline(always)]
-    fn static_dispatch<Then, Else, Output>() -> Output
+    fn static_dispatch<Then, Else, Output, Args>(args: Args) -> Output
     where
-        Then: StaticMethodImpl<Output>,
-        Else: StaticMethodImpl<Output>,
+        Then: StaticMethodImpl<Output, Args>,
+        Else: StaticMethodImpl<Output, Args>,
     {
-        Then::call()
+        Then::call(args)
     }
 }
 
@@ -64,12 +66,12 @@ impl Bool for Absent {
     type Or<Other: Bool> = Other;
 
     #[inline(always)]
-    fn static_dispatch<Then, Else, Output>() -> Output
+    fn static_dispatch<Then, Else, Output, Args>(args: Args) -> Output
     where
-        Then: StaticMethodImpl<Output>,
-        Else: StaticMethodImpl<Output>,
+        Then: StaticMethodImpl<Output, Args>,
+        Else: StaticMethodImpl<Output, Args>,
     {
-        Else::call()
+        Else::call(args)
     }
 }
 
@@ -93,16 +95,70 @@ impl<A: Bool, B: Bool> BoolOr<B> for A {
 
 
 /// Type-level NOT.
+///
+/// Blanket over `Bool`, so `Not<Q>` evaluates for any `Evaluate<Q>` (including
+/// a generic `Cap<T>`): `Present -> Absent`, `Absent -> Present`.
 pub trait BoolNot: Bool {
     type Out: Bool;
 }
 
-impl BoolNot for Present {
-    type Out = Absent;
+impl<B: Bool> BoolNot for B {
+    type Out = B::Elif<Absent, Present>;
 }
 
-impl BoolNot for Absent {
-    type Out = Present;
+/// Type-level XOR.
+pub trait BoolXor<Other: Bool>: Bool {
+    type Out: Bool;
+}
+
+impl<B: BoolNot> BoolXor<B> for Present {
+    type Out = <B as BoolNot>::Out;
+}
+
+impl<B: Bool> BoolXor<B> for Absent {
+    type Out = B;
+}
+
+/// Const-fn operations between a type-level [`Bool`] and runtime `bool`s.
+///
+/// ```
+/// use tola_caps::{Absent, ConstBool, Present};
+///
+/// const DEBUG: bool = true;
+/// const LOGGING: bool = ConstBool::<Present>::and(DEBUG);
+/// assert!(LOGGING);
+/// assert!(ConstBool::<Absent>::not());
+/// ```
+pub struct ConstBool<B: Bool>(core::marker::PhantomData<B>);
+
+impl<B: Bool> ConstBool<B> {
+    /// `B` as a runtime `bool`.
+    pub const VALUE: bool = B::VALUE;
+
+    /// `!B`.
+    pub const fn not() -> bool {
+        !B::VALUE
+    }
+
+    /// `B && other`.
+    pub const fn and(other: bool) -> bool {
+        B::VALUE && other
+    }
+
+    /// `B || other`.
+    pub const fn or(other: bool) -> bool {
+        B::VALUE || other
+    }
+
+    /// `B ^ other`.
+    pub const fn xor(other: bool) -> bool {
+        B::VALUE != other
+    }
+
+    /// Picks `then` for `Present`, `otherwise` for `Absent`.
+    pub const fn select<T: Copy>(then: T, otherwise: T) -> T {
+        if B::VALUE { then } else { otherwise }
+    }
 }
 
 /// Convert const bool to type-level Bool.
@@ -100,6 +100,17 @@ pub const fn hash_nibble(s: &str, n: u8) -> u8 {
     ((hash >> (n * 4)) & 0xF) as u8
 }
 
+/// Extract nibble N (0-31) of the 128-bit deep stream
+/// Nibbles 0-15 match [`hash_nibble`]; 16-31 come from a seeded 128-bit hash.
+/// Used for HashStream32 generation (`#[capability(deep)]`)
+pub const fn hash_nibble_deep(s: &str, n: u8) -> u8 {
+    if n < 16 {
+        return hash_nibble(s, n);
+    }
+    let hash = hash_512_h0(s);
+    ((hash >> ((n - 16) * 4)) & 0xF) as u8
+}
+
 // =============================================================================
 // Raw Byte → Nibble extraction
 // =============================================================================
@@ -252,7 +263,8 @@ pub const fn pack_bytes_16(path: &str, name: &str, offset: usize) -> u128 {
 // UniqueId - Hybrid Identity (Type-Hash + Type-Body)
 // =============================================================================
 
-use crate::primitives::identity::IdentityEq;
+use crate::primitives::identity::{IdentityCmp, IdentityEq};
+use crate::primitives::stream::{EqualT, GreaterT, LessT, OrdThen};
 use crate::primitives::pack::TupleEq;
 
 
@@ -408,6 +420,14 @@ pub const fn str_char_at(s: &str, n: usize) -> char {
     }
 }
 
+/// Nibble `k` (0 = high, 1 = low) of the byte at sample position `i`.
+/// Used to build `Byte` identity elements from `concat!()` strings.
+pub const fn sampled_nibble(s: &str, i: usize, k: usize) -> u8 {
+    let indices = sample_indices_64(s.len());
+    let byte = str_char_at(s, indices[i]) as u8;
+    if k == 0 { byte >> 4 } else { byte & 0xF }
+}
+
 /// Get effective length for identity (max 64 chars)
 pub const fn identity_len(s: &str) -> usize {
     let len = s.len();
@@ -422,6 +442,105 @@ impl<T> IdentityEq<TypeMarker<T>> for TypeMarker<T> {
     type Out = Present;
 }
 
+// IList comparison: element-wise, lists of different length differ.
+impl IdentityEq<INil> for INil {
+    type Out = Present;
+}
+
+impl<H, T> IdentityEq<INil> for IList<H, T> {
+    type Out = Absent;
+}
+
+impl<H, T> IdentityEq<IList<H, T>> for INil {
+    type Out = Absent;
+}
+
+impl<H1, T1, H2, T2> IdentityEq<IList<H2, T2>> for IList<H1, T1>
+where
+    H1: IdentityEq<H2>,
+    T1: IdentityEq<T2>,
+    <H1 as IdentityEq<H2>>::Out: crate::primitives::BoolAnd<<T1 as IdentityEq<T2>>::Out>,
+{
+    type Out = <<H1 as IdentityEq<H2>>::Out as crate::primitives::BoolAnd<<T1 as IdentityEq<T2>>::Out>>::Out;
+}
+
+// Tier comparison: same tier compares the lists, different tiers differ.
+macro_rules! impl_tier_eq {
+    ($($tier:ident),*) => {
+        $(
+            impl<A, B> IdentityEq<$tier<B>> for $tier<A>
+            where
+                A: IdentityEq<B>,
+            {
+                type Out = <A as IdentityEq<B>>::Out;
+            }
+        )*
+        impl_tier_eq!(@mismatch [$($tier),*] [$($tier),*]);
+    };
+    (@mismatch [$($lhs:ident),*] $rhs:tt) => {
+        $( impl_tier_eq!(@row $lhs $rhs); )*
+    };
+    (@row $lhs:ident [$($rhs:ident),*]) => {
+        $( impl_tier_eq!(@cell $lhs $rhs); )*
+    };
+    (@cell IList8 IList8) => {};
+    (@cell IList16 IList16) => {};
+    (@cell IList32 IList32) => {};
+    (@cell IList64 IList64) => {};
+    (@cell IListSampled IListSampled) => {};
+    (@cell $lhs:ident $rhs:ident) => {
+        impl<A, B> IdentityEq<$rhs<B>> for $lhs<A> {
+            type Out = Absent;
+        }
+    };
+}
+
+impl_tier_eq!(IList8, IList16, IList32, IList64, IListSampled);
+
+// IList ordering: lexicographic, a proper prefix orders first.
+impl IdentityCmp<INil> for INil {
+    type Out = EqualT;
+}
+
+impl<H, T> IdentityCmp<INil> for IList<H, T> {
+    type Out = GreaterT;
+}
+
+impl<H, T> IdentityCmp<IList<H, T>> for INil {
+    type Out = LessT;
+}
+
+impl<H1, T1, H2, T2> IdentityCmp<IList<H2, T2>> for IList<H1, T1>
+where
+    H1: IdentityCmp<H2>,
+    T1: IdentityCmp<T2>,
+    <H1 as IdentityCmp<H2>>::Out: OrdThen<<T1 as IdentityCmp<T2>>::Out>,
+{
+    type Out = <<H1 as IdentityCmp<H2>>::Out as OrdThen<<T1 as IdentityCmp<T2>>::Out>>::Out;
+}
+
+// Tier ordering ignores the tier and compares the lists themselves.
+macro_rules! impl_tier_cmp {
+    ($($tier:ident),*) => {
+        impl_tier_cmp!(@rows [$($tier),*] [$($tier),*]);
+    };
+    (@rows [$($lhs:ident),*] $rhs:tt) => {
+        $( impl_tier_cmp!(@row $lhs $rhs); )*
+    };
+    (@row $lhs:ident [$($rhs:ident),*]) => {
+        $(
+            impl<A, B> IdentityCmp<$rhs<B>> for $lhs<A>
+            where
+                A: IdentityCmp<B>,
+            {
+                type Out = <A as IdentityCmp<B>>::Out;
+            }
+        )*
+    };
+}
+
+impl_tier_cmp!(IList8, IList16, IList32, IList64, IListSampled);
+
 // =============================================================================
 // IdentityBytes
 // =============================================================================
@@ -467,8 +586,6 @@ impl<
 // But Rust doesn't allow overlapping impls without specialization.
 //
 // WORKAROUND: Since we use full module_path in both Stream hash AND Identity,
-// two capabilities with the same Stream hash MUST have the same Identity
-// (because they're from the same module with the same name).
-// Therefore, Bucket collision (same hash, different Identity) should NEVER happen.
-//
-// If it does happen (extremely rare 64-bit hash collision), compilation fails safely.
+// two capabilities with the same Stream hash almost always have the same
+// Identity. A true collision (e.g. identical explicit streams) is stored in a
+// Bucket, whose members are compared with the sampled `IList` identities.
@@ -1,7 +1,8 @@
 //! Identity primitives for Type Tuple system.
 
 use core::marker::PhantomData;
-use crate::primitives::nibble::{Nibble, NibbleEq};
+use crate::primitives::nibble::{Nibble, NibbleCmp, NibbleEq};
+use crate::primitives::stream::{OrdThen, Ordering3};
 use crate::primitives::stream::{StreamEq, DefaultMaxDepth};
 use crate::primitives::stream::HashStream;
 use crate::primitives::{Bool, Present, Absent};
@@ -25,6 +26,13 @@ pub trait IdentityEq<Other: ?Sized> {
     type Out: Bool;
 }
 
+/// Trait to order Identities lexicographically, byte by byte.
+///
+/// Shorter lists order before their extensions.
+pub trait IdentityCmp<Other: ?Sized> {
+    type Out: Ordering3;
+}
+
 // =============================================================================
 // Primitive Implementations
 // =============================================================================
@@ -62,6 +70,17 @@ where
     type Out = <<A0 as NibbleEq<B0>>::Out as crate::primitives::BoolAnd<<A1 as NibbleEq<B1>>::Out>>::Out;
 }
 
+// Byte ordering - high nibble, then low nibble
+impl<A0, A1, B0, B1> IdentityCmp<Byte<B0, B1>> for Byte<A0, A1>
+where
+    A0: Nibble + NibbleCmp<B0>,
+    A1: Nibble + NibbleCmp<B1>,
+    B0: Nibble, B1: Nibble,
+    <A0 as NibbleCmp<B0>>::Out: OrdThen<<A1 as NibbleCmp<B1>>::Out>,
+{
+    type Out = <<A0 as NibbleCmp<B0>>::Out as OrdThen<<A1 as NibbleCmp<B1>>::Out>>::Out;
+}
+
 // Marker comparison via hash stream equality (legacy)
 impl<S1: HashStream, S2: HashStream> IdentityEq<Marker<S2>> for Marker<S1>
 where
@@ -19,6 +19,6 @@ pub mod byte_cmp;
 
 
 // Re-export key types at this level
-pub use bool::{Bool, Present, Absent, BoolAnd, BoolOr, BoolNot, SelectBool};
-pub use nibble::{Nibble, X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, XA, XB, XC, XD, XE, XF, NibbleEq};
-pub use stream::{HashStream, GetTail, Cons, ConstStream, Z, S, Peano};
+pub use bool::{Bool, Present, Absent, BoolAnd, BoolOr, BoolNot, BoolXor, SelectBool, ConstBool};
+pub use nibble::{Nibble, X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, XA, XB, XC, XD, XE, XF, NibbleEq, NibbleCmp};
+pub use stream::{HashStream, GetTail, Cons, ConstStream, WithDepth, StreamDepth, Z, S, Peano, PeanoAdd, PeanoVal, PeanoLessEq, PeanoEq, PeanoLess, PeanoCmp, Ordering3, OrdThen, LessT, EqualT, GreaterT, StreamPrefix, ToPeano};
@@ -3,6 +3,7 @@
 //! Nibbles are used for hash-based routing in the 16-ary trie.
 
 use super::bool::{Bool, Present, Absent};
+use super::stream::{Ordering3, LessT, EqualT, GreaterT};
 
 // =============================================================================
 // Nibble iteration macros
@@ -51,16 +52,24 @@ macro_rules! for_distinct_pairs {
 // =============================================================================
 
 /// Type-level nibble (4-bit value, 0..15)
-pub trait Nibble: 'static {}
+pub trait Nibble: 'static {
+    /// Runtime value of this nibble (0..=15).
+    const VALUE: u8;
+}
 
 // Define structs X0..XF and implement Nibble
 macro_rules! define_nibble {
-    ($n:ident) => {
+    ($n:ident = $v:literal) => {
         pub struct $n;
-        impl Nibble for $n {}
+        impl Nibble for $n {
+            const VALUE: u8 = $v;
+        }
     };
 }
-for_each_nibble!(define_nibble);
+define_nibble!(X0 = 0x0); define_nibble!(X1 = 0x1); define_nibble!(X2 = 0x2); define_nibble!(X3 = 0x3);
+define_nibble!(X4 = 0x4); define_nibble!(X5 = 0x5); define_nibble!(X6 = 0x6); define_nibble!(X7 = 0x7);
+define_nibble!(X8 = 0x8); define_nibble!(X9 = 0x9); define_nibble!(XA = 0xA); define_nibble!(XB = 0xB);
+define_nibble!(XC = 0xC); define_nibble!(XD = 0xD); define_nibble!(XE = 0xE); define_nibble!(XF = 0xF);
 
 // =============================================================================
 // Const to Type Mapping (Map<N> -> Xn)
@@ -109,6 +118,29 @@ impl_eq_self!(X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, XA, XB, XC, XD, XE, XF);
 macro_rules! impl_neq { ($a:ident, $b:ident) => { impl NibbleEq<$b> for $a { type Out = Absent; } }; }
 for_distinct_pairs!(impl_neq);
 
+// =============================================================================
+// Nibble ordering
+// =============================================================================
+
+/// Type-level nibble comparison by value
+pub trait NibbleCmp<Other: Nibble>: Nibble {
+    type Out: Ordering3;
+}
+
+// Each head is less than every later nibble and greater than none.
+macro_rules! impl_nibble_cmp {
+    ([$head:ident $(, $tail:ident)*]) => {
+        impl NibbleCmp<$head> for $head { type Out = EqualT; }
+        $(
+            impl NibbleCmp<$tail> for $head { type Out = LessT; }
+            impl NibbleCmp<$head> for $tail { type Out = GreaterT; }
+        )*
+        impl_nibble_cmp!([$($tail),*]);
+    };
+    ([]) => {};
+}
+impl_nibble_cmp!([X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, XA, XB, XC, XD, XE, XF]);
+
 // =============================================================================
 // HexAnd (Bitwise AND)
 // =============================================================================
@@ -59,6 +59,84 @@ impl<H: Nibble + 'static, T: HashStream> HashStream for Cons<H, T> {
     type Tail = T;
 }
 
+/// A stream that carries a non-default comparison depth.
+///
+/// Routes exactly like `St`; only its [`StreamDepth`] differs. Emitted by
+/// `#[capability(max_depth = N)]`.
+pub struct WithDepth<St, D>(PhantomData<(St, D)>);
+
+impl<St: HashStream, D: 'static> HashStream for WithDepth<St, D> {
+    type Head = St::Head;
+    type Tail = St::Tail;
+}
+
+// =============================================================================
+// Stream depth
+// =============================================================================
+
+/// How many nibbles of a capability's stream are compared for equality.
+///
+/// Read through [`CapabilityConfig`](crate::CapabilityConfig). The built-in
+/// streams compare [`DefaultMaxDepth`], except [`HashStream32`] (`D32`) and
+/// [`WithDepth`]. A custom [`HashStream`] type used as a capability stream
+/// implements this as well.
+pub trait StreamDepth {
+    /// Number of nibbles compared for equality.
+    type MaxDepth: Peano;
+}
+
+impl<N> StreamDepth for ConstStream<N> {
+    type MaxDepth = DefaultMaxDepth;
+}
+
+impl<A, B> StreamDepth for AltStream<A, B> {
+    type MaxDepth = DefaultMaxDepth;
+}
+
+impl<H, T> StreamDepth for Cons<H, T> {
+    type MaxDepth = DefaultMaxDepth;
+}
+
+impl<St, D: Peano> StreamDepth for WithDepth<St, D> {
+    type MaxDepth = D;
+}
+
+// =============================================================================
+// Stream prefix as runtime values
+// =============================================================================
+
+/// Longest prefix [`StreamPrefix`] holds: the deepest `max_depth` (`D64`).
+pub const MAX_PREFIX: usize = 64;
+
+/// The first `N` nibbles of a stream as runtime values, head first.
+///
+/// Built recursively: the head's value followed by the tail's `N - 1` prefix.
+/// Slots past `N` (up to [`MAX_PREFIX`]) are zero.
+pub trait StreamPrefix<N> {
+    const NIBBLES: [u8; MAX_PREFIX];
+}
+
+impl<H: HashStream> StreamPrefix<Z> for H {
+    const NIBBLES: [u8; MAX_PREFIX] = [0; MAX_PREFIX];
+}
+
+impl<H: HashStream, N> StreamPrefix<S<N>> for H
+where
+    H::Tail: StreamPrefix<N>,
+{
+    const NIBBLES: [u8; MAX_PREFIX] = {
+        let tail = <H::Tail as StreamPrefix<N>>::NIBBLES;
+        let mut out = [0; MAX_PREFIX];
+        out[0] = <H::Head as Nibble>::VALUE;
+        let mut i = 1;
+        while i < MAX_PREFIX {
+            out[i] = tail[i - 1];
+            i += 1;
+        }
+        out
+    };
+}
+
 // =============================================================================
 // Peano Numbers
 // =============================================================================
@@ -77,6 +155,180 @@ impl<N: Peano> Peano for S<N> {}
 // Generate D0..D64 using proc-macro
 macros::peano!(64);
 
+/// Convert a `const u32` in `0..=64` to its Peano number (`D0..D64`).
+///
+/// Stable Rust cannot compute a type from a generic const, so this is a
+/// lookup table; values above 64 have no impl.
+pub trait ToPeano<const N: u32> {
+    type Out: Peano;
+}
+
+macro_rules! impl_to_peano {
+    ($($n:literal => $d:ident),* $(,)?) => {
+        $(impl ToPeano<$n> for () { type Out = $d; })*
+    };
+}
+
+impl_to_peano!(
+    0 => D0, 1 => D1, 2 => D2, 3 => D3, 4 => D4, 5 => D5, 6 => D6, 7 => D7,
+    8 => D8, 9 => D9, 10 => D10, 11 => D11, 12 => D12, 13 => D13, 14 => D14, 15 => D15,
+    16 => D16, 17 => D17, 18 => D18, 19 => D19, 20 => D20, 21 => D21, 22 => D22, 23 => D23,
+    24 => D24, 25 => D25, 26 => D26, 27 => D27, 28 => D28, 29 => D29, 30 => D30, 31 => D31,
+    32 => D32, 33 => D33, 34 => D34, 35 => D35, 36 => D36, 37 => D37, 38 => D38, 39 => D39,
+    40 => D40, 41 => D41, 42 => D42, 43 => D43, 44 => D44, 45 => D45, 46 => D46, 47 => D47,
+    48 => D48, 49 => D49, 50 => D50, 51 => D51, 52 => D52, 53 => D53, 54 => D54, 55 => D55,
+    56 => D56, 57 => D57, 58 => D58, 59 => D59, 60 => D60, 61 => D61, 62 => D62, 63 => D63,
+    64 => D64,
+);
+
+/// Type-level addition (recursive on the first operand)
+pub trait PeanoAdd<Other: Peano>: Peano {
+    type Out: Peano;
+}
+
+impl<M: Peano> PeanoAdd<M> for Z {
+    type Out = M;
+}
+
+impl<N: PeanoAdd<M>, M: Peano> PeanoAdd<M> for S<N> {
+    type Out = S<<N as PeanoAdd<M>>::Out>;
+}
+
+/// Type-level `Self <= Other`
+pub trait PeanoLessEq<Other: Peano>: Peano {
+    type Out: Bool;
+}
+
+impl<M: Peano> PeanoLessEq<M> for Z {
+    type Out = Present;
+}
+
+impl<N: Peano> PeanoLessEq<Z> for S<N> {
+    type Out = Absent;
+}
+
+impl<N: PeanoLessEq<M>, M: Peano> PeanoLessEq<S<M>> for S<N> {
+    type Out = <N as PeanoLessEq<M>>::Out;
+}
+
+/// Type-level `Self == Other`
+pub trait PeanoEq<Other: Peano>: Peano {
+    type Out: Bool;
+}
+
+impl PeanoEq<Z> for Z {
+    type Out = Present;
+}
+
+impl<M: Peano> PeanoEq<S<M>> for Z {
+    type Out = Absent;
+}
+
+impl<N: Peano> PeanoEq<Z> for S<N> {
+    type Out = Absent;
+}
+
+impl<N: PeanoEq<M>, M: Peano> PeanoEq<S<M>> for S<N> {
+    type Out = <N as PeanoEq<M>>::Out;
+}
+
+/// Type-level `Self < Other`
+pub trait PeanoLess<Other: Peano>: Peano {
+    type Out: Bool;
+}
+
+impl PeanoLess<Z> for Z {
+    type Out = Absent;
+}
+
+impl<M: Peano> PeanoLess<S<M>> for Z {
+    type Out = Present;
+}
+
+impl<N: Peano> PeanoLess<Z> for S<N> {
+    type Out = Absent;
+}
+
+impl<N: PeanoLess<M>, M: Peano> PeanoLess<S<M>> for S<N> {
+    type Out = <N as PeanoLess<M>>::Out;
+}
+
+/// Type-level three-way ordering: [`LessT`], [`EqualT`] or [`GreaterT`].
+pub trait Ordering3 {
+    /// Runtime equivalent.
+    const VALUE: core::cmp::Ordering;
+}
+
+/// `Self < Other`
+pub struct LessT;
+/// `Self == Other`
+pub struct EqualT;
+/// `Self > Other`
+pub struct GreaterT;
+
+impl Ordering3 for LessT {
+    const VALUE: core::cmp::Ordering = core::cmp::Ordering::Less;
+}
+
+impl Ordering3 for EqualT {
+    const VALUE: core::cmp::Ordering = core::cmp::Ordering::Equal;
+}
+
+impl Ordering3 for GreaterT {
+    const VALUE: core::cmp::Ordering = core::cmp::Ordering::Greater;
+}
+
+/// Lexicographic chaining: `Self` unless it is [`EqualT`], then `Next`.
+pub trait OrdThen<Next: Ordering3>: Ordering3 {
+    type Out: Ordering3;
+}
+
+impl<Next: Ordering3> OrdThen<Next> for LessT {
+    type Out = LessT;
+}
+
+impl<Next: Ordering3> OrdThen<Next> for EqualT {
+    type Out = Next;
+}
+
+impl<Next: Ordering3> OrdThen<Next> for GreaterT {
+    type Out = GreaterT;
+}
+
+/// Type-level three-way comparison of `Self` with `Other`
+pub trait PeanoCmp<Other: Peano>: Peano {
+    type Out: Ordering3;
+}
+
+impl PeanoCmp<Z> for Z {
+    type Out = EqualT;
+}
+
+impl<M: Peano> PeanoCmp<S<M>> for Z {
+    type Out = LessT;
+}
+
+impl<N: Peano> PeanoCmp<Z> for S<N> {
+    type Out = GreaterT;
+}
+
+impl<N: PeanoCmp<M>, M: Peano> PeanoCmp<S<M>> for S<N> {
+    type Out = <N as PeanoCmp<M>>::Out;
+}
+
+/// Runtime value of a Peano number
+pub trait PeanoVal: Peano {
+    const VAL: usize;
+}
+
+impl PeanoVal for Z {
+    const VAL: usize = 0;
+}
+
+impl<N: PeanoVal> PeanoVal for S<N> {
+    const VAL: usize = N::VAL + 1;
+}
+
 /// Default max depth for collision resolution (16 nibbles = 64 bits)
 pub type DefaultMaxDepth = D16;
 
@@ -95,30 +347,36 @@ impl<A: HashStream, B: HashStream> StreamEq<B, Z> for A {
     type Out = Present;
 }
 
+// Only the heads are compared here; the tails are projected inside the
+// `Present` dispatch, so a mismatch never computes `A::Tail`/`B::Tail`.
 impl<A, B, L> StreamEq<B, S<L>> for A
 where
     A: HashStream,
     B: HashStream,
     A::Head: super::nibble::NibbleEq<B::Head>,
-    <A::Head as super::nibble::NibbleEq<B::Head>>::Out: StreamEqDispatch<A::Tail, B::Tail, L>,
+    <A::Head as super::nibble::NibbleEq<B::Head>>::Out: StreamEqDispatch<A, B, L>,
 {
-    type Out = <<A::Head as super::nibble::NibbleEq<B::Head>>::Out as StreamEqDispatch<A::Tail, B::Tail, L>>::Out;
+    type Out = <<A::Head as super::nibble::NibbleEq<B::Head>>::Out as StreamEqDispatch<A, B, L>>::Out;
 }
 
-pub trait StreamEqDispatch<TailA, TailB, Limit> {
+/// Continues a [`StreamEq`] walk once the heads of `A` and `B` compared.
+///
+/// `Absent` stops with no bounds; `Present` moves on to the tails.
+pub trait StreamEqDispatch<A, B, Limit> {
     type Out: Bool;
 }
 
-impl<TailA, TailB, L> StreamEqDispatch<TailA, TailB, L> for Absent {
+impl<A, B, L> StreamEqDispatch<A, B, L> for Absent {
     type Out = Absent;
 }
 
-impl<TailA, TailB, L> StreamEqDispatch<TailA, TailB, L> for Present
+impl<A, B, L> StreamEqDispatch<A, B, L> for Present
 where
-    TailA: HashStream + StreamEq<TailB, L>,
-    TailB: HashStream,
+    A: HashStream,
+    B: HashStream,
+    A::Tail: StreamEq<B::Tail, L>,
 {
-    type Out = <TailA as StreamEq<TailB, L>>::Out;
+    type Out = <A::Tail as StreamEq<B::Tail, L>>::Out;
 }
 
 // =============================================================================
@@ -202,6 +460,67 @@ where
     type Tail = HashStream16<N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15, N0>;
 }
 
+/// Build a hash stream from 32 const nibble values (for 128-bit hash)
+/// Usage: HashStream32<{n0}, {n1}, ..., {n31}>
+///
+/// Emitted by `#[capability(deep)]`, compared at `D32`.
+pub struct HashStream32<
+    const N0: u8, const N1: u8, const N2: u8, const N3: u8,
+    const N4: u8, const N5: u8, const N6: u8, const N7: u8,
+    const N8: u8, const N9: u8, const N10: u8, const N11: u8,
+    const N12: u8, const N13: u8, const N14: u8, const N15: u8,
+    const N16: u8, const N17: u8, const N18: u8, const N19: u8,
+    const N20: u8, const N21: u8, const N22: u8, const N23: u8,
+    const N24: u8, const N25: u8, const N26: u8, const N27: u8,
+    const N28: u8, const N29: u8, const N30: u8, const N31: u8,
+>(PhantomData<()>);
+
+impl<
+    const N0: u8, const N1: u8, const N2: u8, const N3: u8,
+    const N4: u8, const N5: u8, const N6: u8, const N7: u8,
+    const N8: u8, const N9: u8, const N10: u8, const N11: u8,
+    const N12: u8, const N13: u8, const N14: u8, const N15: u8,
+    const N16: u8, const N17: u8, const N18: u8, const N19: u8,
+    const N20: u8, const N21: u8, const N22: u8, const N23: u8,
+    const N24: u8, const N25: u8, const N26: u8, const N27: u8,
+    const N28: u8, const N29: u8, const N30: u8, const N31: u8,
+> HashStream for HashStream32<N0, N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15, N16, N17, N18, N19, N20, N21, N22, N23, N24, N25, N26, N27, N28, N29, N30, N31>
+where
+    (): SelectNibble<N0> + SelectNibble<N1> + SelectNibble<N2> + SelectNibble<N3>
+      + SelectNibble<N4> + SelectNibble<N5> + SelectNibble<N6> + SelectNibble<N7>
+      + SelectNibble<N8> + SelectNibble<N9> + SelectNibble<N10> + SelectNibble<N11>
+      + SelectNibble<N12> + SelectNibble<N13> + SelectNibble<N14> + SelectNibble<N15>
+      + SelectNibble<N16> + SelectNibble<N17> + SelectNibble<N18> + SelectNibble<N19>
+      + SelectNibble<N20> + SelectNibble<N21> + SelectNibble<N22> + SelectNibble<N23>
+      + SelectNibble<N24> + SelectNibble<N25> + SelectNibble<N26> + SelectNibble<N27>
+      + SelectNibble<N28> + SelectNibble<N29> + SelectNibble<N30> + SelectNibble<N31>,
+{
+    type Head = <() as SelectNibble<N0>>::Out;
+    type Tail = HashStream32<N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15, N16, N17, N18, N19, N20, N21, N22, N23, N24, N25, N26, N27, N28, N29, N30, N31, N0>;
+}
+
+impl<
+    const N0: u8, const N1: u8, const N2: u8, const N3: u8,
+    const N4: u8, const N5: u8, const N6: u8, const N7: u8,
+    const N8: u8, const N9: u8, const N10: u8, const N11: u8,
+    const N12: u8, const N13: u8, const N14: u8, const N15: u8,
+> StreamDepth for HashStream16<N0, N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15> {
+    type MaxDepth = DefaultMaxDepth;
+}
+
+impl<
+    const N0: u8, const N1: u8, const N2: u8, const N3: u8,
+    const N4: u8, const N5: u8, const N6: u8, const N7: u8,
+    const N8: u8, const N9: u8, const N10: u8, const N11: u8,
+    const N12: u8, const N13: u8, const N14: u8, const N15: u8,
+    const N16: u8, const N17: u8, const N18: u8, const N19: u8,
+    const N20: u8, const N21: u8, const N22: u8, const N23: u8,
+    const N24: u8, const N25: u8, const N26: u8, const N27: u8,
+    const N28: u8, const N29: u8, const N30: u8, const N31: u8,
+> StreamDepth for HashStream32<N0, N1, N2, N3, N4, N5, N6, N7, N8, N9, N10, N11, N12, N13, N14, N15, N16, N17, N18, N19, N20, N21, N22, N23, N24, N25, N26, N27, N28, N29, N30, N31> {
+    type MaxDepth = D32;
+}
+
 // =============================================================================
 // ByteStream128 - Disabled (requires nightly features for proper streaming)
 // =============================================================================
@@ -62,21 +62,21 @@ where
 /// This trait enables calling `StaticMethodImpl::call()` through type-level selection
 /// without requiring the caller to prove that `SelectCap::Out` implements `StaticMethodImpl`.
 ///
-/// `Then` and `Else` must implement `StaticMethodImpl<Output>`.
-pub trait SelectStaticCall<Q, Then, Else, Output> {
-    fn call() -> Output;
+/// `Then` and `Else` must implement `StaticMethodImpl<Output, Args>`.
+pub trait SelectStaticCall<Q, Then, Else, Output, Args = ()> {
+    fn call(args: Args) -> Output;
 }
 
-impl<S, Q, Then, Else, Output> SelectStaticCall<Q, Then, Else, Output> for S
+impl<S, Q, Then, Else, Output, Args> SelectStaticCall<Q, Then, Else, Output, Args> for S
 where
     S: Evaluate<Q>,
     <S as Evaluate<Q>>::Out: Bool,
-    Then: StaticMethodImpl<Output>,
-    Else: StaticMethodImpl<Output>,
+    Then: StaticMethodImpl<Output, Args>,
+    Else: StaticMethodImpl<Output, Args>,
 {
     #[inline(always)]
-    fn call() -> Output {
-        <S as Evaluate<Q>>::Out::static_dispatch::<Then, Else, Output>()
+    fn call(args: Args) -> Output {
+        <S as Evaluate<Q>>::Out::static_dispatch::<Then, Else, Output, Args>(args)
     }
 }
 
@@ -84,27 +84,27 @@ where
 ///
 /// This trait is implemented for `Present` and `Absent` to enable
 /// type-level branching for static method calls.
-pub trait BoolStaticCall<Then, Else, Output> {
-    fn call() -> Output;
+pub trait BoolStaticCall<Then, Else, Output, Args = ()> {
+    fn call(args: Args) -> Output;
 }
 
-impl<Then, Else, Output> BoolStaticCall<Then, Else, Output> for Present
+impl<Then, Else, Output, Args> BoolStaticCall<Then, Else, Output, Args> for Present
 where
-    Then: StaticMethodImpl<Output>,
+    Then: StaticMethodImpl<Output, Args>,
 {
     #[inline(always)]
-    fn call() -> Output {
-        Then::call()
+    fn call(args: Args) -> Output {
+        Then::call(args)
     }
 }
 
-impl<Then, Else, Output> BoolStaticCall<Then, Else, Output> for Absent
+impl<Then, Else, Output, Args> BoolStaticCall<Then, Else, Output, Args> for Absent
 where
-    Else: StaticMethodImpl<Output>,
+    Else: StaticMethodImpl<Output, Args>,
 {
     #[inline(always)]
-    fn call() -> Output {
-        Else::call()
+    fn call(args: Args) -> Output {
+        Else::call(args)
     }
 }
 
@@ -116,16 +116,37 @@ pub struct StaticSelect<Cap, Q, Then, Else>(
     core::marker::PhantomData<(Cap, Q, Then, Else)>
 );
 
-impl<Cap, Q, Then, Else, Output> StaticMethodImpl<Output> for StaticSelect<Cap, Q, Then, Else>
+impl<Cap, Q, Then, Else, Output, Args> StaticMethodImpl<Output, Args> for StaticSelect<Cap, Q, Then, Else>
 where
-    Cap: SelectStaticCall<Q, Then, Else, Output>,
+    Cap: SelectStaticCall<Q, Then, Else, Output, Args>,
 {
     #[inline(always)]
-    fn call() -> Output {
-        <Cap as SelectStaticCall<Q, Then, Else, Output>>::call()
+    fn call(args: Args) -> Output {
+        <Cap as SelectStaticCall<Q, Then, Else, Output, Args>>::call(args)
     }
 }
 
+/// Wrapper type for chaining associated const selections.
+///
+/// Implements `ConstImpl<V>` with `Then::VALUE` if `Cap: Evaluate<Q>` is
+/// `Present`, else `Else::VALUE`, enabling nested selections.
+pub struct ConstSelect<Cap, Q, Then, Else>(
+    core::marker::PhantomData<(Cap, Q, Then, Else)>
+);
+
+impl<Cap, Q, Then, Else, V> ConstImpl<V> for ConstSelect<Cap, Q, Then, Else>
+where
+    Cap: Evaluate<Q>,
+    Then: ConstImpl<V>,
+    Else: ConstImpl<V>,
+{
+    const VALUE: V = if <<Cap as Evaluate<Q>>::Out as Bool>::VALUE {
+        Then::VALUE
+    } else {
+        Else::VALUE
+    };
+}
+
 // =============================================================================
 // Compound Selectors (And, Or, Not)
 // =============================================================================
@@ -171,13 +192,23 @@ where
 // =============================================================================
 
 /// Trait for method implementations that can be type-selected.
-pub trait MethodImpl<T: ?Sized, Output = ()> {
-    fn call(value: &T) -> Output;
+///
+/// `Args` is the tuple of parameters after the receiver, e.g. `(&T,)` for
+/// `fn combine(&self, other: &Self)`.
+pub trait MethodImpl<T: ?Sized, Output = (), Args = ()> {
+    fn call(value: &T, args: Args) -> Output;
 }
 
 /// Trait for static/associated function implementations (no self parameter).
-pub trait StaticMethodImpl<Output = ()> {
-    fn call() -> Output;
+///
+/// `Args` is the tuple of parameters.
+pub trait StaticMethodImpl<Output = (), Args = ()> {
+    fn call(args: Args) -> Output;
+}
+
+/// Trait for associated const implementations (used by specialize! for consts).
+pub trait ConstImpl<V> {
+    const VALUE: V;
 }
 
 // =============================================================================
@@ -209,16 +240,16 @@ pub trait TypeSelector {
 /// Marker type for when no specialized implementation is available.
 pub struct NoImpl;
 
-impl<T: ?Sized, Output: Default> MethodImpl<T, Output> for NoImpl {
+impl<T: ?Sized, Output: Default, Args> MethodImpl<T, Output, Args> for NoImpl {
     #[inline(always)]
-    fn call(_value: &T) -> Output {
+    fn call(_value: &T, _args: Args) -> Output {
         Output::default()
     }
 }
 
-impl<Output: Default> StaticMethodImpl<Output> for NoImpl {
+impl<Output: Default, Args> StaticMethodImpl<Output, Args> for NoImpl {
     #[inline(always)]
-    fn call() -> Output {
+    fn call(_args: Args) -> Output {
         Output::default()
     }
 }
@@ -24,7 +24,7 @@
 //! ### Type-Level Dispatch
 //! ```ignore
 //! type Impl = <Cap<T> as SelectClone<CloneImpl, FallbackImpl>>::Out;
-//! Impl::call(&value);
+//! Impl::call(&value, ());
 //! ```
 
 pub mod runtime;
@@ -35,8 +35,8 @@ pub mod dispatch;
 pub use runtime::SpecializeWrapper;
 pub use dispatch::{
     SelectCap, SelectAnd, SelectOr, SelectNot,
-    SelectStaticCall, BoolStaticCall, StaticSelect,
-    MethodImpl, StaticMethodImpl, TypeSelector, NoImpl,
+    SelectStaticCall, BoolStaticCall, StaticSelect, ConstSelect,
+    MethodImpl, StaticMethodImpl, ConstImpl, TypeSelector, NoImpl,
 };
 
 // Select traits are only available when detect feature is enabled
@@ -4,6 +4,8 @@
 
 use super::node::Empty;
 use super::insert::With;
+use super::ops::SetDifference;
+use crate::primitives::Bool;
 
 // =============================================================================
 // Convenience Type Aliases
@@ -24,6 +26,22 @@ pub type CapSet3<A, B, C> = <<<Empty as With<A>>::Out as With<B>>::Out as With<C
 /// Capability set with 4 capabilities
 pub type CapSet4<A, B, C, D> = <<<<Empty as With<A>>::Out as With<B>>::Out as With<C>>::Out as With<D>>::Out;
 
+/// Complement of `Set` within `Universe`: every capability of `Universe`
+/// that is not in `Set`. Require it with [`ComplementOf`](super::evaluate::ComplementOf).
+pub type Complement<Universe, Set> = <Universe as SetDifference<Set>>::Out;
+
+/// Pick one of two capability sets by a type-level [`Bool`].
+///
+/// `<() as SelectSet<Present, A, B>>::Out` is `A`, with `Absent` it is `B`.
+/// Driven by a `const bool` through [`select_set!`](crate::select_set).
+pub trait SelectSet<Cond, Then, Else> {
+    type Out;
+}
+
+impl<Cond: Bool, Then, Else> SelectSet<Cond, Then, Else> for () {
+    type Out = Cond::If<Then, Else>;
+}
+
 // =============================================================================
 // Convenience Macros
 // =============================================================================
@@ -43,33 +61,182 @@ macro_rules! capset {
     };
 }
 
-/// Macro to compute union of two capability sets
-/// Usage: `union![SetA, SetB]`
+/// Macro to compute union of two or more capability sets, folded left
+/// Usage: `union![SetA, SetB]`, `union![SetA, SetB, SetC]`
 #[macro_export]
 macro_rules! union {
-    ($a:ty, $b:ty) => {
+    ($a:ty, $b:ty $(,)?) => {
         <$a as $crate::trie::SetUnion<$b>>::Out
     };
+    ($a:ty, $b:ty, $($rest:ty),+ $(,)?) => {
+        $crate::union![<$a as $crate::trie::SetUnion<$b>>::Out, $($rest),+]
+    };
 }
 
-/// Macro to compute intersection of two capability sets
-/// Usage: `intersect![SetA, SetB]`
+/// Macro to compute intersection of two or more capability sets, folded left
+/// Usage: `intersect![SetA, SetB]`, `intersect![SetA, SetB, SetC]`
 #[macro_export]
 macro_rules! intersect {
-    ($a:ty, $b:ty) => {
+    ($a:ty, $b:ty $(,)?) => {
         <$a as $crate::trie::SetIntersect<$b>>::Out
     };
+    ($a:ty, $b:ty, $($rest:ty),+ $(,)?) => {
+        $crate::intersect![<$a as $crate::trie::SetIntersect<$b>>::Out, $($rest),+]
+    };
+}
+
+/// Macro to compute difference of two capability sets (A minus B)
+/// Usage: `diff![SetA, SetB]`
+#[macro_export]
+macro_rules! diff {
+    ($a:ty, $b:ty) => {
+        <$a as $crate::trie::SetDifference<$b>>::Out
+    };
+}
+
+/// Macro to select one of two capability sets by a `const bool`
+/// Usage: `select_set!(FLAG => SetA, else => SetB)`
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct Net;
+/// #[derive(Capability)] struct Disk;
+///
+/// const NET_ENABLED: bool = true;
+/// type Effective = tola_caps::select_set!(NET_ENABLED => caps![Net, Disk], else => caps![Disk]);
+/// tola_caps::caps_eq!(Effective, caps![Disk, Net]);
+/// ```
+#[macro_export]
+macro_rules! select_set {
+    ($cond:expr => $then:ty, else => $else:ty $(,)?) => {
+        <() as $crate::trie::SelectSet<
+            <() as $crate::primitives::SelectBool<{ $cond }>>::Out,
+            $then,
+            $else,
+        >>::Out
+    };
+}
+
+/// Macro to build a capability set step by step, with `const bool` guards
+/// Usage: `build_set! { CapA; @if(FLAG) CapB; }`
+///
+/// Each `Cap;` is added unconditionally; `@if(COND) Cap;` is added only when
+/// the const expression `COND` is true.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanWrite;
+///
+/// const WRITABLE: bool = false;
+/// type Effective = tola_caps::build_set! { CanRead; @if(WRITABLE) CanWrite; };
+/// tola_caps::caps_eq!(Effective, caps![CanRead]);
+/// ```
+#[macro_export]
+macro_rules! build_set {
+    (@acc $set:ty;) => { $set };
+    (@acc $set:ty; @if($cond:expr) $cap:ty; $($rest:tt)*) => {
+        $crate::build_set!(@acc
+            <() as $crate::trie::SelectSet<
+                <() as $crate::primitives::SelectBool<{ $cond }>>::Out,
+                <$set as $crate::trie::With<$cap>>::Out,
+                $set,
+            >>::Out;
+            $($rest)*)
+    };
+    (@acc $set:ty; $cap:ty; $($rest:tt)*) => {
+        $crate::build_set!(@acc <$set as $crate::trie::With<$cap>>::Out; $($rest)*)
+    };
+    ($($body:tt)*) => {
+        $crate::build_set!(@acc $crate::trie::Empty; $($body)*)
+    };
+}
+
+/// Compile-time assertion that two capability sets have the same members.
+/// Usage: `caps_eq!(SetA, SetB);`
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// tola_caps::caps_eq!(caps![A, B], caps![B, A]);
+/// ```
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// tola_caps::caps_eq!(caps![A, B], caps![A]);
+/// ```
+#[macro_export]
+macro_rules! caps_eq {
+    ($a:ty, $b:ty $(,)?) => {
+        const _: () = {
+            fn __caps_eq<L: $crate::trie::AssertSetEq<R>, R>() {}
+            let _ = __caps_eq::<$a, $b>;
+        };
+    };
+}
+
+/// Compile-time assertion that `Mine` is a subset of `Allowed`.
+/// Usage: `assert_subset!(Mine, Allowed);`
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// tola_caps::assert_subset!(caps![A], caps![A, B]);
+/// tola_caps::assert_subset!(caps![], caps![B]);
+/// ```
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// tola_caps::assert_subset!(caps![B], caps![A]);
+/// ```
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// // A single capability never covers two.
+/// tola_caps::assert_subset!(caps![A, B], caps![A]);
+/// ```
+#[macro_export]
+macro_rules! assert_subset {
+    ($mine:ty, $allowed:ty $(,)?) => {
+        const _: () = {
+            fn __assert_subset<Mine, Allowed>()
+            where
+                Allowed: $crate::trie::SupersetOf<Mine>,
+            {
+            }
+            let _ = __assert_subset::<$mine, $allowed>;
+        };
+    };
 }
 
 /// Macro to add a capability to a set
 /// Usage:
 /// - `with![Set, Cap]` -> Single add
 /// - `with![Set, A, B]` -> Chain add (A then B)
-/// - `with![Cap]` -> Implicit `__C`
+///
+/// The one-argument form `with![Cap]` is only accepted in the signature of a
+/// `#[caps_bound(transparent)]` function, which supplies the injected set
+/// as the base. Anywhere else, including the body of that function, it is
+/// an error pointing at `with![Set, Cap]`; in the body the set is `__C`.
 #[macro_export]
 macro_rules! with {
     ($cap:ty) => {
-        <__C as $crate::trie::With<$cap>>::Out
+        ::core::compile_error!(
+            "`with![Cap]` has no base set; write `with![Set, Cap]` (the one-argument form only works in `#[caps_bound(transparent)]` signatures)"
+        )
     };
     ($set:ty, $cap:ty) => {
         <$set as $crate::trie::With<$cap>>::Out
@@ -79,15 +246,36 @@ macro_rules! with {
     };
 }
 
+/// Macro to fold `With` over a list, starting from an arbitrary base set
+/// Usage:
+/// - `with_all!(Base; A, B, C)` -> Base plus A, B and C
+/// - `with_all!(Base;)` -> Base unchanged
+///
+/// Unlike `caps!`, the base need not be `Empty`, so generated lists can be
+/// layered onto an existing set.
+#[macro_export]
+macro_rules! with_all {
+    ($set:ty; $(,)?) => {
+        $set
+    };
+    ($set:ty; $cap:ty $(, $rest:ty)* $(,)?) => {
+        $crate::with_all!(<$set as $crate::trie::With<$cap>>::Out; $($rest),*)
+    };
+}
+
 /// Macro to remove a capability from a set
 /// Usage:
 /// - `without![Set, Cap]` -> Remove Cap from Set
 /// - `without![Set, A, B]` -> Remove A then B
-/// - `without![Cap]` -> Remove Cap from implicit `__C`
+///
+/// As with [`with!`], the one-argument form `without![Cap]` is only accepted
+/// in `#[caps_bound(transparent)]` signatures.
 #[macro_export]
 macro_rules! without {
     ($cap:ty) => {
-        <__C as $crate::trie::Without<$cap>>::Out
+        ::core::compile_error!(
+            "`without![Cap]` has no base set; write `without![Set, Cap]` (the one-argument form only works in `#[caps_bound(transparent)]` signatures)"
+        )
     };
     ($set:ty, $cap:ty) => {
         <$set as $crate::trie::Without<$cap>>::Out
@@ -1,27 +1,174 @@
 use crate::primitives::nibble::Nibble;
-use crate::primitives::stream::HashStream;
+use crate::primitives::stream::{HashStream, Ordering3, PeanoVal, StreamDepth, StreamPrefix, D16};
 use crate::primitives::GetTail;
 use crate::primitives::Peano;
+use crate::primitives::Bool;
+use crate::primitives::identity::{IdentityCmp, IdentityEq};
+
+/// Per-capability tuning of collision resolution.
+///
+/// `MaxDepth` is how many stream nibbles are compared before two capabilities
+/// are considered equal. The default, [`DefaultMaxDepth`](crate::primitives::stream::DefaultMaxDepth) (`D16`), compares the
+/// full 64-bit hash. A shallower depth speeds up comparisons but treats any two
+/// capabilities that agree on the first `MaxDepth` nibbles as the same one; a
+/// deeper depth only helps for streams longer than 16 nibbles, such as the
+/// 32-nibble streams of `#[capability(deep)]` (compared at `D32`).
+///
+/// Comparisons use the depth of the capability being looked up or inserted.
+/// Every [`Capability`] implements this through its stream's
+/// [`StreamDepth`], so hand-written impls keep the default. Override it with
+/// `#[capability(max_depth = 8)]`, or with a
+/// [`WithDepth`](crate::primitives::stream::WithDepth) stream.
+pub trait CapabilityConfig {
+    /// Number of nibbles compared for equality.
+    type MaxDepth: Peano;
+}
+
+impl<C: Capability> CapabilityConfig for C {
+    type MaxDepth = MaxDepthOf<C>;
+}
+
+/// `CapabilityConfig::MaxDepth` of `C`, spelled through its stream so that
+/// where-clauses over a generic `C` normalize it the same way everywhere.
+pub(crate) type MaxDepthOf<C> = <<C as Capability>::Stream as StreamDepth>::MaxDepth;
 
 /// Capability Trait
 ///
 /// Implemented by unit structs representing capabilities.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a capability",
+    label = "missing `#[derive(Capability)]`",
+    note = "add `#[derive(Capability)]` to `{Self}`"
+)]
 pub trait Capability: 'static {
     /// Type-level nibble stream responsible for Routing (Trie Path).
     /// This is typically a short hash (64-bit) of the identity.
-    type Stream: HashStream;
+    type Stream: HashStream + StreamDepth;
 
     /// Unique Type-Level Identity.
     /// Typically a Tuple of `Char` types representing the full name.
     /// Used for collision resolution (Equality checks) in leaf nodes.
     type Identity: ?Sized + 'static;
 
+    /// Human-readable name, used for runtime inspection and logging.
+    ///
+    /// `#[derive(Capability)]` sets it to the struct name; hand-written
+    /// impls provide their own.
+    const NAME: &'static str;
+
     /// Helper to get the stream head at depth D (used for trie building)
     type At<D: Peano>: Nibble
     where
         Self::Stream: GetTail<D>;
 }
 
+/// A capability with a version, for [`HasAtLeast`](crate::trie::HasAtLeast).
+///
+/// Versions of one capability share its identity (`#[capability(alias_of = Base)]`)
+/// and so occupy the same slot; a set holds at most one of them. Set with
+/// `#[capability(version = N)]`, `N` in `0..=64`.
+pub trait VersionedCapability: Capability {
+    /// The version number.
+    const VERSION: u32;
+
+    /// `VERSION` as a Peano number, for type-level comparison.
+    type Version: Peano;
+}
+
+/// Compare two capabilities by full `Identity` rather than by hash stream.
+///
+/// `Present` iff both identities are equal. Unlike stream comparison this
+/// tells apart capabilities whose hash streams collide.
+pub trait CapabilityIdentityEq<Other: Capability>: Capability {
+    type Out: Bool;
+}
+
+impl<A, B> CapabilityIdentityEq<B> for A
+where
+    A: Capability,
+    B: Capability,
+    A::Identity: IdentityEq<B::Identity>,
+{
+    type Out = <A::Identity as IdentityEq<B::Identity>>::Out;
+}
+
+/// Total order on capabilities by `Identity` (the hashed key, byte by byte).
+///
+/// Independent of routing, so it gives a canonical order for output such as
+/// [`SortSet`](crate::trie::SortSet).
+pub trait CapabilityOrd<Other: Capability>: Capability {
+    type Out: Ordering3;
+}
+
+impl<A, B> CapabilityOrd<B> for A
+where
+    A: Capability,
+    B: Capability,
+    A::Identity: IdentityCmp<B::Identity>,
+{
+    type Out = <A::Identity as IdentityCmp<B::Identity>>::Out;
+}
+
+/// A capability's routing path as runtime nibble values, root first.
+///
+/// `PATH[d]` is the Node16 slot taken at depth `d`, e.g. for rendering
+/// "CanRead routes through slots [3, A, 0, ...]".
+pub trait CapabilityPath: Capability {
+    const PATH: [u8; 16];
+}
+
+impl<C> CapabilityPath for C
+where
+    C: Capability,
+    C::Stream: StreamPrefix<D16>,
+{
+    const PATH: [u8; 16] = {
+        let nibbles = <C::Stream as StreamPrefix<D16>>::NIBBLES;
+        let mut out = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            out[i] = nibbles[i];
+            i += 1;
+        }
+        out
+    };
+}
+
+/// The slot nibbles a `Has<Cap>` query descends through, for tooling.
+///
+/// `STEPS[d]` is the Node16 slot taken at depth `d`, for every depth the
+/// capability is compared at (`CapabilityConfig::MaxDepth`): 16 steps by
+/// default, 32 for `deep`. Its first 16 entries are
+/// [`CapabilityPath::PATH`]. The route depends only on the capability;
+/// evaluation against a set stops at the first node that is not a `Node16`,
+/// so only a prefix of `STEPS` is walked in practice.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::EvalPath;
+///
+/// #[derive(Capability)]
+/// #[capability(stream = "X3 X0 XF")]
+/// struct CanRead;
+///
+/// let steps = <CanRead as EvalPath>::STEPS;
+/// assert_eq!(steps.len(), 16);
+/// assert_eq!(&steps[..4], [0x3, 0x0, 0xF, 0x0]);
+/// ```
+pub trait EvalPath: CapabilityPath {
+    const STEPS: &'static [u8];
+}
+
+impl<C> EvalPath for C
+where
+    C: CapabilityPath,
+    MaxDepthOf<C>: PeanoVal,
+    C::Stream: StreamPrefix<MaxDepthOf<C>>,
+{
+    const STEPS: &'static [u8] =
+        <C::Stream as StreamPrefix<MaxDepthOf<C>>>::NIBBLES.split_at(<MaxDepthOf<C> as PeanoVal>::VAL).0;
+}
+
 // -----------------------------------------------------------------------------
 // Macros
 // -----------------------------------------------------------------------------
@@ -42,8 +189,74 @@ macro_rules! impl_capability {
          impl $crate::Capability for $name {
             type Stream = $stream;
             type Identity = $identity;
+            const NAME: &'static str = stringify!($name);
             type At<D: $crate::Peano> = <<Self::Stream as $crate::GetTail<D>>::Out as $crate::HashStream>::Head
             where Self::Stream: $crate::GetTail<D>;
         }
     };
 }
+
+/// Compile-time assertion that each type implements [`Capability`].
+/// Usage: `assert_capability!(CanRead, CanWrite);`
+///
+/// A forgotten `#[derive(Capability)]` is then reported at the assertion
+/// instead of deep inside the trie types built by `caps![]`.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct CanRead;
+///
+/// tola_caps::assert_capability!(CanRead);
+/// ```
+///
+/// ```compile_fail
+/// struct NotACap;
+///
+/// tola_caps::assert_capability!(NotACap);
+/// ```
+#[macro_export]
+macro_rules! assert_capability {
+    ($($cap:ty),+ $(,)?) => {
+        const _: () = {
+            fn __assert_capability<C: $crate::trie::Capability>() {}
+            $(let _ = __assert_capability::<$cap>;)+
+        };
+    };
+}
+
+/// Re-export a capability from [`define_capabilities!`](crate::define_capabilities)
+/// under a new name, e.g. to keep an old name working during a rename.
+///
+/// `capability_alias!(CanView = CanRead)` generates `pub type CanViewCap = CanReadCap;`
+/// (same `Stream` and `Identity`) plus `HasCanViewCap` / `NotHasCanViewCap`
+/// delegating to `HasCanReadCap` / `NotHasCanReadCap`. Attributes such as
+/// `#[deprecated]` are applied to the type alias.
+///
+/// ```
+/// use tola_caps::prelude::*;
+///
+/// tola_caps::define_capabilities! {
+///     CanRead => "Can read",
+/// }
+/// tola_caps::capability_alias!(CanView = CanRead);
+///
+/// fn view<C: HasCanViewCap>() {}
+/// view::<caps![CanReadCap]>();
+/// ```
+#[macro_export]
+macro_rules! capability_alias {
+    ($(#[$meta:meta])* $new:ident = $old:ident $(,)?) => {
+        $crate::paste::paste! {
+            $(#[$meta])*
+            pub type [<$new Cap>] = [<$old Cap>];
+
+            #[doc = "Alias of `Has" $old "Cap`."]
+            pub trait [<Has $new Cap>]: [<Has $old Cap>] {}
+            impl<C: [<Has $old Cap>]> [<Has $new Cap>] for C {}
+
+            #[doc = "Alias of `NotHas" $old "Cap`."]
+            pub trait [<NotHas $new Cap>]: [<NotHas $old Cap>] {}
+            impl<C: [<NotHas $old Cap>]> [<NotHas $new Cap>] for C {}
+        }
+    };
+}
@@ -0,0 +1,131 @@
+//! Runtime capability sets
+//!
+//! For sets only known at runtime (e.g. built from user input). Members are
+//! tracked by `TypeId`, so nothing here is type-checked.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::{IntoIter, Vec};
+use core::any::TypeId;
+
+use super::capability::Capability;
+use super::inspect::InspectNames;
+
+/// Runtime capability set, keyed by each capability's `TypeId`.
+///
+/// The dynamic counterpart of a `caps![...]` type: membership is decided at
+/// runtime instead of by the trait solver. Same-named capabilities from
+/// different modules are distinct members, and so is an `alias_of` shim and
+/// the capability it aliases. [`Capability::NAME`] is only kept for reporting.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::DynCapSet;
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// struct CanWrite;
+///
+/// let mut granted = DynCapSet::new();
+/// granted.insert::<CanRead>();
+/// assert!(granted.contains::<CanRead>());
+/// assert!(!granted.contains::<CanWrite>());
+/// assert!(DynCapSet::from_set::<caps![CanRead, CanWrite]>().is_superset(&granted));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DynCapSet {
+    members: BTreeMap<TypeId, &'static str>,
+}
+
+impl DynCapSet {
+    /// An empty set.
+    pub const fn new() -> Self {
+        DynCapSet { members: BTreeMap::new() }
+    }
+
+    /// The members of the type-level set `S`.
+    pub fn from_set<S: InspectNames>() -> Self {
+        let mut set = Self::new();
+        S::each_cap(&mut |id, name| {
+            set.members.insert(id, name);
+        });
+        set
+    }
+
+    /// Adds `C`; returns `false` if it was already present.
+    pub fn insert<C: Capability>(&mut self) -> bool {
+        self.members.insert(TypeId::of::<C>(), C::NAME).is_none()
+    }
+
+    /// Removes `C`; returns `false` if it was not present.
+    pub fn remove<C: Capability>(&mut self) -> bool {
+        self.members.remove(&TypeId::of::<C>()).is_some()
+    }
+
+    /// Whether `C` is a member.
+    pub fn contains<C: Capability>(&self) -> bool {
+        self.members.contains_key(&TypeId::of::<C>())
+    }
+
+    /// Whether every member of `other` is also a member of `self`.
+    pub fn is_superset(&self, other: &DynCapSet) -> bool {
+        other.members.keys().all(|id| self.members.contains_key(id))
+    }
+
+    /// Number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Member names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        sorted(self.members.values().copied())
+    }
+}
+
+/// Checks a plugin's declared capabilities against the ones a host requires.
+///
+/// The runtime counterpart of a `Require` bound, for capabilities that cannot
+/// be type-checked at the host boundary. On failure, returns the names of the
+/// `required` members missing from `declared`, in sorted order.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::{validate_plugin_caps, DynCapSet};
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// struct CanWrite;
+///
+/// let required = DynCapSet::from_set::<caps![CanRead, CanWrite]>();
+/// let mut declared = DynCapSet::new();
+/// declared.insert::<CanRead>();
+/// assert_eq!(validate_plugin_caps(&declared, &required), Err(vec!["CanWrite"]));
+/// ```
+pub fn validate_plugin_caps(declared: &DynCapSet, required: &DynCapSet) -> Result<(), Vec<&'static str>> {
+    let missing = sorted(
+        required
+            .members
+            .iter()
+            .filter(|(id, _)| !declared.members.contains_key(id))
+            .map(|(_, name)| *name),
+    )
+    .collect::<Vec<_>>();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(missing)
+    }
+}
+
+fn sorted(names: impl Iterator<Item = &'static str>) -> IntoIter<&'static str> {
+    let mut names: Vec<_> = names.collect();
+    names.sort_unstable();
+    names.into_iter()
+}
@@ -1,13 +1,35 @@
 //! Query types and evaluation logic
 //!
-//! Provides Evaluate trait and boolean query types (Has, And, Or, Not).
+//! Provides Evaluate trait and boolean query types (Has, HasAtLeast, NoneOfFamily,
+//! And, Or, Not, Xor, Implies, Superset, AllAbsent, AnyAbsent, AtLeast, AtMost, ExactlyN).
+//!
+//! # Empty lists and zero thresholds
+//!
+//! The list queries follow the usual vacuous-truth conventions:
+//!
+//! | Query                      | Result                                  |
+//! |----------------------------|-----------------------------------------|
+//! | `All<HNil>`                | `Present`                               |
+//! | `Any<HNil>`                | `Absent`                                |
+//! | `AllAbsent<HNil>`          | `Present`                               |
+//! | `AnyAbsent<HNil>`          | `Absent`                                |
+//! | `AtLeast<Z, List>`         | `Present` for every list, empty or not  |
+//! | `AtLeast<S<N>, HNil>`      | `Absent`                                |
+//! | `AtMost<N, HNil>`          | `Present`                               |
+//! | `AtMost<Z, List>`          | `Present` iff no query in `List` holds  |
+//! | `ExactlyN<Z, HNil>`        | `Present`                               |
+//! | `ExactlyN<S<N>, HNil>`     | `Absent`                                |
+//!
+//! In particular `AtMost<Z, List>` with any query holding is `Absent`, and
+//! `ExactlyN<Z, List>` is the same query as `AtMost<Z, List>`.
 
 use core::marker::PhantomData;
 use crate::primitives::Peano;
-use crate::primitives::{Bool, Present, Absent, GetTail, BoolAnd, BoolOr, BoolNot};
-use crate::primitives::stream::{S, D0};
+use crate::primitives::{Bool, Present, Absent, GetTail, BoolAnd, BoolOr, BoolNot, BoolXor};
+use crate::primitives::stream::{S, Z, D0, PeanoLessEq, PeanoEq, ToPeano};
 use super::node::{Empty, Leaf, Node16};
-use super::capability::Capability;
+use super::node::sealed::TrieNode;
+use super::capability::{Capability, MaxDepthOf};
 
 // =============================================================================
 // Query Types
@@ -25,22 +47,76 @@ pub struct Or<L, R>(PhantomData<(L, R)>);
 /// Negation: NOT Q
 pub struct Not<Q>(PhantomData<Q>);
 
+/// Exclusive disjunction: exactly one of L, R
+pub struct Xor<L, R>(PhantomData<(L, R)>);
+
+/// Implication: if L then R (same truth table as `Or<Not<L>, R>`)
+pub struct Implies<L, R>(PhantomData<(L, R)>);
+
+/// Query: Does the set contain every capability of the set `Set`?
+pub struct Superset<Set>(PhantomData<Set>);
+
+/// Query: Is the set exactly `Universe` without the capabilities of `Set`?
+///
+/// The query form of [`Complement`](super::aliases::Complement), e.g. for
+/// "every capability except the admin ones".
+pub struct ComplementOf<Universe, Set>(PhantomData<(Universe, Set)>);
+
+/// Query: Does the set hold a version of `Cap` at `MIN` or newer?
+///
+/// Looks up the stored capability with `Cap`'s identity and compares its
+/// [`VersionedCapability::VERSION`]; `MIN` must be in `0..=64`.
+pub struct HasAtLeast<Cap, const MIN: u32>(PhantomData<Cap>);
+
+/// Query: Does the set hold no version of `Family`?
+///
+/// Versions of one capability share its identity (`#[capability(alias_of = Base)]`),
+/// so this is the absence of that identity, e.g. for "no storage provider is
+/// configured yet" before a provider is picked. Any member of the family may
+/// stand for it.
+pub struct NoneOfFamily<Family>(PhantomData<Family>);
+
 // =============================================================================
 // HList for All/Any
 // =============================================================================
 
 /// Empty HList
+#[derive(Default)]
 pub struct HNil;
 
 /// HList cons cell
 pub struct HCons<H, T>(PhantomData<(H, T)>);
 
+impl<H, T> Default for HCons<H, T> {
+    fn default() -> Self {
+        HCons(PhantomData)
+    }
+}
+
 /// All queries must be true (conjunction)
 pub struct All<List>(PhantomData<List>);
 
 /// At least one query must be true (disjunction)
 pub struct Any<List>(PhantomData<List>);
 
+/// No query may be true: `All` over `Not<_>` of each member.
+///
+/// Members are queries or bare capabilities, as in [`All`]; e.g. a stage
+/// that must run before any of a group of capabilities is added.
+pub struct AllAbsent<List>(PhantomData<List>);
+
+/// At least one query must be false: `Any` over `Not<_>` of each member
+pub struct AnyAbsent<List>(PhantomData<List>);
+
+/// At least `N` (a Peano number) of the queries must be true
+pub struct AtLeast<N, List>(PhantomData<(N, List)>);
+
+/// At most `N` of the queries may be true
+pub struct AtMost<N, List>(PhantomData<(N, List)>);
+
+/// Exactly `N` of the queries must be true
+pub struct ExactlyN<N, List>(PhantomData<(N, List)>);
+
 // =============================================================================
 // Evaluate (Main Entry Point)
 // =============================================================================
@@ -48,23 +124,81 @@ pub struct Any<List>(PhantomData<List>);
 /// Evaluate a boolean query on a capability set.
 ///
 /// Returns `Present` (true) or `Absent` (false).
+///
+/// Sets produced by type-level functions (e.g. `<T as Pipeline>::FinalCaps`)
+/// can be queried directly. In generic code, state the bound on the
+/// projection itself (`where T::FinalCaps: Evaluate<Q>`), or go through
+/// [`Resolve`] when the projection is nested inside another query.
 #[diagnostic::on_unimplemented(
     message = "Capability logic requirement evaluated to false or is invalid",
     label = "Logic '{Query}' is NOT satisfied by capability set '{Self}'",
     note = "Check if you are missing a required capability or possess a conflicting one."
 )]
-pub trait Evaluate<Query> {
+pub trait Evaluate<Query>: TrieNode {
     type Out: Bool;
     /// The boolean result of the evaluation as a constant.
     const RESULT: bool = <Self::Out as Bool>::VALUE;
 }
 
+// =============================================================================
+// Resolve - force a projected set before evaluation
+// =============================================================================
+
+/// Normalize a capability set produced by a type-level function.
+///
+/// `<X as Resolve>::Out` is `X` itself. Naming the projection makes the
+/// compiler resolve `X` (typically `<T as SomeTrait>::Out`) to a concrete
+/// trie before any `Evaluate` impl is selected, which avoids inference
+/// failures when the set and the query are both generic.
+///
+/// ```ignore
+/// fn check<T: Pipeline>()
+/// where
+///     <T::FinalCaps as Resolve>::Out: Evaluate<Has<CanRead>, Out = Present>,
+/// { }
+/// ```
+pub trait Resolve {
+    type Out;
+}
+
+impl<T> Resolve for T {
+    type Out = T;
+}
+
+// =============================================================================
+// Evaluated - query result as an inherent const
+// =============================================================================
+
+/// The result of `Evaluate<Query>` on `Set` as a plain `const`.
+///
+/// Same value as [`Evaluate::RESULT`], but reachable by path, so it reads
+/// well in `const` items and array lengths.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::{Evaluated, Has};
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+///
+/// type MyCaps = caps![CanRead];
+/// const CAN_READ: bool = Evaluated::<MyCaps, Has<CanRead>>::VALUE;
+/// let buf = [0u8; if CAN_READ { 1 } else { 0 }];
+/// assert_eq!(buf.len(), 1);
+/// ```
+pub struct Evaluated<Set, Query>(PhantomData<(Set, Query)>);
+
+impl<Set: Evaluate<Query>, Query> Evaluated<Set, Query> {
+    /// Whether `Set` satisfies `Query`.
+    pub const VALUE: bool = <<Set as Evaluate<Query>>::Out as Bool>::VALUE;
+}
+
 // =============================================================================
 // EvalAt - Internal depth-aware evaluation
 // =============================================================================
 
 /// Internal trait for evaluating queries at a specific depth
-pub trait EvalAt<Query, Depth> {
+pub trait EvalAt<Query, Depth>: TrieNode {
     type Out: Bool;
 }
 
@@ -72,18 +206,39 @@ impl<Cap, Depth> EvalAt<Has<Cap>, Depth> for Empty {
     type Out = Absent;
 }
 
-// Leaf: Two-tier matching (Identity perfect + Stream fallback)
-// - Same Identity64 type → Stream also matches → Present (perfect!)
-// - Different Identity64 → Stream comparison → Present/Absent (~10^-15 collision)
-// Identity64 provides type documentation, Stream provides actual comparison
-use crate::primitives::stream::{StreamEq, DefaultMaxDepth};
+// Leaf: Two-tier matching (Stream routes, Identity verifies)
+// - Different Stream → Absent
+// - Same Stream → compare Identity, so a true hash collision is not mistaken
+//   for the stored capability (the same check a Bucket does per member)
+// Streams are compared up to the query's CapabilityConfig::MaxDepth.
+use crate::primitives::stream::StreamEq;
 impl<QCap, StoredCap, Depth> EvalAt<Has<QCap>, Depth> for Leaf<StoredCap>
 where
     QCap: Capability,
     StoredCap: Capability,
-    QCap::Stream: StreamEq<StoredCap::Stream, DefaultMaxDepth>,
+    QCap::Stream: StreamEq<StoredCap::Stream, MaxDepthOf<QCap>>,
+    Self: LeafEval<QCap, <QCap::Stream as StreamEq<StoredCap::Stream, MaxDepthOf<QCap>>>::Out>,
 {
-    type Out = <QCap::Stream as StreamEq<StoredCap::Stream, DefaultMaxDepth>>::Out;
+    type Out = <Self as LeafEval<
+        QCap, <QCap::Stream as StreamEq<StoredCap::Stream, MaxDepthOf<QCap>>>::Out
+    >>::Out;
+}
+
+/// Leaf lookup by stream match; identities are compared only on `Present`.
+pub trait LeafEval<QCap, SameStream> {
+    type Out: Bool;
+}
+
+impl<QCap, StoredCap> LeafEval<QCap, Present> for Leaf<StoredCap>
+where
+    StoredCap: Capability,
+    QCap: CapabilityIdentityEq<StoredCap>,
+{
+    type Out = <QCap as CapabilityIdentityEq<StoredCap>>::Out;
+}
+
+impl<QCap, StoredCap> LeafEval<QCap, Absent> for Leaf<StoredCap> {
+    type Out = Absent;
 }
 
 #[macros::node16]
@@ -120,28 +275,136 @@ where
 }
 
 // =============================================================================
-// Bucket Evaluation - Identity match + Stream fallback
+// Bucket Evaluation - Stream match, then Identity search
 // =============================================================================
 //
 // Strategy:
-// 1. Check if QCap matches Head via Identity64 (perfect match)
-// 2. If not, continue searching Tail
+// 1. Every member shares one stream, so compare QCap against the Head once
+// 2. On a stream match, search the members by Identity
 // 3. Stream routes, Identity verifies
 
 use super::node::Bucket;
+use super::capability::{CapabilityIdentityEq, VersionedCapability};
 
-/// Bucket: Linear search with Identity+Stream hybrid matching
-/// Checks head via Stream, returns Present if match OR continues to tail
+/// Bucket: `Present` if QCap shares the bucket's stream and some member has
+/// QCap's identity.
 impl<QCap, Head, Tail, Depth> EvalAt<Has<QCap>, Depth> for Bucket<Head, Tail>
 where
     QCap: Capability,
     Head: Capability,
-    Tail: EvalAt<Has<QCap>, Depth>,
-    QCap::Stream: StreamEq<Head::Stream, DefaultMaxDepth>,
-    <QCap::Stream as StreamEq<Head::Stream, DefaultMaxDepth>>::Out: BoolOr<<Tail as EvalAt<Has<QCap>, Depth>>::Out>,
+    QCap::Stream: StreamEq<Head::Stream, MaxDepthOf<QCap>>,
+    Self: BucketEval<QCap, <QCap::Stream as StreamEq<Head::Stream, MaxDepthOf<QCap>>>::Out>,
+{
+    type Out = <Self as BucketEval<
+        QCap, <QCap::Stream as StreamEq<Head::Stream, MaxDepthOf<QCap>>>::Out
+    >>::Out;
+}
+
+/// Bucket lookup by stream match; identities are compared only on `Present`.
+pub trait BucketEval<QCap, SameStream> {
+    type Out: Bool;
+}
+
+impl<QCap, Head, Tail> BucketEval<QCap, Present> for Bucket<Head, Tail>
+where
+    Self: BucketContains<QCap>,
+{
+    type Out = <Self as BucketContains<QCap>>::Out;
+}
+
+impl<QCap, Head, Tail> BucketEval<QCap, Absent> for Bucket<Head, Tail> {
+    type Out = Absent;
+}
+
+/// Linear identity search over a bucket list.
+pub trait BucketContains<QCap> {
+    type Out: Bool;
+}
+
+impl<QCap> BucketContains<QCap> for Empty {
+    type Out = Absent;
+}
+
+impl<QCap, Head, Tail> BucketContains<QCap> for Bucket<Head, Tail>
+where
+    Head: Capability,
+    QCap: CapabilityIdentityEq<Head>,
+    Tail: BucketContains<QCap>,
+{
+    type Out = <<QCap as CapabilityIdentityEq<Head>>::Out as Bool>::Or<<Tail as BucketContains<QCap>>::Out>;
+}
+
+// =============================================================================
+// HasAtLeast Evaluation - route by stream, match by identity, then compare versions
+// =============================================================================
+
+/// Route a query to the Node16 slot selected by `Nib`, one level down.
+pub trait RouteBy<Query, Depth, Nib: Nibble> {
+    type Out: Bool;
+}
+
+#[macros::node16(for_nibble)]
+impl<Query, Depth, _Slots_> RouteBy<Query, Depth, _Nibble_> for _Node16_
+where
+    Depth: Peano,
+    _SlotN_: EvalAt<Query, S<Depth>>,
+{
+    type Out = <_SlotN_ as EvalAt<Query, S<Depth>>>::Out;
+}
+
+impl<Cap, Depth, const MIN: u32> EvalAt<HasAtLeast<Cap, MIN>, Depth> for Empty {
+    type Out = Absent;
+}
+
+impl<Cap, Stored, Depth, const MIN: u32> EvalAt<HasAtLeast<Cap, MIN>, Depth> for Leaf<Stored>
+where
+    Cap: CapabilityIdentityEq<Stored>,
+    Stored: Capability,
+    <Cap as CapabilityIdentityEq<Stored>>::Out: VersionGate<Stored, MIN>,
+{
+    type Out = <<Cap as CapabilityIdentityEq<Stored>>::Out as VersionGate<Stored, MIN>>::Out;
+}
+
+impl<Cap, Head, Tail, Depth, const MIN: u32> EvalAt<HasAtLeast<Cap, MIN>, Depth> for Bucket<Head, Tail>
+where
+    Cap: CapabilityIdentityEq<Head>,
+    Head: Capability,
+    <Cap as CapabilityIdentityEq<Head>>::Out: VersionGate<Head, MIN>,
+    Tail: EvalAt<HasAtLeast<Cap, MIN>, Depth>,
+{
+    type Out = <<<Cap as CapabilityIdentityEq<Head>>::Out as VersionGate<Head, MIN>>::Out as Bool>::Or<
+        <Tail as EvalAt<HasAtLeast<Cap, MIN>, Depth>>::Out,
+    >;
+}
+
+#[macros::node16]
+impl<Cap, Depth, _Slots_, const MIN: u32> EvalAt<HasAtLeast<Cap, MIN>, Depth> for _Node16_
+where
+    Cap: Capability,
+    Depth: Peano,
+    Cap::Stream: GetTail<Depth>,
+    Self: RouteBy<HasAtLeast<Cap, MIN>, Depth, Cap::At<Depth>>,
 {
-    type Out = <<QCap::Stream as StreamEq<Head::Stream, DefaultMaxDepth>>::Out as BoolOr<<Tail as EvalAt<Has<QCap>, Depth>>::Out>>::Out;
-    // Stream matches head OR tail contains QCap
+    type Out = <Self as RouteBy<HasAtLeast<Cap, MIN>, Depth, Cap::At<Depth>>>::Out;
+}
+
+/// Version check behind an identity match: `Absent` stops without needing
+/// `Stored` to be versioned, `Present` compares `MIN <= Stored::VERSION`.
+pub trait VersionGate<Stored, const MIN: u32> {
+    type Out: Bool;
+}
+
+impl<Stored, const MIN: u32> VersionGate<Stored, MIN> for Absent {
+    type Out = Absent;
+}
+
+impl<Stored, const MIN: u32> VersionGate<Stored, MIN> for Present
+where
+    Stored: VersionedCapability,
+    (): ToPeano<MIN>,
+    <() as ToPeano<MIN>>::Out: PeanoLessEq<Stored::Version>,
+{
+    type Out = <<() as ToPeano<MIN>>::Out as PeanoLessEq<Stored::Version>>::Out;
 }
 
 /// Leaf: Exact Identity64 match (higher priority via &)
@@ -166,6 +429,33 @@ where
     type Out = <Ctx as EvalAt<Has<Cap>, D0>>::Out;
 }
 
+// Has<Cap> - explicit form of the bare capability query
+impl<Ctx, Cap> Evaluate<Has<Cap>> for Ctx
+where
+    Cap: Capability,
+    Ctx: EvalAt<Has<Cap>, D0>,
+{
+    type Out = <Ctx as EvalAt<Has<Cap>, D0>>::Out;
+}
+
+// HasAtLeast<Cap, MIN> - versioned lookup
+impl<Ctx, Cap, const MIN: u32> Evaluate<HasAtLeast<Cap, MIN>> for Ctx
+where
+    Ctx: EvalAt<HasAtLeast<Cap, MIN>, D0>,
+{
+    type Out = <Ctx as EvalAt<HasAtLeast<Cap, MIN>, D0>>::Out;
+}
+
+// NoneOfFamily<Family> - `Has` matches by identity, which the versions share
+impl<Ctx, Family> Evaluate<NoneOfFamily<Family>> for Ctx
+where
+    Family: Capability,
+    Ctx: EvalAt<Has<Family>, D0>,
+    <Ctx as EvalAt<Has<Family>, D0>>::Out: BoolNot,
+{
+    type Out = <<Ctx as EvalAt<Has<Family>, D0>>::Out as BoolNot>::Out;
+}
+
 // And<L, R>
 impl<Ctx, L, R> Evaluate<And<L, R>> for Ctx
 where
@@ -193,8 +483,27 @@ where
     type Out = <<Ctx as Evaluate<Q>>::Out as BoolNot>::Out;
 }
 
+// Xor<L, R>
+impl<Ctx, L, R> Evaluate<Xor<L, R>> for Ctx
+where
+    Ctx: Evaluate<L> + Evaluate<R>,
+    <Ctx as Evaluate<L>>::Out: BoolXor<<Ctx as Evaluate<R>>::Out>,
+{
+    type Out = <<Ctx as Evaluate<L>>::Out as BoolXor<<Ctx as Evaluate<R>>::Out>>::Out;
+}
+
+// Implies<L, R> = !L | R
+impl<Ctx, L, R> Evaluate<Implies<L, R>> for Ctx
+where
+    Ctx: Evaluate<L> + Evaluate<R>,
+    <Ctx as Evaluate<L>>::Out: BoolNot,
+    <<Ctx as Evaluate<L>>::Out as BoolNot>::Out: BoolOr<<Ctx as Evaluate<R>>::Out>,
+{
+    type Out = <<<Ctx as Evaluate<L>>::Out as BoolNot>::Out as BoolOr<<Ctx as Evaluate<R>>::Out>>::Out;
+}
+
 // All<HNil>
-impl<Ctx> Evaluate<All<HNil>> for Ctx {
+impl<Ctx: TrieNode> Evaluate<All<HNil>> for Ctx {
     type Out = Present;
 }
 
@@ -208,7 +517,7 @@ where
 }
 
 // Any<HNil>
-impl<Ctx> Evaluate<Any<HNil>> for Ctx {
+impl<Ctx: TrieNode> Evaluate<Any<HNil>> for Ctx {
     type Out = Absent;
 }
 
@@ -221,6 +530,103 @@ where
     type Out = <<Ctx as Evaluate<H>>::Out as BoolOr<<Ctx as Evaluate<Any<T>>>::Out>>::Out;
 }
 
+// AllAbsent<HNil>
+impl<Ctx: TrieNode> Evaluate<AllAbsent<HNil>> for Ctx {
+    type Out = Present;
+}
+
+// AllAbsent<HCons<H, T>>
+impl<Ctx, H, T> Evaluate<AllAbsent<HCons<H, T>>> for Ctx
+where
+    Ctx: Evaluate<H> + Evaluate<AllAbsent<T>>,
+    <Ctx as Evaluate<H>>::Out: BoolNot,
+    <<Ctx as Evaluate<H>>::Out as BoolNot>::Out: BoolAnd<<Ctx as Evaluate<AllAbsent<T>>>::Out>,
+{
+    type Out = <<<Ctx as Evaluate<H>>::Out as BoolNot>::Out as BoolAnd<<Ctx as Evaluate<AllAbsent<T>>>::Out>>::Out;
+}
+
+// AnyAbsent<HNil>
+impl<Ctx: TrieNode> Evaluate<AnyAbsent<HNil>> for Ctx {
+    type Out = Absent;
+}
+
+// AnyAbsent<HCons<H, T>>
+impl<Ctx, H, T> Evaluate<AnyAbsent<HCons<H, T>>> for Ctx
+where
+    Ctx: Evaluate<H> + Evaluate<AnyAbsent<T>>,
+    <Ctx as Evaluate<H>>::Out: BoolNot,
+    <<Ctx as Evaluate<H>>::Out as BoolNot>::Out: BoolOr<<Ctx as Evaluate<AnyAbsent<T>>>::Out>,
+{
+    type Out = <<<Ctx as Evaluate<H>>::Out as BoolNot>::Out as BoolOr<<Ctx as Evaluate<AnyAbsent<T>>>::Out>>::Out;
+}
+
+// =============================================================================
+// Counting queries
+// =============================================================================
+
+/// Number of queries in an HList that evaluate to `Present` on `Ctx`.
+pub trait CountPresent<Ctx> {
+    type Out: Peano;
+}
+
+impl<Ctx> CountPresent<Ctx> for HNil {
+    type Out = Z;
+}
+
+impl<Ctx, H, T> CountPresent<Ctx> for HCons<H, T>
+where
+    Ctx: Evaluate<H>,
+    T: CountPresent<Ctx>,
+    <Ctx as Evaluate<H>>::Out: IncIf<<T as CountPresent<Ctx>>::Out>,
+{
+    type Out = <<Ctx as Evaluate<H>>::Out as IncIf<<T as CountPresent<Ctx>>::Out>>::Out;
+}
+
+/// `N + 1` if Self is `Present`, `N` otherwise.
+pub trait IncIf<N: Peano>: Bool {
+    type Out: Peano;
+}
+
+impl<N: Peano> IncIf<N> for Present {
+    type Out = S<N>;
+}
+
+impl<N: Peano> IncIf<N> for Absent {
+    type Out = N;
+}
+
+// AtLeast<N, List>: N <= count
+impl<Ctx, N, List> Evaluate<AtLeast<N, List>> for Ctx
+where
+    Ctx: TrieNode,
+    N: PeanoLessEq<<List as CountPresent<Ctx>>::Out>,
+    List: CountPresent<Ctx>,
+{
+    type Out = <N as PeanoLessEq<<List as CountPresent<Ctx>>::Out>>::Out;
+}
+
+// AtMost<N, List>: count <= N
+impl<Ctx, N, List> Evaluate<AtMost<N, List>> for Ctx
+where
+    Ctx: TrieNode,
+    N: Peano,
+    List: CountPresent<Ctx>,
+    <List as CountPresent<Ctx>>::Out: PeanoLessEq<N>,
+{
+    type Out = <<List as CountPresent<Ctx>>::Out as PeanoLessEq<N>>::Out;
+}
+
+// ExactlyN<N, List>: count == N
+impl<Ctx, N, List> Evaluate<ExactlyN<N, List>> for Ctx
+where
+    Ctx: TrieNode,
+    N: Peano,
+    List: CountPresent<Ctx>,
+    <List as CountPresent<Ctx>>::Out: PeanoEq<N>,
+{
+    type Out = <<List as CountPresent<Ctx>>::Out as PeanoEq<N>>::Out;
+}
+
 // =============================================================================
 // IsTrue / Require helpers
 // =============================================================================
@@ -234,17 +640,143 @@ pub trait IsTrue<Set, Query: ?Sized> {}
 
 impl<S, Q: ?Sized> IsTrue<S, Q> for Present {}
 
+/// `IsTrue` that also names the part of the query that failed.
+///
+/// `Failing` is the query itself for [`Require`], and the first conjunct
+/// that evaluated to `Absent` for [`RequireEach`].
+#[diagnostic::on_unimplemented(
+    message = "Capability requirement failed: {Failing}",
+    label = "This capability set does not satisfy '{Failing}'",
+    note = "Set: {Set}\nRequirement: {Query}\nCheck if you are missing a required capability or possess a conflicting one."
+)]
+pub trait Satisfied<Set, Query: ?Sized, Failing: ?Sized> {}
+
+impl<S, Q: ?Sized, F: ?Sized> Satisfied<S, Q, F> for Present {}
+
 /// Wrapper trait to enforce a requirement.
-/// Implemented only when `Evaluate<Q>::Out` is `Present`.
+/// Implemented only when `Evaluate<Q>::Out` is `Present`, for any query `Q`.
 pub trait Require<Q> {}
 
 impl<C, Q> Require<Q> for C
 where
     C: Evaluate<Q>,
-    <C as Evaluate<Q>>::Out: IsTrue<C, Q>,
+    <C as Evaluate<Q>>::Out: Satisfied<C, Q, Q>,
 {
 }
 
+/// `Require<All<List>>` whose diagnostic names the first conjunct of `List`
+/// that evaluated to `Absent`, instead of the whole `All`.
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// use tola_caps::{hlist, Has, RequireEach};
+///
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// // error: Capability requirement failed: Has<B>
+/// fn needs_ab<S: RequireEach<hlist![Has<A>, Has<B>]>>() {}
+/// needs_ab::<caps![A]>();
+/// ```
+pub trait RequireEach<List> {}
+
+impl<C, List> RequireEach<List> for C
+where
+    C: Evaluate<All<List>>,
+    List: FirstAbsent<C>,
+    <C as Evaluate<All<List>>>::Out: Satisfied<C, All<List>, <List as FirstAbsent<C>>::Out>,
+{
+}
+
+/// First query in an HList that evaluates to `Absent` against `Ctx`
+/// (`All<HNil>` if there is none).
+pub trait FirstAbsent<Ctx> {
+    type Out;
+}
+
+impl<Ctx> FirstAbsent<Ctx> for HNil {
+    type Out = All<HNil>;
+}
+
+impl<Ctx, H, T> FirstAbsent<Ctx> for HCons<H, T>
+where
+    Ctx: Evaluate<H>,
+    Self: FirstAbsentStep<Ctx, <Ctx as Evaluate<H>>::Out>,
+{
+    type Out = <Self as FirstAbsentStep<Ctx, <Ctx as Evaluate<H>>::Out>>::Out;
+}
+
+/// `FirstAbsent` by the head's result: `Present` moves on, `Absent` stops.
+pub trait FirstAbsentStep<Ctx, HeadOut> {
+    type Out;
+}
+
+impl<Ctx, H, T> FirstAbsentStep<Ctx, Present> for HCons<H, T>
+where
+    T: FirstAbsent<Ctx>,
+{
+    type Out = <T as FirstAbsent<Ctx>>::Out;
+}
+
+impl<Ctx, H, T> FirstAbsentStep<Ctx, Absent> for HCons<H, T> {
+    type Out = H;
+}
+
+/// Cons list of capabilities, as `(A, (B, ()))`, mapped to an HList of
+/// `Has<_>` queries. `()` entries are padding and are skipped.
+pub trait HasList {
+    type Out;
+}
+
+impl HasList for () {
+    type Out = HNil;
+}
+
+impl<Rest: HasList> HasList for ((), Rest) {
+    type Out = Rest::Out;
+}
+
+impl<Cap: Capability, Rest: HasList> HasList for (Cap, Rest) {
+    type Out = HCons<Has<Cap>, Rest::Out>;
+}
+
+macro_rules! define_require_list {
+    ($(#[$meta:meta])* $name:ident => $($bound:tt)+) => {
+        $(#[$meta])*
+        pub trait $name<A, B = (), C = (), D = (), E = (), F = (), G = (), H = ()> {}
+
+        impl<Set, List, A, B, C, D, E, F, G, H> $name<A, B, C, D, E, F, G, H> for Set
+        where
+            (A, (B, (C, (D, (E, (F, (G, (H, ())))))))): HasList<Out = List>,
+            Set: $($bound)+,
+        {
+        }
+    };
+}
+
+define_require_list! {
+    /// Bound alias for `RequireEach<hlist![Has<A>, Has<B>, ...]>`: the set
+    /// must contain every listed capability (up to 8), and an error names
+    /// the first one missing.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    ///
+    /// #[derive(Capability)] struct CanRead;
+    /// #[derive(Capability)] struct CanWrite;
+    ///
+    /// fn copy_file<C: RequireAll<CanRead, CanWrite>>() {}
+    /// copy_file::<caps![CanRead, CanWrite]>();
+    /// ```
+    RequireAll => RequireEach<List>
+}
+
+define_require_list! {
+    /// Bound alias for `Require<Any<hlist![Has<A>, Has<B>, ...]>>`: the set
+    /// must contain at least one listed capability (up to 8).
+    RequireAny => Require<Any<List>>
+}
+
 // =============================================================================
 // Macros
 // =============================================================================
@@ -254,7 +786,7 @@ where
 macro_rules! hlist {
     () => { $crate::trie::HNil };
     ($head:ty $(, $tail:ty)*) => {
-        $crate::trie::HCons<$head, hlist![$($tail),*]>
+        $crate::trie::HCons<$head, $crate::hlist![$($tail),*]>
     };
 }
 
@@ -262,7 +794,7 @@ macro_rules! hlist {
 #[macro_export]
 macro_rules! all {
     ($($item:ty),* $(,)?) => {
-        $crate::trie::All<hlist![$($item),*]>
+        $crate::trie::All<$crate::hlist![$($item),*]>
     };
 }
 
@@ -270,6 +802,57 @@ macro_rules! all {
 #[macro_export]
 macro_rules! any {
     ($($item:ty),* $(,)?) => {
-        $crate::trie::Any<hlist![$($item),*]>
+        $crate::trie::Any<$crate::hlist![$($item),*]>
+    };
+}
+
+/// Macro for AllAbsent query
+/// Usage: `all_absent![CanRead, CanWrite]`
+#[macro_export]
+macro_rules! all_absent {
+    ($($item:ty),* $(,)?) => {
+        $crate::trie::AllAbsent<$crate::hlist![$($item),*]>
+    };
+}
+
+/// Macro for AnyAbsent query
+/// Usage: `any_absent![CanRead, CanWrite]`
+#[macro_export]
+macro_rules! any_absent {
+    ($($item:ty),* $(,)?) => {
+        $crate::trie::AnyAbsent<$crate::hlist![$($item),*]>
+    };
+}
+
+/// Macro for AtMost query
+/// Usage: `at_most![1; Has<A>, Has<B>, Has<C>]`
+#[macro_export]
+macro_rules! at_most {
+    ($n:literal; $($item:ty),* $(,)?) => {
+        $crate::paste::paste! {
+            $crate::trie::AtMost<$crate::primitives::stream::[<D $n>], $crate::hlist![$($item),*]>
+        }
+    };
+}
+
+/// Macro for ExactlyN query
+/// Usage: `exactly![1; Has<A>, Has<B>, Has<C>]`
+#[macro_export]
+macro_rules! exactly {
+    ($n:literal; $($item:ty),* $(,)?) => {
+        $crate::paste::paste! {
+            $crate::trie::ExactlyN<$crate::primitives::stream::[<D $n>], $crate::hlist![$($item),*]>
+        }
+    };
+}
+
+/// Macro for AtLeast query
+/// Usage: `at_least![2; Has<A>, Has<B>, Has<C>]`
+#[macro_export]
+macro_rules! at_least {
+    ($n:literal; $($item:ty),* $(,)?) => {
+        $crate::paste::paste! {
+            $crate::trie::AtLeast<$crate::primitives::stream::[<D $n>], $crate::hlist![$($item),*]>
+        }
     };
 }
@@ -3,21 +3,24 @@
 //! Provides traits for inserting capabilities into and removing them from the trie.
 
 use crate::primitives::Peano;
-use crate::primitives::{GetTail, Nibble, Present, Absent};
-use crate::primitives::stream::{S, D0, StreamEq, DefaultMaxDepth};
+use crate::primitives::{Bool, GetTail, Nibble, Present, Absent};
+use crate::primitives::stream::{S, D0, D64, PeanoLess, StreamEq};
 use crate::primitives::nibble::{NibbleEq, *};
 
 
 use super::node::{Empty, Leaf, Node16, EmptyNode16, Bucket};
-use super::capability::Capability;
-use super::evaluate::{EvalAt, Has};
+use super::node::sealed::TrieNode;
+use super::capability::{Capability, CapabilityIdentityEq, MaxDepthOf};
+use super::evaluate::{EvalAt, Has, HCons, HNil};
+use super::ops::Count;
+use crate::hlist;
 
 // =============================================================================
 // InsertAt - Main insertion trait
 // =============================================================================
 
 /// Insert a capability into a trie node at a given depth
-pub trait InsertAt<Cap, Depth> {
+pub trait InsertAt<Cap, Depth>: TrieNode {
     type Out;
 }
 
@@ -77,6 +80,25 @@ pub trait LeafInsert<NewCap, StoredCap, Depth, NewNib: Nibble, StoredNib: Nibble
     type Out;
 }
 
+/// Dispatches on whether the two nibbles match, so each case has exactly one
+/// candidate impl (and errors point at the real cause, e.g. a hash collision).
+impl<NewCap, StoredCap, Depth, NewNib, StoredNib>
+    LeafInsert<NewCap, StoredCap, Depth, NewNib, StoredNib> for Leaf<StoredCap>
+where
+    NewNib: Nibble + NibbleEq<StoredNib>,
+    StoredNib: Nibble,
+    Self: LeafInsertBranch<NewCap, StoredCap, Depth, NewNib, StoredNib, <NewNib as NibbleEq<StoredNib>>::Out>,
+{
+    type Out = <Self as LeafInsertBranch<
+        NewCap, StoredCap, Depth, NewNib, StoredNib, <NewNib as NibbleEq<StoredNib>>::Out
+    >>::Out;
+}
+
+/// `LeafInsert` by nibble match: `SameNib` is `Present` (same slot) or `Absent`.
+pub trait LeafInsertBranch<NewCap, StoredCap, Depth, NewNib: Nibble, StoredNib: Nibble, SameNib> {
+    type Out;
+}
+
 // =============================================================================
 // MakeNode16WithLeaf - Helper traits
 // =============================================================================
@@ -97,6 +119,16 @@ impl<Cap> MakeNode16WithLeaf<Cap, _Nibble_> for () {
     type Out = Node16<_EmptyBefore_, Leaf<Cap>, _EmptyAfter_>;
 }
 
+/// Helper trait to create a Node16 with an arbitrary child at position Xi
+pub trait MakeNode16WithChild<Child, Nib: Nibble> {
+    type Out;
+}
+
+#[macros::node16(for_nibble_split)]
+impl<Child> MakeNode16WithChild<Child, _Nibble_> for () {
+    type Out = Node16<_EmptyBefore_, Child, _EmptyAfter_>;
+}
+
 // =============================================================================
 // LeafInsert - Same nibble collision (16 impls)
 // =============================================================================
@@ -108,22 +140,21 @@ pub trait LeafCollisionBranch<NewCap, StoredCap, IsCollision, Depth, Nib: Nibble
     type Out;
 }
 
-// Case 1a: Stream collision AND same type (NewCap == StoredCap) - Deduplicate
-impl<Cap, Depth, Nib> LeafCollisionBranch<Cap, Cap, Present, Depth, Nib> for Leaf<Cap>
+// Case 1: Stream collision - compare full identities.
+// Same identity (the same capability, or an `alias_of`) deduplicates.
+// Different identity is a true hash collision: both are kept in a `Bucket`.
+impl<NewCap, StoredCap, Depth, Nib> LeafCollisionBranch<NewCap, StoredCap, Present, Depth, Nib> for Leaf<StoredCap>
 where
-    Cap: Capability,
+    NewCap: CapabilityIdentityEq<StoredCap>,
+    StoredCap: Capability,
     Nib: Nibble,
 {
-    type Out = Leaf<Cap>; // Already present, deduplicate
+    type Out = <<NewCap as CapabilityIdentityEq<StoredCap>>::Out as Bool>::If<
+        Leaf<StoredCap>,
+        Bucket<StoredCap, Bucket<NewCap, Empty>>,
+    >;
 }
 
-// Note: Case 1b (Stream collision but different types) would create a Bucket.
-// However, in our current design, we rely on the hash being unique enough
-// that true collisions (different types with same hash) are effectively impossible
-// with 64-bit FNV-1a hash + full module path.
-// If a collision does occur, compilation will fail with "conflicting implementations"
-// which is SAFE (fail-closed) rather than silently wrong behavior.
-
 // Case 2: Diverge -> Deepen
 #[macros::node16(for_nibble_split)]
 impl<NewCap, StoredCap, Depth> LeafCollisionBranch<NewCap, StoredCap, Absent, Depth, _Nibble_> for Leaf<StoredCap>
@@ -142,15 +173,15 @@ where
 
 // Same nibble collision: Check for Full Stream Collision first
 #[macros::node16(for_nibble_split)]
-impl<NewCap, StoredCap, Depth> LeafInsert<NewCap, StoredCap, Depth, _Nibble_, _Nibble_> for Leaf<StoredCap>
+impl<NewCap, StoredCap, Depth> LeafInsertBranch<NewCap, StoredCap, Depth, _Nibble_, _Nibble_, Present> for Leaf<StoredCap>
 where
     NewCap: Capability,
     StoredCap: Capability,
-    NewCap::Stream: StreamEq<StoredCap::Stream, DefaultMaxDepth>, // Check for collision
+    NewCap::Stream: StreamEq<StoredCap::Stream, MaxDepthOf<NewCap>>, // Check for collision
     Self: LeafCollisionBranch<
         NewCap,
         StoredCap,
-        <NewCap::Stream as StreamEq<StoredCap::Stream, DefaultMaxDepth>>::Out,
+        <NewCap::Stream as StreamEq<StoredCap::Stream, MaxDepthOf<NewCap>>>::Out,
         Depth,
         _Nibble_
     >,
@@ -158,7 +189,7 @@ where
     type Out = <Self as LeafCollisionBranch<
         NewCap,
         StoredCap,
-        <NewCap::Stream as StreamEq<StoredCap::Stream, DefaultMaxDepth>>::Out,
+        <NewCap::Stream as StreamEq<StoredCap::Stream, MaxDepthOf<NewCap>>>::Out,
         Depth,
         _Nibble_
     >>::Out;
@@ -169,13 +200,12 @@ where
 // =============================================================================
 
 /// Different nibbles: insert both leaves into EmptyNode16 at current depth.
-/// Uses NibbleEq<Out = Absent> constraint to ensure NewNib != StoredNib.
 impl<NewCap, StoredCap, Depth, NewNib, StoredNib>
-    LeafInsert<NewCap, StoredCap, Depth, NewNib, StoredNib> for Leaf<StoredCap>
+    LeafInsertBranch<NewCap, StoredCap, Depth, NewNib, StoredNib, Absent> for Leaf<StoredCap>
 where
     NewCap: Capability,
     StoredCap: Capability,
-    NewNib: Nibble + NibbleEq<StoredNib, Out = Absent>,
+    NewNib: Nibble,
     StoredNib: Nibble,
     EmptyNode16: NodeInsert<StoredCap, Depth, StoredNib>,
     <EmptyNode16 as NodeInsert<StoredCap, Depth, StoredNib>>::Out:
@@ -190,7 +220,7 @@ where
 // =============================================================================
 
 /// Remove a capability from a trie node at a given depth
-pub trait RemoveAt<Cap, Depth> {
+pub trait RemoveAt<Cap, Depth>: TrieNode {
     type Out;
 }
 
@@ -240,8 +270,9 @@ impl<Cap, Depth, _Slots_> NodeRemove<Cap, Depth, _Nibble_> for _Node16_
 where
     Cap: Capability,
     _SlotN_: RemoveAt<Cap, S<Depth>>,
+    Node16<_Before_, <_SlotN_ as RemoveAt<Cap, S<Depth>>>::Out, _After_>: Normalize,
 {
-    type Out = Node16<_Before_, <_SlotN_ as RemoveAt<Cap, S<Depth>>>::Out, _After_>;
+    type Out = <Node16<_Before_, <_SlotN_ as RemoveAt<Cap, S<Depth>>>::Out, _After_> as Normalize>::Out;
 }
 
 #[macros::node16]
@@ -255,24 +286,250 @@ where
     type Out = <Self as NodeRemove<Cap, Depth, Cap::At<Depth>>>::Out;
 }
 
+// =============================================================================
+// Normalize - Canonical shape after removal
+// =============================================================================
+
+/// Restore the canonical trie shape produced by insertion.
+///
+/// A `Node16` only exists above two or more capabilities, so after a removal:
+/// - a Node16 with all slots `Empty` becomes `Empty`
+/// - a Node16 whose only non-empty slot is a `Leaf` becomes that `Leaf`
+///
+/// Applied bottom-up by `RemoveAt`, so `<caps![A, B] as Without<B>>::Out`
+/// is the same type as `caps![A]`.
+pub trait Normalize {
+    type Out;
+}
+
+impl Normalize for Empty {
+    type Out = Empty;
+}
+
+impl<C> Normalize for Leaf<C> {
+    type Out = Leaf<C>;
+}
+
+impl<H> Normalize for Bucket<H, Empty> {
+    type Out = Leaf<H>;
+}
+
+impl<H, H2, T2> Normalize for Bucket<H, Bucket<H2, T2>> {
+    type Out = Self;
+}
+
+#[macros::node16]
+impl<_Slots_> Normalize for _Node16_
+where
+    hlist![_Slots_]: NormalizeSlots<SlotsEmpty>,
+    <hlist![_Slots_] as NormalizeSlots<SlotsEmpty>>::Out: NormalizeResult<Self>,
+{
+    type Out = <<hlist![_Slots_] as NormalizeSlots<SlotsEmpty>>::Out as NormalizeResult<Self>>::Out;
+}
+
+/// Slot scan state: no non-empty slot seen yet
+pub struct SlotsEmpty;
+/// Slot scan state: exactly one non-empty slot, holding a Leaf (or Bucket)
+pub struct SlotsOneLeaf<L>(core::marker::PhantomData<L>);
+/// Slot scan state: a Node16 or more than one non-empty slot
+pub struct SlotsMany;
+
+/// Advance the slot scan state by one slot.
+pub trait NormalizeStep<Slot> {
+    type Out;
+}
+
+impl NormalizeStep<Empty> for SlotsEmpty {
+    type Out = SlotsEmpty;
+}
+
+impl<C> NormalizeStep<Leaf<C>> for SlotsEmpty {
+    type Out = SlotsOneLeaf<Leaf<C>>;
+}
+
+// A Bucket sits where a Leaf would, so a lone Bucket is hoisted like one.
+impl<H, T> NormalizeStep<Bucket<H, T>> for SlotsEmpty {
+    type Out = SlotsOneLeaf<Bucket<H, T>>;
+}
+
+#[macros::node16]
+impl<_Slots_> NormalizeStep<_Node16_> for SlotsEmpty {
+    type Out = SlotsMany;
+}
+
+impl<L> NormalizeStep<Empty> for SlotsOneLeaf<L> {
+    type Out = SlotsOneLeaf<L>;
+}
+
+impl<L, C> NormalizeStep<Leaf<C>> for SlotsOneLeaf<L> {
+    type Out = SlotsMany;
+}
+
+impl<L, H, T> NormalizeStep<Bucket<H, T>> for SlotsOneLeaf<L> {
+    type Out = SlotsMany;
+}
+
+#[macros::node16]
+impl<L, _Slots_> NormalizeStep<_Node16_> for SlotsOneLeaf<L> {
+    type Out = SlotsMany;
+}
+
+impl<Slot> NormalizeStep<Slot> for SlotsMany {
+    type Out = SlotsMany;
+}
+
+/// Fold `NormalizeStep` over an HList of slots.
+pub trait NormalizeSlots<State> {
+    type Out;
+}
+
+impl<State> NormalizeSlots<State> for HNil {
+    type Out = State;
+}
+
+impl<State, H, T> NormalizeSlots<State> for HCons<H, T>
+where
+    State: NormalizeStep<H>,
+    T: NormalizeSlots<<State as NormalizeStep<H>>::Out>,
+{
+    type Out = <T as NormalizeSlots<<State as NormalizeStep<H>>::Out>>::Out;
+}
+
+/// Map the final scan state to the normalized node (`Node` is the original).
+pub trait NormalizeResult<Node> {
+    type Out;
+}
+
+impl<Node> NormalizeResult<Node> for SlotsEmpty {
+    type Out = Empty;
+}
+
+impl<Node, L> NormalizeResult<Node> for SlotsOneLeaf<L> {
+    type Out = L;
+}
+
+impl<Node> NormalizeResult<Node> for SlotsMany {
+    type Out = Node;
+}
+
 // =============================================================================
 // Bucket Operations (Linear Scan)
 // =============================================================================
 
-// InsertAt for Bucket - same type at head means already present
-impl<Cap, Tail, Depth> InsertAt<Cap, Depth> for Bucket<Cap, Tail>
+// A Bucket holds two or more capabilities whose streams are equal to full
+// depth, as `Bucket<A, Bucket<B, ... Empty>>` in insertion order. Members are
+// told apart by identity; anything with a different stream splits the bucket
+// into a Node16 exactly as it would split a Leaf.
+
+impl<Cap, Head, Tail, Depth> InsertAt<Cap, Depth> for Bucket<Head, Tail>
 where
     Cap: Capability,
+    Head: Capability,
+    Cap::Stream: StreamEq<Head::Stream, MaxDepthOf<Cap>>,
+    Self: BucketInsert<Cap, Depth, <Cap::Stream as StreamEq<Head::Stream, MaxDepthOf<Cap>>>::Out>,
 {
-    type Out = Self; // Already present
+    type Out = <Self as BucketInsert<
+        Cap, Depth, <Cap::Stream as StreamEq<Head::Stream, MaxDepthOf<Cap>>>::Out
+    >>::Out;
+}
+
+/// Insert into a Bucket by stream match: `SameStream` is `Present` (join the
+/// bucket) or `Absent` (push the bucket one level down and route `Cap`).
+pub trait BucketInsert<Cap, Depth, SameStream> {
+    type Out;
+}
+
+impl<Cap, Head, Tail, Depth> BucketInsert<Cap, Depth, Present> for Bucket<Head, Tail>
+where
+    Self: BucketPush<Cap>,
+{
+    type Out = <Self as BucketPush<Cap>>::Out;
+}
+
+impl<Cap, Head, Tail, Depth> BucketInsert<Cap, Depth, Absent> for Bucket<Head, Tail>
+where
+    Head: Capability,
+    Depth: Peano,
+    Head::Stream: GetTail<Depth>,
+    (): MakeNode16WithChild<Self, Head::At<Depth>>,
+    <() as MakeNode16WithChild<Self, Head::At<Depth>>>::Out: InsertAt<Cap, Depth>,
+{
+    type Out = <<() as MakeNode16WithChild<Self, Head::At<Depth>>>::Out as InsertAt<Cap, Depth>>::Out;
+}
+
+/// Append `Cap` to a bucket list unless a member has the same identity.
+pub trait BucketPush<Cap> {
+    type Out;
+}
+
+impl<Cap> BucketPush<Cap> for Empty {
+    type Out = Bucket<Cap, Empty>;
+}
+
+impl<Cap, Head, Tail> BucketPush<Cap> for Bucket<Head, Tail>
+where
+    Head: Capability,
+    Cap: CapabilityIdentityEq<Head>,
+    Tail: BucketPush<Cap>,
+{
+    type Out = <<Cap as CapabilityIdentityEq<Head>>::Out as Bool>::If<
+        Self,
+        Bucket<Head, <Tail as BucketPush<Cap>>::Out>,
+    >;
 }
 
-// RemoveAt for Bucket - same type at head means remove head
-impl<Cap, Tail, Depth> RemoveAt<Cap, Depth> for Bucket<Cap, Tail>
+impl<Cap, Head, Tail, Depth> RemoveAt<Cap, Depth> for Bucket<Head, Tail>
 where
     Cap: Capability,
+    Head: Capability,
+    Cap::Stream: StreamEq<Head::Stream, MaxDepthOf<Cap>>,
+    Self: BucketRemove<Cap, <Cap::Stream as StreamEq<Head::Stream, MaxDepthOf<Cap>>>::Out>,
+{
+    type Out = <Self as BucketRemove<
+        Cap, <Cap::Stream as StreamEq<Head::Stream, MaxDepthOf<Cap>>>::Out
+    >>::Out;
+}
+
+/// Remove from a Bucket by stream match: identities are only compared when
+/// `Cap` shares the bucket's stream.
+pub trait BucketRemove<Cap, SameStream> {
+    type Out;
+}
+
+impl<Cap, Head, Tail> BucketRemove<Cap, Present> for Bucket<Head, Tail>
+where
+    Self: BucketDrop<Cap>,
+    <Self as BucketDrop<Cap>>::Out: Normalize,
+{
+    type Out = <<Self as BucketDrop<Cap>>::Out as Normalize>::Out;
+}
+
+impl<Cap, Head, Tail> BucketRemove<Cap, Absent> for Bucket<Head, Tail> {
+    type Out = Self;
+}
+
+/// Remove the member with `Cap`'s identity from a bucket list.
+///
+/// A bucket left with one member becomes a `Leaf` via `Normalize`.
+/// Members are unique, so the search stops at the first match.
+pub trait BucketDrop<Cap> {
+    type Out;
+}
+
+impl<Cap> BucketDrop<Cap> for Empty {
+    type Out = Empty;
+}
+
+impl<Cap, Head, Tail> BucketDrop<Cap> for Bucket<Head, Tail>
+where
+    Head: Capability,
+    Cap: CapabilityIdentityEq<Head>,
+    Tail: BucketDrop<Cap>,
 {
-    type Out = Tail; // Remove head
+    type Out = <<Cap as CapabilityIdentityEq<Head>>::Out as Bool>::If<
+        Tail,
+        Bucket<Head, <Tail as BucketDrop<Cap>>::Out>,
+    >;
 }
 
 // =============================================================================
@@ -290,7 +547,7 @@ where
     label = "Failed to add {Cap} to {Self}",
     note = "Ensure {Self} is a valid capability set (Empty/Leaf/Node) and {Cap} is a Capability."
 )]
-pub trait With<Cap>: Sized {
+pub trait With<Cap>: TrieNode + Sized {
     type Out;
 }
 
@@ -302,13 +559,113 @@ where
     type Out = <Ctx as InsertAt<Cap, D0>>::Out;
 }
 
+/// [`With`] with the operands swapped: `<Cap as AddTo<Set>>::Out` is
+/// `<Set as With<Cap>>::Out`.
+///
+/// Useful in method signatures such as `fn add<N: AddTo<C>>(self) -> X<N::Out>`:
+/// with `N` as the `Self` type, rustc leaves the projection alone until `N`
+/// is known instead of walking the (cyclic) hash stream of an unknown cap.
+pub trait AddTo<Set> {
+    type Out;
+}
+
+impl<Cap, Set: With<Cap>> AddTo<Set> for Cap {
+    type Out = <Set as With<Cap>>::Out;
+}
+
+/// [`With`] that checks `Has<Cap>` first: `Self` unchanged when `Cap` is
+/// already present, else `<Self as With<Cap>>::Out`.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::WithIfAbsent;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanWrite;
+///
+/// type Same = <caps![CanRead] as WithIfAbsent<CanRead>>::Out;
+/// type Both = <caps![CanRead] as WithIfAbsent<CanWrite>>::Out;
+/// tola_caps::caps_eq!(Same, caps![CanRead]);
+/// tola_caps::caps_eq!(Both, caps![CanRead, CanWrite]);
+/// ```
+pub trait WithIfAbsent<Cap>: TrieNode + Sized {
+    type Out;
+}
+
+impl<Ctx, Cap> WithIfAbsent<Cap> for Ctx
+where
+    Cap: Capability,
+    Ctx: EvalAt<Has<Cap>, D0> + With<Cap>,
+{
+    type Out = <<Ctx as EvalAt<Has<Cap>, D0>>::Out as Bool>::If<Ctx, <Ctx as With<Cap>>::Out>;
+}
+
+/// [`WithIfAbsent`] with the operands swapped, for method signatures (see [`AddTo`]).
+pub trait AddIfAbsentTo<Set> {
+    type Out;
+}
+
+impl<Cap, Set: WithIfAbsent<Cap>> AddIfAbsentTo<Set> for Cap {
+    type Out = <Set as WithIfAbsent<Cap>>::Out;
+}
+
+/// Default size limit for [`WithLimit`].
+pub type MaxCaps = D64;
+
+/// `With` that stops at a size limit instead of recursing further.
+///
+/// `Limit` bounds the number of capabilities in the set, not the trie
+/// depth. Adding a new capability to a set that already holds `Limit`
+/// capabilities fails with a short error naming the capability, rather
+/// than a `recursion limit reached` deep inside `InsertAt`/`EvalAt`.
+/// Adding one that is already present leaves the set unchanged, even at
+/// the limit. Checking the limit counts the whole set, so plain [`With`]
+/// stays the cheaper choice where no limit is needed.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::WithLimit;
+/// use tola_caps::primitives::stream::D2;
+///
+/// #[derive(Capability)] struct CanRead;
+/// #[derive(Capability)] struct CanWrite;
+///
+/// type Two = <caps![CanRead] as WithLimit<CanWrite, D2>>::Out;
+/// assert_eq!(<Two as InspectNames>::COUNT, 2);
+/// ```
+pub trait WithLimit<Cap, Limit: Peano = MaxCaps>: TrieNode + Sized {
+    type Out;
+}
+
+impl<Ctx, Cap, Limit> WithLimit<Cap, Limit> for Ctx
+where
+    Cap: Capability,
+    Limit: Peano,
+    Ctx: EvalAt<Has<Cap>, D0> + Count + InsertAt<Cap, D0>,
+    <Ctx as Count>::Out: PeanoLess<Limit>,
+    <<Ctx as EvalAt<Has<Cap>, D0>>::Out as Bool>::Or<<<Ctx as Count>::Out as PeanoLess<Limit>>::Out>:
+        WithinMaxCaps<Ctx, Cap>,
+{
+    type Out = <<Ctx as EvalAt<Has<Cap>, D0>>::Out as Bool>::If<Ctx, <Ctx as InsertAt<Cap, D0>>::Out>;
+}
+
+/// Size guard for [`WithLimit`]: implemented only for `Present`.
+#[diagnostic::on_unimplemented(
+    message = "capability set exceeds its configured maximum size; raise the limit or remove capabilities",
+    label = "cannot add `{Cap}`: the set is already at its size limit",
+    note = "the limit is the `Limit` parameter of `WithLimit` (default `MaxCaps`)"
+)]
+pub trait WithinMaxCaps<Set, Cap> {}
+
+impl<Set, Cap> WithinMaxCaps<Set, Cap> for Present {}
+
 /// Remove a capability from a set (user-facing API)
 ///
 /// This trait wraps `RemoveAt` with depth=D0 for a cleaner API.
 /// ```ignore
 /// type Reduced = <MySet as Without<CloneCap>>::Out;
 /// ```
-pub trait Without<Cap>: Sized {
+pub trait Without<Cap>: TrieNode + Sized {
     type Out;
 }
 
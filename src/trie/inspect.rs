@@ -2,8 +2,17 @@
 //!
 //! Allows iterating over all capabilities in a set for debugging.
 
-use super::node::{Empty, Leaf, Node16};
-use super::capability::Capability;
+use core::any::TypeId;
+use core::fmt;
+use core::marker::PhantomData;
+
+use super::node::{Empty, Leaf, Node16, Bucket};
+use super::capability::{Capability, CapabilityPath};
+use super::evaluate::{Evaluate, HCons, HNil, Has};
+use crate::primitives::stream::{PeanoVal, S};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
 
 /// Runtime inspection of capability sets.
 ///
@@ -12,6 +21,306 @@ use super::capability::Capability;
 pub trait Inspect {
     /// Calls `f` for each capability in the set with its type name.
     fn inspect<F: FnMut(&'static str)>(&self, f: F);
+
+    /// Returns whether the set contains `Q`.
+    ///
+    /// Resolved at compile time; handy where threading an `Evaluate` bound
+    /// through the caller is inconvenient.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::Inspect;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    /// #[derive(Capability)]
+    /// struct CanWrite;
+    ///
+    /// assert!(<caps![CanRead]>::has::<CanRead>());
+    /// assert!(!<caps![CanRead]>::has::<CanWrite>());
+    /// ```
+    fn has<Q: Capability>() -> bool
+    where
+        Self: Evaluate<Has<Q>>,
+    {
+        <Self as Evaluate<Has<Q>>>::RESULT
+    }
+
+    /// Returns a [`CapToken`] for `Q` if the set contains it.
+    ///
+    /// Bridges a static set into `Option`-based runtime code, e.g. when
+    /// deciding which dynamically loaded plugins may run.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::Inspect;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    /// #[derive(Capability)]
+    /// struct CanWrite;
+    ///
+    /// assert!(<caps![CanRead]>::present::<CanRead>().is_some());
+    /// assert!(<caps![CanRead]>::present::<CanWrite>().is_none());
+    /// ```
+    fn present<Q: Capability>() -> Option<CapToken<Q>>
+    where
+        Self: Evaluate<Has<Q>>,
+    {
+        if <Self as Evaluate<Has<Q>>>::RESULT {
+            Some(CapToken::new())
+        } else {
+            None
+        }
+    }
+}
+
+/// Name-level enumeration of a capability set, by type rather than value.
+///
+/// Separate from [`Inspect`] so that impls of it written before these
+/// methods existed keep compiling. Implemented for every trie node.
+pub trait InspectNames {
+    /// Number of capabilities stored in the set.
+    ///
+    /// Usable in const contexts; see [`InspectNames::count`] for the runtime form.
+    const COUNT: usize;
+
+    /// Calls `f` with each capability's `TypeId` and [`Capability::NAME`],
+    /// in trie order.
+    ///
+    /// Node16 slots are visited from `0x0` to `0xF`.
+    fn each_cap<F: FnMut(TypeId, &'static str)>(f: &mut F);
+
+    /// Calls `f` with each capability's [`Capability::NAME`], in trie order.
+    fn each_name<F: FnMut(&'static str)>(f: &mut F) {
+        Self::each_cap(&mut |_, name| f(name));
+    }
+
+    /// Object-safe form of [`InspectNames::each_name`].
+    ///
+    /// Never allocates, so it is the way to enumerate names without `alloc`:
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::InspectNames;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    ///
+    /// let mut buf = [""; 4];
+    /// let mut len = 0;
+    /// <caps![CanRead]>::for_each_name(&mut |name| {
+    ///     buf[len] = name;
+    ///     len += 1;
+    /// });
+    /// assert_eq!(&buf[..len], ["CanRead"]);
+    /// ```
+    fn for_each_name(f: &mut dyn FnMut(&'static str)) {
+        Self::each_name(&mut |name| f(name));
+    }
+
+    /// Returns the number of capabilities stored in the set.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::InspectNames;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    /// #[derive(Capability)]
+    /// struct CanWrite;
+    ///
+    /// assert_eq!(<caps![CanRead, CanWrite]>::count(), 2);
+    /// ```
+    fn count() -> usize {
+        Self::COUNT
+    }
+
+    /// Short summary of the set's root node, for logging without `alloc`.
+    ///
+    /// One of `"empty"`, `"leaf"`, `"bucket(n=2)"` (colliding capabilities)
+    /// or `"node(n=3)"`, where `n` is the number of capabilities below.
+    /// Impls outside the crate default to `"empty"` or `"set(n=N)"`.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::InspectNames;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    /// #[derive(Capability)]
+    /// struct CanWrite;
+    ///
+    /// assert_eq!(<caps![]>::describe(), "empty");
+    /// assert_eq!(<caps![CanRead]>::describe(), "leaf");
+    /// assert_eq!(<caps![CanRead, CanWrite]>::describe(), "node(n=2)");
+    /// ```
+    fn describe() -> &'static str {
+        if Self::COUNT == 0 {
+            "empty"
+        } else {
+            const { &CountLabel::new("set", <Self as InspectNames>::COUNT) }.as_str()
+        }
+    }
+
+    /// Collects the [`Capability::NAME`] of every capability in the set.
+    ///
+    /// ```
+    /// use tola_caps::prelude::*;
+    /// use tola_caps::InspectNames;
+    ///
+    /// #[derive(Capability)]
+    /// struct CanRead;
+    ///
+    /// assert_eq!(<caps![CanRead]>::names(), ["CanRead"]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn names() -> Vec<&'static str> {
+        let mut out = Vec::new();
+        Self::each_name(&mut |name| out.push(name));
+        out
+    }
+
+    /// Renders the set as `{CanRead, CanWrite}`, in trie order.
+    #[cfg(feature = "alloc")]
+    fn render() -> String {
+        alloc::format!("{:?}", CapSetDisplay::<Self>::new())
+    }
+}
+
+/// Formats a capability set type for printing.
+///
+/// Capability sets are zero-sized markers, so this wrapper carries the set
+/// type and implements `Debug`/`Display` via [`InspectNames::each_name`].
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::CapSetDisplay;
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+///
+/// assert_eq!(format!("{:?}", CapSetDisplay::<caps![CanRead]>::default()), "{CanRead}");
+/// ```
+pub struct CapSetDisplay<C: ?Sized>(PhantomData<C>);
+
+impl<C: ?Sized> CapSetDisplay<C> {
+    /// Creates a display wrapper for `C`.
+    pub const fn new() -> Self {
+        CapSetDisplay(PhantomData)
+    }
+}
+
+impl<C: ?Sized> Default for CapSetDisplay<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: InspectNames + ?Sized> fmt::Debug for CapSetDisplay<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut set = f.debug_set();
+        C::each_name(&mut |name| {
+            set.entry(&format_args!("{}", name));
+        });
+        set.finish()
+    }
+}
+
+impl<C: InspectNames + ?Sized> fmt::Display for CapSetDisplay<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Zero-sized runtime witness that a capability `C` was present.
+///
+/// Obtained from [`Inspect::present`] (or minted directly with
+/// [`CapToken::new`] where the capability is known statically).
+pub struct CapToken<C>(PhantomData<C>);
+
+impl<C> CapToken<C> {
+    /// Creates a token for `C`.
+    pub const fn new() -> Self {
+        CapToken(PhantomData)
+    }
+}
+
+impl<C> Default for CapToken<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for CapToken<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for CapToken<C> {}
+
+impl<C: Capability> fmt::Debug for CapToken<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CapToken<{}>", C::NAME)
+    }
+}
+
+/// Serializes a capability set type as a sequence of [`Capability::NAME`]s,
+/// sorted by name so the output does not depend on hash order
+/// (e.g. `["CanRead","CanWrite"]` in JSON).
+///
+/// Serialization only: set types cannot be reconstructed from names.
+#[cfg(feature = "serde")]
+pub struct CapSetSerde<C: ?Sized>(PhantomData<C>);
+
+#[cfg(feature = "serde")]
+impl<C: ?Sized> CapSetSerde<C> {
+    /// Creates a serialization wrapper for `C`.
+    pub const fn new() -> Self {
+        CapSetSerde(PhantomData)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: ?Sized> Default for CapSetSerde<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: InspectNames + ?Sized> serde::Serialize for CapSetSerde<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(C::COUNT))?;
+        // Selection by name over repeated traversals: sorted output without alloc.
+        let mut last: Option<&'static str> = None;
+        loop {
+            let mut next: Option<&'static str> = None;
+            let mut repeats = 0;
+            C::each_name(&mut |name| {
+                if last.is_some_and(|last| name <= last) {
+                    return;
+                }
+                match next {
+                    Some(cur) if name > cur => {}
+                    Some(cur) if name == cur => repeats += 1,
+                    _ => {
+                        next = Some(name);
+                        repeats = 1;
+                    }
+                }
+            });
+            let Some(name) = next else { break };
+            for _ in 0..repeats {
+                seq.serialize_element(name)?;
+            }
+            last = Some(name);
+        }
+        seq.end()
+    }
 }
 
 impl Inspect for Empty {
@@ -24,6 +333,13 @@ impl<C: Capability> Inspect for Leaf<C> {
     }
 }
 
+impl<H: Capability, T: Inspect + Default> Inspect for Bucket<H, T> {
+    fn inspect<F: FnMut(&'static str)>(&self, mut f: F) {
+        f(core::any::type_name::<H>());
+        T::default().inspect(f);
+    }
+}
+
 /// Inspect impl for Node16 using #[node16(each_slot)]
 #[macros::node16(each_slot)]
 impl<_Slots_> Inspect for _Node16_
@@ -35,3 +351,705 @@ where
         <_Slot_ as Inspect>::inspect(&<_Slot_>::default(), &mut f);
     }
 }
+
+/// Sorted member lists from [`SortSet`](crate::trie::SortSet), in list order.
+impl Inspect for HNil {
+    fn inspect<F: FnMut(&'static str)>(&self, _f: F) {}
+}
+
+impl<H: Capability, T: Inspect + Default> Inspect for HCons<H, T> {
+    fn inspect<F: FnMut(&'static str)>(&self, mut f: F) {
+        f(core::any::type_name::<H>());
+        T::default().inspect(f);
+    }
+}
+
+impl InspectNames for Empty {
+    const COUNT: usize = 0;
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(_f: &mut F) {}
+
+    fn describe() -> &'static str {
+        "empty"
+    }
+}
+
+impl<C: Capability> InspectNames for Leaf<C> {
+    const COUNT: usize = 1;
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(f: &mut F) {
+        f(TypeId::of::<C>(), C::NAME);
+    }
+
+    fn describe() -> &'static str {
+        "leaf"
+    }
+}
+
+impl<H: Capability, T: InspectNames> InspectNames for Bucket<H, T> {
+    const COUNT: usize = 1 + T::COUNT;
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(f: &mut F) {
+        f(TypeId::of::<H>(), H::NAME);
+        T::each_cap(f);
+    }
+
+    fn describe() -> &'static str {
+        const { &CountLabel::new("bucket", <Self as InspectNames>::COUNT) }.as_str()
+    }
+}
+
+#[macros::node16(each_slot)]
+impl<_Slots_> InspectNames for _Node16_
+where
+    each(_Slots_): InspectNames,
+{
+    const COUNT: usize = {
+        let mut n = 0;
+        n += <_Slot_ as InspectNames>::COUNT;
+        n
+    };
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(f: &mut F) {
+        <_Slot_ as InspectNames>::each_cap(f);
+    }
+
+    fn describe() -> &'static str {
+        const { &CountLabel::new("node", <Self as InspectNames>::COUNT) }.as_str()
+    }
+}
+
+impl InspectNames for HNil {
+    const COUNT: usize = 0;
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(_f: &mut F) {}
+
+    fn describe() -> &'static str {
+        "empty"
+    }
+}
+
+impl<H: Capability, T: InspectNames> InspectNames for HCons<H, T> {
+    const COUNT: usize = 1 + T::COUNT;
+
+    fn each_cap<F: FnMut(TypeId, &'static str)>(f: &mut F) {
+        f(TypeId::of::<H>(), H::NAME);
+        T::each_cap(f);
+    }
+
+    fn describe() -> &'static str {
+        const { &CountLabel::new("list", <Self as InspectNames>::COUNT) }.as_str()
+    }
+}
+
+/// `kind(n=N)` label built at compile time for [`InspectNames::describe`].
+struct CountLabel {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl CountLabel {
+    const fn new(kind: &str, n: usize) -> Self {
+        let mut label = CountLabel { bytes: [0; 32], len: 0 };
+        label = label.push(kind.as_bytes()).push(b"(n=");
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        let mut rest = n;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (rest % 10) as u8;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        label.push(digits.split_at(start).1).push(b")")
+    }
+
+    const fn push(mut self, s: &[u8]) -> Self {
+        let mut i = 0;
+        while i < s.len() {
+            self.bytes[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => "",
+        }
+    }
+}
+
+// =============================================================================
+// Trie layout dump
+// =============================================================================
+
+/// Byte capacity of a [`TrieDump`] rendering; longer dumps end in `...`.
+pub const TRIE_DUMP_CAPACITY: usize = 4096;
+
+/// Fixed-size text buffer built during const evaluation by [`TrieDump`].
+#[derive(Clone, Copy)]
+pub struct TrieDumpBuf {
+    bytes: [u8; TRIE_DUMP_CAPACITY],
+    len: usize,
+}
+
+impl TrieDumpBuf {
+    const ELLIPSIS: &'static [u8] = b"...";
+
+    /// An empty buffer.
+    pub const fn new() -> Self {
+        TrieDumpBuf { bytes: [0; TRIE_DUMP_CAPACITY], len: 0 }
+    }
+
+    /// Appends `s`, truncating with `...` once the buffer is full.
+    pub const fn push_str(self, s: &str) -> Self {
+        self.push_bytes(s.as_bytes())
+    }
+
+    const fn push_bytes(mut self, s: &[u8]) -> Self {
+        let room = TRIE_DUMP_CAPACITY - Self::ELLIPSIS.len();
+        let mut i = 0;
+        while i < s.len() {
+            if self.len == room {
+                let mut j = 0;
+                while j < Self::ELLIPSIS.len() {
+                    self.bytes[self.len] = Self::ELLIPSIS[j];
+                    self.len += 1;
+                    j += 1;
+                }
+                return self;
+            }
+            if self.len > room {
+                return self;
+            }
+            self.bytes[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    /// Appends a Node16 child line (indentation, `[slot]`, then its dump)
+    /// unless the child is empty, and returns the next slot index.
+    const fn push_slot(self, slot: usize, depth: usize, empty: bool, child: &TrieDumpBuf) -> (Self, usize) {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        if empty {
+            return (self, slot + 1);
+        }
+        let mut out = self;
+        let mut i = 0;
+        while i < depth {
+            out = out.push_bytes(b"  ");
+            i += 1;
+        }
+        out = out.push_bytes(&[b'[', HEX[slot], b']', b' ']);
+        (out.push_bytes(child.bytes.split_at(child.len).0), slot + 1)
+    }
+
+    /// The rendered text.
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(s) => s,
+            // Truncation can split a multi-byte name.
+            Err(e) => match core::str::from_utf8(self.bytes.split_at(e.valid_up_to()).0) {
+                Ok(s) => s,
+                Err(_) => "",
+            },
+        }
+    }
+}
+
+impl Default for TrieDumpBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Const ASCII rendering of a trie's shape, one node per line.
+///
+/// `D` is the depth of `Self`, used for indentation. Node16 children are
+/// listed by slot nibble; empty slots are omitted. Use [`dump_trie!`](crate::dump_trie).
+pub trait TrieDump<D> {
+    /// `true` for `Empty`, so parents can skip the slot.
+    const IS_EMPTY: bool;
+    /// Rendering of this node and everything below it.
+    const DUMP: TrieDumpBuf;
+}
+
+impl<D> TrieDump<D> for Empty {
+    const IS_EMPTY: bool = true;
+    const DUMP: TrieDumpBuf = TrieDumpBuf::new().push_str("Empty\n");
+}
+
+impl<C: Capability, D> TrieDump<D> for Leaf<C> {
+    const IS_EMPTY: bool = false;
+    const DUMP: TrieDumpBuf = TrieDumpBuf::new().push_str("Leaf ").push_str(C::NAME).push_str("\n");
+}
+
+impl<H: Capability, T: BucketDump, D> TrieDump<D> for Bucket<H, T> {
+    const IS_EMPTY: bool = false;
+    const DUMP: TrieDumpBuf = TrieDumpBuf::new().push_str("Bucket ").push_str(H::NAME).push_str(T::NAMES.as_str()).push_str("\n");
+}
+
+/// Member names of a bucket's tail, each prefixed with `", "`.
+pub trait BucketDump {
+    const NAMES: TrieDumpBuf;
+}
+
+impl BucketDump for Empty {
+    const NAMES: TrieDumpBuf = TrieDumpBuf::new();
+}
+
+impl<H: Capability, T: BucketDump> BucketDump for Bucket<H, T> {
+    const NAMES: TrieDumpBuf = TrieDumpBuf::new().push_str(", ").push_str(H::NAME).push_str(T::NAMES.as_str());
+}
+
+#[macros::node16(each_slot)]
+impl<_Slots_, D: PeanoVal> TrieDump<D> for _Node16_
+where
+    each(_Slots_): TrieDump<S<D>>,
+{
+    const IS_EMPTY: bool = false;
+    const DUMP: TrieDumpBuf = {
+        let mut buf = TrieDumpBuf::new().push_str("Node16\n");
+        let mut slot = 0;
+        (buf, slot) = buf.push_slot(slot, D::VAL + 1, <_Slot_ as TrieDump<S<D>>>::IS_EMPTY, &<_Slot_ as TrieDump<S<D>>>::DUMP);
+        let _ = slot;
+        buf
+    };
+}
+
+/// Compares `actual` (a set's [`CapNames::NAMES`]) with `expected`,
+/// ignoring order. Returns an empty buffer on a match, else a message
+/// listing the missing and unexpected names. Used by
+/// [`caps_snapshot!`](crate::caps_snapshot).
+#[doc(hidden)]
+pub const fn snapshot_diff(set: &str, actual: &[&str], expected: &[&str]) -> TrieDumpBuf {
+    let mut missing = TrieDumpBuf::new();
+    let mut i = 0;
+    while i < expected.len() {
+        if !has_name(actual, expected[i].as_bytes()) {
+            missing = push_listed(missing, expected[i].as_bytes());
+        }
+        i += 1;
+    }
+
+    let mut unexpected = TrieDumpBuf::new();
+    let mut i = 0;
+    while i < actual.len() {
+        if !has_name(expected, actual[i].as_bytes()) {
+            unexpected = push_listed(unexpected, actual[i].as_bytes());
+        }
+        i += 1;
+    }
+
+    if missing.len == 0 && unexpected.len == 0 {
+        return TrieDumpBuf::new();
+    }
+    TrieDumpBuf::new()
+        .push_str("caps_snapshot! mismatch for `")
+        .push_str(set)
+        .push_str("`: missing [")
+        .push_bytes(missing.bytes.split_at(missing.len).0)
+        .push_str("], unexpected [")
+        .push_bytes(unexpected.bytes.split_at(unexpected.len).0)
+        .push_str("]")
+}
+
+/// Appends `name` quoted, comma-separated from earlier entries.
+const fn push_listed(buf: TrieDumpBuf, name: &[u8]) -> TrieDumpBuf {
+    let buf = if buf.len == 0 { buf } else { buf.push_bytes(b", ") };
+    buf.push_bytes(b"\"").push_bytes(name).push_bytes(b"\"")
+}
+
+const fn has_name(expected: &[&str], name: &[u8]) -> bool {
+    let mut i = 0;
+    while i < expected.len() {
+        if bytes_eq(expected[i].as_bytes(), name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// =============================================================================
+// Const name lists
+// =============================================================================
+
+/// Most names a [`CapNames`] list can hold.
+pub const CAP_NAMES_CAPACITY: usize = 128;
+
+/// Fixed-capacity name list built during const trie traversal.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct CapNameBuf {
+    names: [&'static str; CAP_NAMES_CAPACITY],
+    len: usize,
+}
+
+impl CapNameBuf {
+    pub const fn new() -> Self {
+        CapNameBuf { names: [""; CAP_NAMES_CAPACITY], len: 0 }
+    }
+
+    pub const fn push(mut self, name: &'static str) -> Self {
+        assert!(self.len < CAP_NAMES_CAPACITY, "CapNames: set exceeds CAP_NAMES_CAPACITY");
+        self.names[self.len] = name;
+        self.len += 1;
+        self
+    }
+
+    pub const fn append(mut self, other: &CapNameBuf) -> Self {
+        let mut i = 0;
+        while i < other.len {
+            self = self.push(other.names[i]);
+            i += 1;
+        }
+        self
+    }
+}
+
+impl Default for CapNameBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Capability names of a set as a `const` slice, in trie order.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::{set_contains, CapNames};
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// struct CanWrite;
+///
+/// const NAMES: &[&str] = <caps![CanRead, CanWrite] as CapNames>::NAMES;
+/// const OK: bool = set_contains(NAMES, "CanRead") && set_contains(NAMES, "CanWrite");
+/// assert!(OK);
+/// ```
+pub trait CapNames {
+    #[doc(hidden)]
+    const BUF: CapNameBuf;
+
+    /// Member names, one per capability.
+    const NAMES: &'static [&'static str] = {
+        let buf: &'static CapNameBuf = &Self::BUF;
+        buf.names.split_at(buf.len).0
+    };
+}
+
+impl CapNames for Empty {
+    const BUF: CapNameBuf = CapNameBuf::new();
+}
+
+impl<C: Capability> CapNames for Leaf<C> {
+    const BUF: CapNameBuf = CapNameBuf::new().push(C::NAME);
+}
+
+impl<H: Capability, T: CapNames> CapNames for Bucket<H, T> {
+    const BUF: CapNameBuf = CapNameBuf::new().push(H::NAME).append(&T::BUF);
+}
+
+#[macros::node16(each_slot)]
+impl<_Slots_> CapNames for _Node16_
+where
+    each(_Slots_): CapNames,
+{
+    const BUF: CapNameBuf = {
+        let mut buf = CapNameBuf::new();
+        buf = buf.append(&<_Slot_ as CapNames>::BUF);
+        buf
+    };
+}
+
+/// Whether `names` contains `target`, usable in `const` contexts.
+pub const fn set_contains(names: &[&str], target: &str) -> bool {
+    has_name(names, target.as_bytes())
+}
+
+/// The [`CapNames::NAMES`] of a capability set, as a `const` expression.
+///
+/// ```
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+///
+/// const NAMES: &[&str] = tola_caps::cap_names!(caps![CanRead]);
+/// assert_eq!(NAMES, ["CanRead"]);
+/// ```
+#[macro_export]
+macro_rules! cap_names {
+    ($set:ty) => {
+        <$set as $crate::trie::CapNames>::NAMES
+    };
+}
+
+/// Renders the trie layout of a capability set as a `&'static str`,
+/// evaluated at compile time.
+///
+/// Each Node16 child is shown under its slot nibble, so two capabilities
+/// that share a path prefix show up nested under the same slots.
+///
+/// The rendering is read off the set's node types ([`TrieDump`]) rather
+/// than recomputed from capability names, so explicit streams, namespaces,
+/// `alias_of`, hand-written `Capability` impls and collision buckets
+/// appear exactly as the trie stores them:
+///
+/// ```
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)]
+/// #[capability(stream = "X3 X0")]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// #[capability(stream = "X3 X7")]
+/// struct CanWrite;
+///
+/// const LAYOUT: &str = tola_caps::dump_trie!(caps![CanRead, CanWrite]);
+/// assert_eq!(LAYOUT, "Node16\n  [3] Node16\n    [0] Leaf CanRead\n    [7] Leaf CanWrite\n");
+/// ```
+#[macro_export]
+macro_rules! dump_trie {
+    ($set:ty) => {{
+        const __DUMP: $crate::trie::TrieDumpBuf =
+            <$set as $crate::trie::TrieDump<$crate::primitives::stream::D0>>::DUMP;
+        __DUMP.as_str()
+    }};
+}
+
+// =============================================================================
+// Bitmask encoding
+// =============================================================================
+
+/// Stable bit index (`0..64`) of a capability, used by [`InspectBits`].
+///
+/// Assign indices with [`register_caps!`](crate::register_caps), which also
+/// checks them for range and uniqueness.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no bit index",
+    note = "assign one with `register_caps!({Self} = N)`"
+)]
+pub trait CapBit: Capability {
+    /// Bit index in the `u64` mask.
+    const BIT: u32;
+}
+
+/// `u64` bitmask of a capability set, e.g. for passing across FFI.
+///
+/// Every capability in the set must implement [`CapBit`].
+pub trait InspectBits {
+    /// OR of `1 << BIT` over all capabilities in the set.
+    const BITS: u64;
+}
+
+impl InspectBits for Empty {
+    const BITS: u64 = 0;
+}
+
+impl<C: CapBit> InspectBits for Leaf<C> {
+    const BITS: u64 = 1 << C::BIT;
+}
+
+impl<H: CapBit, T: InspectBits> InspectBits for Bucket<H, T> {
+    const BITS: u64 = (1 << H::BIT) | T::BITS;
+}
+
+#[macros::node16(each_slot)]
+impl<_Slots_> InspectBits for _Node16_
+where
+    each(_Slots_): InspectBits,
+{
+    const BITS: u64 = {
+        let mut bits = 0;
+        bits |= <_Slot_ as InspectBits>::BITS;
+        bits
+    };
+}
+
+/// Returns the bitmask of `C`.
+///
+/// ```
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// struct CanWrite;
+///
+/// tola_caps::register_caps!(CanRead = 0, CanWrite = 3);
+///
+/// assert_eq!(tola_caps::to_bits::<caps![CanRead, CanWrite]>(), 0b1001);
+/// ```
+pub const fn to_bits<C: InspectBits>() -> u64 {
+    C::BITS
+}
+
+/// A list of registered capabilities (`hlist![CanRead, CanWrite]`) used to
+/// decode a bitmask.
+pub trait BitRegistry {
+    /// OR of the bits of every capability in the registry.
+    const MASK: u64;
+
+    /// Calls `f` with the [`Capability::NAME`] of each registry entry whose bit is set.
+    fn each_set<F: FnMut(&'static str)>(bits: u64, f: &mut F);
+}
+
+impl BitRegistry for HNil {
+    const MASK: u64 = 0;
+
+    fn each_set<F: FnMut(&'static str)>(_bits: u64, _f: &mut F) {}
+}
+
+impl<H: CapBit, T: BitRegistry> BitRegistry for HCons<H, T> {
+    const MASK: u64 = (1 << H::BIT) | T::MASK;
+
+    fn each_set<F: FnMut(&'static str)>(bits: u64, f: &mut F) {
+        if bits & (1 << H::BIT) != 0 {
+            f(H::NAME);
+        }
+        T::each_set(bits, f);
+    }
+}
+
+/// Decodes a bitmask into the names of the registry entries it contains, in
+/// registry order. Bits outside `R::MASK` are ignored.
+#[cfg(feature = "alloc")]
+pub fn from_bits<R: BitRegistry>(bits: u64) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    R::each_set(bits, &mut |name| out.push(name));
+    out
+}
+
+/// Assign fixed bit indices to capabilities for [`InspectBits`].
+/// Usage: `register_caps!(CanRead = 0, CanWrite = 1);`
+///
+/// Indices must be below 64 and unique within one invocation.
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// tola_caps::register_caps!(A = 1, B = 1);
+/// ```
+#[macro_export]
+macro_rules! register_caps {
+    ($($cap:ty = $bit:literal),* $(,)?) => {
+        $(
+            impl $crate::trie::CapBit for $cap {
+                const BIT: u32 = $bit;
+            }
+        )*
+
+        const _: () = {
+            let bits: &[u32] = &[$($bit),*];
+            let mut i = 0;
+            while i < bits.len() {
+                assert!(bits[i] < 64, "register_caps!: bit index must be below 64");
+                let mut j = i + 1;
+                while j < bits.len() {
+                    assert!(bits[i] != bits[j], "register_caps!: duplicate bit index");
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+    };
+}
+
+// =============================================================================
+// Fingerprints
+// =============================================================================
+
+/// Order-independent `u64` hash of a capability set, e.g. for keying a
+/// runtime cache by the set an artifact was built with.
+///
+/// Each member contributes a mix of its 64-bit routing stream
+/// ([`CapabilityPath::PATH`]) and the contributions are summed, so sets equal
+/// under [`SetEq`](crate::trie::SetEq) fingerprint identically. Members whose
+/// streams collide contribute the same value.
+pub trait SetFingerprint {
+    /// Fingerprint of the set; `0` for the empty set.
+    const HASH: u64;
+}
+
+impl SetFingerprint for Empty {
+    const HASH: u64 = 0;
+}
+
+impl<C: CapabilityPath> SetFingerprint for Leaf<C> {
+    const HASH: u64 = member_hash(C::PATH);
+}
+
+impl<H: CapabilityPath, T: SetFingerprint> SetFingerprint for Bucket<H, T> {
+    const HASH: u64 = member_hash(H::PATH).wrapping_add(T::HASH);
+}
+
+#[macros::node16(each_slot)]
+impl<_Slots_> SetFingerprint for _Node16_
+where
+    each(_Slots_): SetFingerprint,
+{
+    const HASH: u64 = {
+        let mut hash: u64 = 0;
+        hash = hash.wrapping_add(<_Slot_ as SetFingerprint>::HASH);
+        hash
+    };
+}
+
+/// SplitMix64 finalizer over the packed routing nibbles.
+const fn member_hash(path: [u8; 16]) -> u64 {
+    let mut x: u64 = 0;
+    let mut i = 0;
+    while i < path.len() {
+        x = (x << 4) | path[i] as u64;
+        i += 1;
+    }
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Returns the fingerprint of `C`.
+///
+/// ```
+/// use tola_caps::prelude::*;
+///
+/// #[derive(Capability)]
+/// struct CanRead;
+/// #[derive(Capability)]
+/// struct CanWrite;
+///
+/// let a = tola_caps::fingerprint::<caps![CanRead, CanWrite]>();
+/// let b = tola_caps::fingerprint::<caps![CanWrite, CanRead]>();
+/// assert_eq!(a, b);
+/// assert_ne!(a, tola_caps::fingerprint::<caps![CanRead]>());
+/// ```
+pub const fn fingerprint<C: SetFingerprint>() -> u64 {
+    C::HASH
+}
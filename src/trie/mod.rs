@@ -16,22 +16,40 @@ pub mod insert;
 pub mod ops;
 pub mod inspect;
 pub mod aliases;
+#[cfg(feature = "alloc")]
+pub mod dyn_set;
 
 // Re-export key types at trie level
-pub use node::{Empty, Leaf, Node16, EmptyNode16};
-pub use capability::Capability;
+pub use node::{Empty, Leaf, Node16, EmptyNode16, Bucket};
+pub use capability::{Capability, CapabilityConfig, CapabilityIdentityEq, CapabilityOrd, CapabilityPath, EvalPath, VersionedCapability};
 pub use evaluate::{
-    Evaluate, EvalAt, RouteQuery,
-    Has, And, Or, Not, All, Any, HNil, HCons,
-    IsTrue, Require,
+    Evaluate, EvalAt, RouteQuery, RouteBy, VersionGate,
+    Has, HasAtLeast, NoneOfFamily, And, Or, Not, Xor, Implies, Superset, ComplementOf, All, Any, AllAbsent, AnyAbsent, AtLeast, AtMost, ExactlyN, HNil, HCons,
+    CountPresent, IncIf,
+    IsTrue, Satisfied, Require, RequireEach, FirstAbsent, FirstAbsentStep, RequireAll, RequireAny, HasList, Resolve, Evaluated,
+    BucketEval, BucketContains,
 };
 pub use insert::{
-    InsertAt, RemoveAt, NodeInsert, LeafInsert, LeafRemove, NodeRemove,
-    MakeNode16WithLeaf, With, Without,
+    InsertAt, RemoveAt, NodeInsert, LeafInsert, LeafInsertBranch, LeafRemove, NodeRemove,
+    MakeNode16WithLeaf, MakeNode16WithChild, With, AddTo, WithIfAbsent, AddIfAbsentTo, Without, WithLimit, WithinMaxCaps, MaxCaps, LeafCollisionBranch,
+    BucketInsert, BucketPush, BucketRemove, BucketDrop,
+    Normalize, NormalizeStep, NormalizeSlots, NormalizeResult, SlotsEmpty, SlotsOneLeaf, SlotsMany,
 };
 pub use ops::{
-    SetUnion, SetIntersect, SupersetOf, SetAnd,
+    SetUnion, UnionAt, SetIntersect, IntersectAt, SetDifference, WithoutStep,
+    SupersetOf, IsSuperset, ContainsStep, SetEq, AssertSetEq,
+    Disjoint, AssertDisjoint, SetAnd,
+    IsEmpty, CollapseEmpty, AllEmpty, BucketInsertAll, BucketRetain,
     IntersectLeafHelper, LeafAndDispatch, NodeAndDispatch,
+    CapPredicate, FoldStep, FoldCaps, FoldSlots, Partition, PartitionStep, RemoveIf, RemoveIfStep, LeafQuery, ForEach, ForEachStep,
+    CapFn, SetMap, SetMapStep, SortSet, SortedInsert, SortedInsertBy, SortStep,
+    Count, SumCounts, Cardinality,
 };
-pub use inspect::Inspect;
-pub use aliases::{CapSet0, CapSet1, CapSet2, CapSet3, CapSet4};
+pub use inspect::{Inspect, InspectNames, CapSetDisplay, CapToken, CapBit, InspectBits, BitRegistry, to_bits, SetFingerprint, fingerprint, TrieDump, TrieDumpBuf, BucketDump, TRIE_DUMP_CAPACITY, snapshot_diff, CapNames, CapNameBuf, CAP_NAMES_CAPACITY, set_contains};
+#[cfg(feature = "alloc")]
+pub use inspect::from_bits;
+#[cfg(feature = "alloc")]
+pub use dyn_set::{DynCapSet, validate_plugin_caps};
+#[cfg(feature = "serde")]
+pub use inspect::CapSetSerde;
+pub use aliases::{CapSet0, CapSet1, CapSet2, CapSet3, CapSet4, SelectSet, Complement};
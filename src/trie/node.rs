@@ -31,8 +31,41 @@ impl<_Slots_> Default for _Node16_ {
 pub type EmptyNode16;
 
 /// Bucket node for storing hash collisions (linear list)
+///
+/// Holds two or more capabilities whose streams are equal to full depth but
+/// whose identities differ, as `Bucket<A, Bucket<B, Empty>>`. It takes the
+/// place of a `Leaf`; a bucket reduced to one member becomes a `Leaf` again.
 pub struct Bucket<Head, Tail>(PhantomData<(Head, Tail)>);
 
 impl<Head, Tail> Default for Bucket<Head, Tail> {
     fn default() -> Self { Self(PhantomData) }
 }
+
+// =============================================================================
+// Sealing
+// =============================================================================
+
+pub(crate) mod sealed {
+    /// Implemented only by the trie node types above.
+    ///
+    /// The set operations (`InsertAt`, `RemoveAt`, `EvalAt`, `Evaluate`, ...)
+    /// require it, so types outside this crate cannot stand in for a trie node.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not a capability set",
+        label = "not a trie node",
+        note = "capability sets are built with `caps![...]`, `With` and `Without`; only `Empty`, `Leaf`, `Node16` and `Bucket` are trie nodes"
+    )]
+    pub trait TrieNode {}
+}
+
+impl sealed::TrieNode for Empty {}
+
+impl<Cap> sealed::TrieNode for Leaf<Cap> {}
+
+// Exact-match `EvalAt` impl on `&Leaf` (see evaluate.rs)
+impl<Cap> sealed::TrieNode for &Leaf<Cap> {}
+
+#[macros::node16]
+impl<_Slots_> sealed::TrieNode for _Node16_ {}
+
+impl<Head, Tail> sealed::TrieNode for Bucket<Head, Tail> {}
@@ -1,12 +1,17 @@
-//! Set operations on capability tries: Union, Intersect, SupersetOf, SetAnd
+//! Set operations on capability tries: Union, Intersect, Difference, SupersetOf, SetEq, Disjoint, SetAnd, Partition, RemoveIf, ForEach, SetMap, Count
 //!
 //! These traits enable combining and comparing capability sets at the type level.
 
-use crate::primitives::{Present, Absent, Bool};
-use crate::primitives::stream::{StreamEq, DefaultMaxDepth};
-use super::node::{Empty, Leaf, Node16};
-use super::capability::Capability;
-use super::insert::With;
+use core::marker::PhantomData;
+use crate::primitives::{Present, Absent, Bool, BoolAnd};
+use crate::primitives::stream::StreamEq;
+use super::node::{Empty, Leaf, Node16, Bucket};
+use super::node::sealed::TrieNode;
+use super::capability::{Capability, CapabilityIdentityEq, CapabilityOrd, MaxDepthOf};
+use super::insert::{With, Without, InsertAt, Normalize};
+use crate::primitives::stream::{S, Z, D0, Peano, PeanoAdd, PeanoVal, LessT, EqualT, GreaterT};
+use super::evaluate::{Evaluate, EvalAt, Has, HCons, HNil, Superset, ComplementOf};
+use crate::hlist;
 
 // =============================================================================
 // Set Operations Traits
@@ -24,10 +29,37 @@ pub trait SetIntersect<Other> {
     type Out;
 }
 
+/// Set Difference: Capabilities of Self that are not in Other.
+/// Computed by walking Other and removing each of its capabilities from Self.
+pub trait SetDifference<Other> {
+    type Out;
+}
+
 /// SupersetOf: Check if Self contains all capabilities in Other.
 /// Used for downcasting / forgetting extra capabilities.
+#[diagnostic::on_unimplemented(
+    message = "Capability set is not a subset of the allowed set",
+    label = "'{Other}' has capabilities that '{Self}' does not allow",
+    note = "Use `diff!` to see which capabilities fall outside the allowed set."
+)]
 pub trait SupersetOf<Other>: Sized {}
 
+/// Bool-valued superset test: `Present` iff Self contains every capability of Other.
+pub trait IsSuperset<Other> {
+    type Out: Bool;
+}
+
+/// Set equality: `Present` iff both sets have exactly the same members,
+/// regardless of insertion order.
+pub trait SetEq<Other> {
+    type Out: Bool;
+}
+
+/// Disjointness: `Present` iff the two sets share no capability.
+pub trait Disjoint<Other> {
+    type Out: Bool;
+}
+
 /// Structural set intersection (Cap1 & Cap2).
 pub trait SetAnd<Other> {
     type Out;
@@ -82,6 +114,186 @@ where
     type Out = <Self as With<A>>::Out;
 }
 
+// Node16 ∪ Node16 = slot-by-slot union one level down
+#[allow(clippy::type_complexity)]
+impl<
+    R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF,
+    L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF,
+>
+    SetUnion<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>>
+    for Node16<L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF>
+where
+    Self: UnionAt<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>, D0>,
+{
+    type Out = <Self as UnionAt<
+        Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>,
+        D0,
+    >>::Out;
+}
+
+// =============================================================================
+// UnionAt - depth-aware union used below the root
+// =============================================================================
+
+/// Union of two sub-tries that both sit at trie depth `Depth`.
+///
+/// Leaves met inside a slot must be inserted at the slot's depth, not at
+/// the root, so the recursive Node16 union cannot reuse `With` directly.
+pub trait UnionAt<Other, Depth> {
+    type Out;
+}
+
+impl<T, Depth> UnionAt<T, Depth> for Empty {
+    type Out = T;
+}
+
+impl<A, Depth> UnionAt<Empty, Depth> for Leaf<A> {
+    type Out = Leaf<A>;
+}
+
+#[macros::node16]
+impl<Depth, _Slots_> UnionAt<Empty, Depth> for _Node16_ {
+    type Out = Self;
+}
+
+impl<A, B, Depth> UnionAt<Leaf<B>, Depth> for Leaf<A>
+where
+    Leaf<A>: InsertAt<B, Depth>,
+{
+    type Out = <Leaf<A> as InsertAt<B, Depth>>::Out;
+}
+
+#[macros::node16]
+impl<A, Depth, _Slots_> UnionAt<_Node16_, Depth> for Leaf<A>
+where
+    _Node16_: InsertAt<A, Depth>,
+{
+    type Out = <_Node16_ as InsertAt<A, Depth>>::Out;
+}
+
+#[macros::node16]
+impl<A, Depth, _Slots_> UnionAt<Leaf<A>, Depth> for _Node16_
+where
+    Self: InsertAt<A, Depth>,
+{
+    type Out = <Self as InsertAt<A, Depth>>::Out;
+}
+
+#[allow(clippy::type_complexity)]
+impl<
+    Depth,
+    R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF,
+    L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF,
+>
+    UnionAt<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>, Depth>
+    for Node16<L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF>
+where
+    L0: UnionAt<R0, S<Depth>>, L1: UnionAt<R1, S<Depth>>,
+    L2: UnionAt<R2, S<Depth>>, L3: UnionAt<R3, S<Depth>>,
+    L4: UnionAt<R4, S<Depth>>, L5: UnionAt<R5, S<Depth>>,
+    L6: UnionAt<R6, S<Depth>>, L7: UnionAt<R7, S<Depth>>,
+    L8: UnionAt<R8, S<Depth>>, L9: UnionAt<R9, S<Depth>>,
+    LA: UnionAt<RA, S<Depth>>, LB: UnionAt<RB, S<Depth>>,
+    LC: UnionAt<RC, S<Depth>>, LD: UnionAt<RD, S<Depth>>,
+    LE: UnionAt<RE, S<Depth>>, LF: UnionAt<RF, S<Depth>>,
+{
+    type Out = Node16<
+        <L0 as UnionAt<R0, S<Depth>>>::Out, <L1 as UnionAt<R1, S<Depth>>>::Out,
+        <L2 as UnionAt<R2, S<Depth>>>::Out, <L3 as UnionAt<R3, S<Depth>>>::Out,
+        <L4 as UnionAt<R4, S<Depth>>>::Out, <L5 as UnionAt<R5, S<Depth>>>::Out,
+        <L6 as UnionAt<R6, S<Depth>>>::Out, <L7 as UnionAt<R7, S<Depth>>>::Out,
+        <L8 as UnionAt<R8, S<Depth>>>::Out, <L9 as UnionAt<R9, S<Depth>>>::Out,
+        <LA as UnionAt<RA, S<Depth>>>::Out, <LB as UnionAt<RB, S<Depth>>>::Out,
+        <LC as UnionAt<RC, S<Depth>>>::Out, <LD as UnionAt<RD, S<Depth>>>::Out,
+        <LE as UnionAt<RE, S<Depth>>>::Out, <LF as UnionAt<RF, S<Depth>>>::Out,
+    >;
+}
+
+// Bucket ∪ X = every bucket member inserted into X (and X ∪ Bucket likewise)
+impl<H, T, X> SetUnion<X> for Bucket<H, T>
+where
+    Self: BucketInsertAll<X, D0>,
+{
+    type Out = <Self as BucketInsertAll<X, D0>>::Out;
+}
+
+impl<A, H, T> SetUnion<Bucket<H, T>> for Leaf<A>
+where
+    Bucket<H, T>: BucketInsertAll<Self, D0>,
+{
+    type Out = <Bucket<H, T> as BucketInsertAll<Self, D0>>::Out;
+}
+
+#[macros::node16]
+impl<H, T, _Slots_> SetUnion<Bucket<H, T>> for _Node16_
+where
+    Bucket<H, T>: BucketInsertAll<Self, D0>,
+{
+    type Out = <Bucket<H, T> as BucketInsertAll<Self, D0>>::Out;
+}
+
+impl<H, T, X, Depth> UnionAt<X, Depth> for Bucket<H, T>
+where
+    Self: BucketInsertAll<X, Depth>,
+{
+    type Out = <Self as BucketInsertAll<X, Depth>>::Out;
+}
+
+impl<A, H, T, Depth> UnionAt<Bucket<H, T>, Depth> for Leaf<A>
+where
+    Bucket<H, T>: BucketInsertAll<Self, Depth>,
+{
+    type Out = <Bucket<H, T> as BucketInsertAll<Self, Depth>>::Out;
+}
+
+#[macros::node16]
+impl<H, T, Depth, _Slots_> UnionAt<Bucket<H, T>, Depth> for _Node16_
+where
+    Bucket<H, T>: BucketInsertAll<Self, Depth>,
+{
+    type Out = <Bucket<H, T> as BucketInsertAll<Self, Depth>>::Out;
+}
+
+/// Insert every member of a bucket list into `Target` at `Depth`.
+pub trait BucketInsertAll<Target, Depth> {
+    type Out;
+}
+
+impl<Target, Depth> BucketInsertAll<Target, Depth> for Empty {
+    type Out = Target;
+}
+
+impl<H, T, Target, Depth> BucketInsertAll<Target, Depth> for Bucket<H, T>
+where
+    Target: InsertAt<H, Depth>,
+    T: BucketInsertAll<<Target as InsertAt<H, Depth>>::Out, Depth>,
+{
+    type Out = <T as BucketInsertAll<<Target as InsertAt<H, Depth>>::Out, Depth>>::Out;
+}
+
+// =============================================================================
+// SetDifference Implementations
+// =============================================================================
+
+// Covers Empty/Leaf/Node16 on either side: Other is folded leaf by leaf,
+// and each of its capabilities is removed from Self with `Without`.
+impl<T, Other> SetDifference<Other> for T
+where
+    Other: FoldCaps<WithoutStep, T>,
+{
+    type Out = <Other as FoldCaps<WithoutStep, T>>::Out;
+}
+
+/// [`FoldStep`] for [`SetDifference`]: remove each visited capability.
+pub struct WithoutStep;
+
+impl<C, Acc> FoldStep<C, Acc> for WithoutStep
+where
+    Acc: Without<C>,
+{
+    type Out = <Acc as Without<C>>::Out;
+}
+
 // =============================================================================
 // SetIntersect Implementations
 // =============================================================================
@@ -106,25 +318,210 @@ impl<A, B> SetIntersect<Leaf<B>> for Leaf<A>
 where
     A: Capability,
     B: Capability,
-    A::Stream: StreamEq<B::Stream, DefaultMaxDepth>,
-    <A::Stream as StreamEq<B::Stream, DefaultMaxDepth>>::Out: IntersectLeafHelper<A>,
+    A::Stream: StreamEq<B::Stream, MaxDepthOf<A>>,
+    <A::Stream as StreamEq<B::Stream, MaxDepthOf<A>>>::Out: IntersectLeafHelper<A, B>,
+{
+    type Out = <<A::Stream as StreamEq<B::Stream, MaxDepthOf<A>>>::Out as IntersectLeafHelper<A, B>>::Out;
+}
+
+// Leaf<A> ∩ Node16 = Leaf<A> if the node contains A, else Empty
+#[macros::node16]
+impl<A, _Slots_> SetIntersect<_Node16_> for Leaf<A>
+where
+    Self: IntersectAt<_Node16_, D0>,
+{
+    type Out = <Self as IntersectAt<_Node16_, D0>>::Out;
+}
+
+// Node16 ∩ Leaf<B> = Leaf<B> if the node contains B, else Empty
+#[macros::node16]
+impl<B, _Slots_> SetIntersect<Leaf<B>> for _Node16_
+where
+    Self: IntersectAt<Leaf<B>, D0>,
+{
+    type Out = <Self as IntersectAt<Leaf<B>, D0>>::Out;
+}
+
+// Node16 ∩ Node16 = slot-by-slot intersection, normalized to the canonical shape
+#[allow(clippy::type_complexity)]
+impl<
+    R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF,
+    L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF,
+>
+    SetIntersect<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>>
+    for Node16<L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF>
+where
+    Self: IntersectAt<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>, D0>,
 {
-    type Out = <<A::Stream as StreamEq<B::Stream, DefaultMaxDepth>>::Out as IntersectLeafHelper<A>>::Out;
+    type Out = <Self as IntersectAt<
+        Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>,
+        D0,
+    >>::Out;
 }
 
 /// Helper for conditional Leaf intersection result
-pub trait IntersectLeafHelper<A> {
+pub trait IntersectLeafHelper<A, B> {
     type Out;
 }
 
-impl<A> IntersectLeafHelper<A> for Present {
-    type Out = Leaf<A>;  // Same capability
+// Same stream: the same capability only if the identities match too
+impl<A, B> IntersectLeafHelper<A, B> for Present
+where
+    A: CapabilityIdentityEq<B>,
+    B: Capability,
+{
+    type Out = <<A as CapabilityIdentityEq<B>>::Out as Bool>::If<Leaf<A>, Empty>;
 }
 
-impl<A> IntersectLeafHelper<A> for Absent {
+impl<A, B> IntersectLeafHelper<A, B> for Absent {
     type Out = Empty;  // Different capabilities
 }
 
+// =============================================================================
+// IntersectAt - depth-aware intersection used below the root
+// =============================================================================
+
+/// Intersection of two sub-tries that both sit at trie depth `Depth`.
+///
+/// Membership of a leaf in a node is routed from `Depth`, and the resulting
+/// Node16 is passed through `Normalize` (all-`Empty` → `Empty`, a lone `Leaf`
+/// → that `Leaf`).
+pub trait IntersectAt<Other, Depth> {
+    type Out;
+}
+
+impl<T, Depth> IntersectAt<T, Depth> for Empty {
+    type Out = Empty;
+}
+
+impl<A, Depth> IntersectAt<Empty, Depth> for Leaf<A> {
+    type Out = Empty;
+}
+
+#[macros::node16]
+impl<Depth, _Slots_> IntersectAt<Empty, Depth> for _Node16_ {
+    type Out = Empty;
+}
+
+impl<A, B, Depth> IntersectAt<Leaf<B>, Depth> for Leaf<A>
+where
+    Leaf<A>: SetIntersect<Leaf<B>>,
+{
+    type Out = <Leaf<A> as SetIntersect<Leaf<B>>>::Out;
+}
+
+#[macros::node16]
+impl<A, Depth, _Slots_> IntersectAt<_Node16_, Depth> for Leaf<A>
+where
+    _Node16_: EvalAt<Has<A>, Depth>,
+{
+    type Out = <<_Node16_ as EvalAt<Has<A>, Depth>>::Out as Bool>::If<Leaf<A>, Empty>;
+}
+
+#[macros::node16]
+impl<B, Depth, _Slots_> IntersectAt<Leaf<B>, Depth> for _Node16_
+where
+    Self: EvalAt<Has<B>, Depth>,
+{
+    type Out = <<Self as EvalAt<Has<B>, Depth>>::Out as Bool>::If<Leaf<B>, Empty>;
+}
+
+#[allow(clippy::type_complexity)]
+impl<
+    Depth,
+    R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF,
+    L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF,
+>
+    IntersectAt<Node16<R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, RA, RB, RC, RD, RE, RF>, Depth>
+    for Node16<L0, L1, L2, L3, L4, L5, L6, L7, L8, L9, LA, LB, LC, LD, LE, LF>
+where
+    L0: IntersectAt<R0, S<Depth>>, L1: IntersectAt<R1, S<Depth>>,
+    L2: IntersectAt<R2, S<Depth>>, L3: IntersectAt<R3, S<Depth>>,
+    L4: IntersectAt<R4, S<Depth>>, L5: IntersectAt<R5, S<Depth>>,
+    L6: IntersectAt<R6, S<Depth>>, L7: IntersectAt<R7, S<Depth>>,
+    L8: IntersectAt<R8, S<Depth>>, L9: IntersectAt<R9, S<Depth>>,
+    LA: IntersectAt<RA, S<Depth>>, LB: IntersectAt<RB, S<Depth>>,
+    LC: IntersectAt<RC, S<Depth>>, LD: IntersectAt<RD, S<Depth>>,
+    LE: IntersectAt<RE, S<Depth>>, LF: IntersectAt<RF, S<Depth>>,
+    Node16<
+        <L0 as IntersectAt<R0, S<Depth>>>::Out, <L1 as IntersectAt<R1, S<Depth>>>::Out,
+        <L2 as IntersectAt<R2, S<Depth>>>::Out, <L3 as IntersectAt<R3, S<Depth>>>::Out,
+        <L4 as IntersectAt<R4, S<Depth>>>::Out, <L5 as IntersectAt<R5, S<Depth>>>::Out,
+        <L6 as IntersectAt<R6, S<Depth>>>::Out, <L7 as IntersectAt<R7, S<Depth>>>::Out,
+        <L8 as IntersectAt<R8, S<Depth>>>::Out, <L9 as IntersectAt<R9, S<Depth>>>::Out,
+        <LA as IntersectAt<RA, S<Depth>>>::Out, <LB as IntersectAt<RB, S<Depth>>>::Out,
+        <LC as IntersectAt<RC, S<Depth>>>::Out, <LD as IntersectAt<RD, S<Depth>>>::Out,
+        <LE as IntersectAt<RE, S<Depth>>>::Out, <LF as IntersectAt<RF, S<Depth>>>::Out,
+    >: Normalize,
+{
+    type Out = <Node16<
+        <L0 as IntersectAt<R0, S<Depth>>>::Out, <L1 as IntersectAt<R1, S<Depth>>>::Out,
+        <L2 as IntersectAt<R2, S<Depth>>>::Out, <L3 as IntersectAt<R3, S<Depth>>>::Out,
+        <L4 as IntersectAt<R4, S<Depth>>>::Out, <L5 as IntersectAt<R5, S<Depth>>>::Out,
+        <L6 as IntersectAt<R6, S<Depth>>>::Out, <L7 as IntersectAt<R7, S<Depth>>>::Out,
+        <L8 as IntersectAt<R8, S<Depth>>>::Out, <L9 as IntersectAt<R9, S<Depth>>>::Out,
+        <LA as IntersectAt<RA, S<Depth>>>::Out, <LB as IntersectAt<RB, S<Depth>>>::Out,
+        <LC as IntersectAt<RC, S<Depth>>>::Out, <LD as IntersectAt<RD, S<Depth>>>::Out,
+        <LE as IntersectAt<RE, S<Depth>>>::Out, <LF as IntersectAt<RF, S<Depth>>>::Out,
+    > as Normalize>::Out;
+}
+
+// =============================================================================
+// Emptiness
+// =============================================================================
+
+/// Type-level emptiness test: `Present` for `Empty`, `Absent` otherwise.
+pub trait IsEmpty {
+    type Out: Bool;
+}
+
+impl IsEmpty for Empty {
+    type Out = Present;
+}
+
+impl<A> IsEmpty for Leaf<A> {
+    type Out = Absent;
+}
+
+impl<H, T> IsEmpty for Bucket<H, T> {
+    type Out = Absent;
+}
+
+#[macros::node16]
+impl<_Slots_> IsEmpty for _Node16_ {
+    type Out = Absent;
+}
+
+/// Replace a Node16 whose slots are all `Empty` with `Empty`.
+pub trait CollapseEmpty {
+    type Out;
+}
+
+#[macros::node16]
+impl<_Slots_> CollapseEmpty for _Node16_
+where
+    hlist![_Slots_]: AllEmpty,
+{
+    type Out = <<hlist![_Slots_] as AllEmpty>::Out as Bool>::If<Empty, Self>;
+}
+
+/// `Present` iff every element of an HList of trie nodes is `Empty`.
+pub trait AllEmpty {
+    type Out: Bool;
+}
+
+impl AllEmpty for HNil {
+    type Out = Present;
+}
+
+impl<H, T> AllEmpty for HCons<H, T>
+where
+    H: IsEmpty,
+    T: AllEmpty,
+{
+    type Out = <<H as IsEmpty>::Out as Bool>::And<<T as AllEmpty>::Out>;
+}
+
 // =============================================================================
 // SupersetOf Implementations
 // =============================================================================
@@ -136,7 +533,6 @@ impl<T> SupersetOf<Empty> for T {}
 impl<A> SupersetOf<Leaf<A>> for Leaf<A> {}
 
 // Node16 is superset of Leaf<A> if it contains A
-use super::evaluate::{Evaluate, Has};
 
 #[macros::node16]
 impl<A, _Slots_> SupersetOf<Leaf<A>> for _Node16_
@@ -145,6 +541,188 @@ where
     Self: Evaluate<Has<A>, Out = Present>,
 {}
 
+// Any set is a superset of a Node16 (or Bucket) if it contains each of its
+// capabilities. A Leaf never holds more than one, so `Leaf: SupersetOf<Node16>`
+// is never satisfied.
+
+#[macros::node16]
+impl<T, _Slots_> SupersetOf<_Node16_> for T
+where
+    T: IsSuperset<_Node16_, Out = Present>,
+{}
+
+impl<T, H, Tail> SupersetOf<Bucket<H, Tail>> for T
+where
+    T: IsSuperset<Bucket<H, Tail>, Out = Present>,
+{}
+
+// =============================================================================
+// IsSuperset / SetEq Implementations
+// =============================================================================
+
+// Other is walked slot by slot; each capability it holds is looked up in Self.
+impl<T, Other> IsSuperset<Other> for T
+where
+    Other: FoldCaps<ContainsStep<T>, Present>,
+    <Other as FoldCaps<ContainsStep<T>, Present>>::Out: Bool,
+{
+    type Out = <Other as FoldCaps<ContainsStep<T>, Present>>::Out;
+}
+
+// Superset<Other> - IsSuperset as a query, for use inside And/Or/Require
+impl<Ctx, Other> Evaluate<Superset<Other>> for Ctx
+where
+    Ctx: TrieNode + IsSuperset<Other>,
+{
+    type Out = <Ctx as IsSuperset<Other>>::Out;
+}
+
+// ComplementOf<Universe, Set> - SetEq against Universe \ Set
+impl<Ctx, Universe, Set> Evaluate<ComplementOf<Universe, Set>> for Ctx
+where
+    Universe: SetDifference<Set>,
+    Ctx: TrieNode + SetEq<<Universe as SetDifference<Set>>::Out>,
+{
+    type Out = <Ctx as SetEq<<Universe as SetDifference<Set>>::Out>>::Out;
+}
+
+/// [`FoldStep`] for [`IsSuperset`]: AND in the membership of each visited capability.
+pub struct ContainsStep<Set>(PhantomData<Set>);
+
+impl<Set, C, Acc> FoldStep<C, Acc> for ContainsStep<Set>
+where
+    Acc: Bool,
+    Set: Evaluate<Has<C>>,
+{
+    type Out = Acc::And<<Set as Evaluate<Has<C>>>::Out>;
+}
+
+impl<L, R> SetEq<R> for L
+where
+    L: IsSuperset<R>,
+    R: IsSuperset<L>,
+{
+    type Out = <<L as IsSuperset<R>>::Out as Bool>::And<<R as IsSuperset<L>>::Out>;
+}
+
+/// Assertion form of [`SetEq`], used by `caps_eq!`.
+#[diagnostic::on_unimplemented(
+    message = "Capability sets are not equal",
+    label = "'{Self}' and '{Other}' have different members",
+    note = "Compare the two sets with `diff!` to see which capabilities drifted."
+)]
+pub trait AssertSetEq<Other> {}
+
+impl<L, R> AssertSetEq<R> for L where L: SetEq<R, Out = Present> {}
+
+// =============================================================================
+// Disjoint Implementations
+// =============================================================================
+
+impl<L, R> Disjoint<R> for L
+where
+    L: SetIntersect<R>,
+    <L as SetIntersect<R>>::Out: IsEmpty,
+{
+    type Out = <<L as SetIntersect<R>>::Out as IsEmpty>::Out;
+}
+
+/// Require that two sets share no capability.
+///
+/// ```
+/// use tola_caps::prelude::*;
+/// use tola_caps::AssertDisjoint;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// fn merge<L: AssertDisjoint<R>, R>() {}
+/// merge::<caps![A], caps![B]>();
+/// ```
+///
+/// ```compile_fail
+/// use tola_caps::prelude::*;
+/// use tola_caps::AssertDisjoint;
+/// #[derive(Capability)] struct A;
+/// #[derive(Capability)] struct B;
+///
+/// fn merge<L: AssertDisjoint<R>, R>() {}
+/// merge::<caps![A, B], caps![B]>();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "Capability sets overlap",
+    label = "'{Self}' and '{Other}' share at least one capability",
+    note = "Use `intersect!` to see the shared capabilities."
+)]
+pub trait AssertDisjoint<Other> {}
+
+impl<L, R> AssertDisjoint<R> for L where L: Disjoint<R, Out = Present> {}
+
+// Bucket ∩ X = the bucket members X contains (and X ∩ Bucket likewise)
+impl<H, T, X> SetIntersect<X> for Bucket<H, T>
+where
+    Self: IntersectAt<X, D0>,
+{
+    type Out = <Self as IntersectAt<X, D0>>::Out;
+}
+
+impl<A, H, T> SetIntersect<Bucket<H, T>> for Leaf<A>
+where
+    Self: IntersectAt<Bucket<H, T>, D0>,
+{
+    type Out = <Self as IntersectAt<Bucket<H, T>, D0>>::Out;
+}
+
+#[macros::node16]
+impl<H, T, _Slots_> SetIntersect<Bucket<H, T>> for _Node16_
+where
+    Bucket<H, T>: IntersectAt<Self, D0>,
+{
+    type Out = <Bucket<H, T> as IntersectAt<Self, D0>>::Out;
+}
+
+impl<H, T, X, Depth> IntersectAt<X, Depth> for Bucket<H, T>
+where
+    Self: BucketRetain<X, Depth>,
+    <Self as BucketRetain<X, Depth>>::Out: Normalize,
+{
+    type Out = <<Self as BucketRetain<X, Depth>>::Out as Normalize>::Out;
+}
+
+impl<A, H, T, Depth> IntersectAt<Bucket<H, T>, Depth> for Leaf<A>
+where
+    Bucket<H, T>: EvalAt<Has<A>, Depth>,
+{
+    type Out = <<Bucket<H, T> as EvalAt<Has<A>, Depth>>::Out as Bool>::If<Leaf<A>, Empty>;
+}
+
+#[macros::node16]
+impl<H, T, Depth, _Slots_> IntersectAt<Bucket<H, T>, Depth> for _Node16_
+where
+    Bucket<H, T>: IntersectAt<Self, Depth>,
+{
+    type Out = <Bucket<H, T> as IntersectAt<Self, Depth>>::Out;
+}
+
+/// Keep the members of a bucket list that `Other` contains at `Depth`.
+pub trait BucketRetain<Other, Depth> {
+    type Out;
+}
+
+impl<Other, Depth> BucketRetain<Other, Depth> for Empty {
+    type Out = Empty;
+}
+
+impl<H, T, Other, Depth> BucketRetain<Other, Depth> for Bucket<H, T>
+where
+    Other: EvalAt<Has<H>, Depth>,
+    T: BucketRetain<Other, Depth>,
+{
+    type Out = <<Other as EvalAt<Has<H>, Depth>>::Out as Bool>::If<
+        Bucket<H, <T as BucketRetain<Other, Depth>>::Out>,
+        <T as BucketRetain<Other, Depth>>::Out,
+    >;
+}
+
 // =============================================================================
 // SetAnd Implementations (Structural Intersection)
 // =============================================================================
@@ -176,9 +754,10 @@ impl<C1, C2> LeafAndDispatch<C1> for Leaf<C2>
 where
     C1: Capability,
     C2: Capability,
-    C1::Stream: StreamEq<C2::Stream, DefaultMaxDepth>,
+    C1::Stream: StreamEq<C2::Stream, MaxDepthOf<C1>>,
+    <C1::Stream as StreamEq<C2::Stream, MaxDepthOf<C1>>>::Out: IntersectLeafHelper<C1, C2>,
 {
-    type Out = <<C1::Stream as StreamEq<C2::Stream, DefaultMaxDepth>>::Out as Bool>::If<Leaf<C1>, Empty>;
+    type Out = <<C1::Stream as StreamEq<C2::Stream, MaxDepthOf<C1>>>::Out as IntersectLeafHelper<C1, C2>>::Out;
 }
 
 // Leaf & Node = Empty
@@ -241,3 +820,338 @@ where
         <LE as SetAnd<RE>>::Out, <LF as SetAnd<RF>>::Out,
     >;
 }
+
+// =============================================================================
+// Leaf Fold (visit every capability in trie order)
+// =============================================================================
+
+/// Type-level predicate over a single capability.
+///
+/// Implement this for a marker type to classify capabilities, e.g. for
+/// [`Partition`].
+pub trait CapPredicate<Cap> {
+    type Out: Bool;
+}
+
+/// One step of a [`FoldCaps`]: combine capability `Cap` into accumulator `Acc`.
+pub trait FoldStep<Cap, Acc> {
+    type Out;
+}
+
+/// Left fold over every capability stored in a trie, in slot order.
+pub trait FoldCaps<F, Acc> {
+    type Out;
+}
+
+/// Fold over an HList of trie slots (used by the Node16 case of [`FoldCaps`]).
+pub trait FoldSlots<F, Acc> {
+    type Out;
+}
+
+impl<F, Acc> FoldCaps<F, Acc> for Empty {
+    type Out = Acc;
+}
+
+impl<F, Acc, C> FoldCaps<F, Acc> for Leaf<C>
+where
+    F: FoldStep<C, Acc>,
+{
+    type Out = <F as FoldStep<C, Acc>>::Out;
+}
+
+impl<F, Acc, H, T> FoldCaps<F, Acc> for Bucket<H, T>
+where
+    F: FoldStep<H, Acc>,
+    T: FoldCaps<F, <F as FoldStep<H, Acc>>::Out>,
+{
+    type Out = <T as FoldCaps<F, <F as FoldStep<H, Acc>>::Out>>::Out;
+}
+
+#[macros::node16]
+impl<F, Acc, _Slots_> FoldCaps<F, Acc> for _Node16_
+where
+    hlist![_Slots_]: FoldSlots<F, Acc>,
+{
+    type Out = <hlist![_Slots_] as FoldSlots<F, Acc>>::Out;
+}
+
+impl<F, Acc> FoldSlots<F, Acc> for HNil {
+    type Out = Acc;
+}
+
+impl<F, Acc, H, T> FoldSlots<F, Acc> for HCons<H, T>
+where
+    H: FoldCaps<F, Acc>,
+    T: FoldSlots<F, <H as FoldCaps<F, Acc>>::Out>,
+{
+    type Out = <T as FoldSlots<F, <H as FoldCaps<F, Acc>>::Out>>::Out;
+}
+
+// =============================================================================
+// Partition
+// =============================================================================
+
+/// Split a set along predicate `P`.
+///
+/// `Matching` holds the capabilities for which `P` is `Present`, `Rest` the
+/// others. Both halves are rebuilt by insertion, so they are canonical sets
+/// (equal to the same members written with `caps![...]`).
+pub trait Partition<P> {
+    type Matching;
+    type Rest;
+}
+
+/// [`FoldStep`] for [`Partition`]: accumulator is `(Matching, Rest)`.
+pub struct PartitionStep<P>(PhantomData<P>);
+
+impl<P, C, M, R> FoldStep<C, (M, R)> for PartitionStep<P>
+where
+    C: Capability,
+    P: CapPredicate<C>,
+    M: With<C>,
+    R: With<C>,
+{
+    type Out = <<P as CapPredicate<C>>::Out as Bool>::If<
+        (<M as With<C>>::Out, R),
+        (M, <R as With<C>>::Out),
+    >;
+}
+
+impl<T, P, M, R> Partition<P> for T
+where
+    T: FoldCaps<PartitionStep<P>, (Empty, Empty), Out = (M, R)>,
+{
+    type Matching = M;
+    type Rest = R;
+}
+
+// =============================================================================
+// RemoveIf
+// =============================================================================
+
+/// Drop every capability for which predicate `P` is `Present`.
+///
+/// The same split as [`Partition`]'s `Rest`, without building the matching
+/// half. To filter by a query instead of a [`CapPredicate`], wrap it in
+/// [`LeafQuery`].
+pub trait RemoveIf<P> {
+    type Out;
+}
+
+/// [`FoldStep`] for [`RemoveIf`]: accumulator is the set of kept capabilities.
+pub struct RemoveIfStep<P>(PhantomData<P>);
+
+impl<P, C, Acc> FoldStep<C, Acc> for RemoveIfStep<P>
+where
+    C: Capability,
+    P: CapPredicate<C>,
+    Acc: With<C>,
+{
+    type Out = <<P as CapPredicate<C>>::Out as Bool>::If<Acc, <Acc as With<C>>::Out>;
+}
+
+impl<T, P> RemoveIf<P> for T
+where
+    T: FoldCaps<RemoveIfStep<P>, Empty>,
+{
+    type Out = <T as FoldCaps<RemoveIfStep<P>, Empty>>::Out;
+}
+
+/// [`CapPredicate`] that evaluates query `Q` against the one-capability
+/// set `Leaf<Cap>`, e.g. `RemoveIf<LeafQuery<Or<CanExec, CanDelete>>>`.
+pub struct LeafQuery<Q>(PhantomData<Q>);
+
+impl<Q, C> CapPredicate<C> for LeafQuery<Q>
+where
+    Leaf<C>: Evaluate<Q>,
+{
+    type Out = <Leaf<C> as Evaluate<Q>>::Out;
+}
+
+// =============================================================================
+// ForEach
+// =============================================================================
+
+/// `Present` iff predicate `P` holds for every capability in the set.
+///
+/// The set-wide counterpart of `All<hlist![...]>`: `P` is applied to each
+/// member rather than to a fixed list. Vacuously `Present` for `Empty`.
+pub trait ForEach<P> {
+    type Out: Bool;
+}
+
+/// [`FoldStep`] for [`ForEach`]: accumulator is the running `Bool`.
+pub struct ForEachStep<P>(PhantomData<P>);
+
+impl<P, C, Acc> FoldStep<C, Acc> for ForEachStep<P>
+where
+    P: CapPredicate<C>,
+    Acc: BoolAnd<<P as CapPredicate<C>>::Out>,
+{
+    type Out = <Acc as BoolAnd<<P as CapPredicate<C>>::Out>>::Out;
+}
+
+impl<T, P, B> ForEach<P> for T
+where
+    T: FoldCaps<ForEachStep<P>, Present, Out = B>,
+    B: Bool,
+{
+    type Out = B;
+}
+
+// =============================================================================
+// SetMap
+// =============================================================================
+
+/// Type-level function from one capability to another, for [`SetMap`].
+pub trait CapFn<In> {
+    type Out: Capability;
+}
+
+/// Apply `F` to every capability and collect the results into a new set.
+///
+/// The output is built by insertion from `Empty`, so it is canonical; members
+/// that `F` maps to the same capability collapse into one.
+pub trait SetMap<F> {
+    type Out;
+}
+
+/// [`FoldStep`] for [`SetMap`]: accumulator is the set built so far.
+pub struct SetMapStep<F>(PhantomData<F>);
+
+impl<F, C, Acc> FoldStep<C, Acc> for SetMapStep<F>
+where
+    F: CapFn<C>,
+    Acc: With<<F as CapFn<C>>::Out>,
+{
+    type Out = <Acc as With<<F as CapFn<C>>::Out>>::Out;
+}
+
+impl<T, F> SetMap<F> for T
+where
+    T: FoldCaps<SetMapStep<F>, Empty>,
+{
+    type Out = <T as FoldCaps<SetMapStep<F>, Empty>>::Out;
+}
+
+// =============================================================================
+// SortSet
+// =============================================================================
+
+/// The members of a set as an HList sorted by [`CapabilityOrd`].
+///
+/// Trie order follows hash routing; this order follows identities (module
+/// path, then name), so [`Inspect`](crate::trie::Inspect) on the result reads
+/// naturally and suits golden files.
+pub trait SortSet {
+    type Out;
+}
+
+/// Insert `C` into an HList already sorted by [`CapabilityOrd`].
+pub trait SortedInsert<C> {
+    type Out;
+}
+
+/// Continue [`SortedInsert`] into `HCons<H, T>` given `C`'s order against `H`.
+pub trait SortedInsertBy<C, H, T> {
+    type Out;
+}
+
+impl<C> SortedInsert<C> for HNil {
+    type Out = HCons<C, HNil>;
+}
+
+impl<C, H, T> SortedInsert<C> for HCons<H, T>
+where
+    C: CapabilityOrd<H>,
+    H: Capability,
+    <C as CapabilityOrd<H>>::Out: SortedInsertBy<C, H, T>,
+{
+    type Out = <<C as CapabilityOrd<H>>::Out as SortedInsertBy<C, H, T>>::Out;
+}
+
+impl<C, H, T> SortedInsertBy<C, H, T> for LessT {
+    type Out = HCons<C, HCons<H, T>>;
+}
+
+impl<C, H, T> SortedInsertBy<C, H, T> for EqualT {
+    type Out = HCons<C, HCons<H, T>>;
+}
+
+impl<C, H, T: SortedInsert<C>> SortedInsertBy<C, H, T> for GreaterT {
+    type Out = HCons<H, <T as SortedInsert<C>>::Out>;
+}
+
+/// [`FoldStep`] for [`SortSet`]: accumulator is the sorted HList so far.
+pub struct SortStep;
+
+impl<C, Acc: SortedInsert<C>> FoldStep<C, Acc> for SortStep {
+    type Out = <Acc as SortedInsert<C>>::Out;
+}
+
+impl<T> SortSet for T
+where
+    T: FoldCaps<SortStep, HNil>,
+{
+    type Out = <T as FoldCaps<SortStep, HNil>>::Out;
+}
+
+// =============================================================================
+// Count
+// =============================================================================
+
+/// Number of capabilities in a set, as a Peano number.
+pub trait Count {
+    type Out: Peano;
+}
+
+impl Count for Empty {
+    type Out = Z;
+}
+
+impl<C> Count for Leaf<C> {
+    type Out = S<Z>;
+}
+
+impl<H, T: Count> Count for Bucket<H, T> {
+    type Out = S<T::Out>;
+}
+
+#[macros::node16]
+impl<_Slots_> Count for _Node16_
+where
+    hlist![_Slots_]: SumCounts,
+{
+    type Out = <hlist![_Slots_] as SumCounts>::Out;
+}
+
+/// Sum of [`Count`] over an HList of trie slots.
+pub trait SumCounts {
+    type Out: Peano;
+}
+
+impl SumCounts for HNil {
+    type Out = Z;
+}
+
+impl<H, T> SumCounts for HCons<H, T>
+where
+    H: Count,
+    T: SumCounts,
+    <H as Count>::Out: PeanoAdd<<T as SumCounts>::Out>,
+{
+    type Out = <<H as Count>::Out as PeanoAdd<<T as SumCounts>::Out>>::Out;
+}
+
+/// Runtime-readable [`Count`] of a set.
+pub trait Cardinality {
+    const N: usize;
+}
+
+impl<T> Cardinality for T
+where
+    T: Count,
+    <T as Count>::Out: PeanoVal,
+{
+    const N: usize = <<T as Count>::Out as PeanoVal>::VAL;
+}
@@ -0,0 +1,105 @@
+//! Capabilities routed by `AltStream`, whose tails alternate between two nibbles.
+
+use tola_caps::prelude::*;
+use std::any::TypeId;
+use tola_caps::primitives::identity::Marker;
+use tola_caps::primitives::stream::{DefaultMaxDepth, StreamEq};
+use tola_caps::{AltStream, Bool, CapabilityPath, Cons, Evaluate, GetTail, X1, X2, X3};
+
+// Share the first nibble, diverge at depth 1.
+pub struct AltA;
+pub struct AltB;
+// Share the first two nibbles, diverge at depth 2.
+pub struct AltC;
+// Same nibbles as AltA, opposite phase.
+pub struct AltD;
+
+tola_caps::impl_capability!(AltA, AltStream<X1, X2>, Marker<AltStream<X1, X2>>);
+tola_caps::impl_capability!(AltB, AltStream<X1, X3>, Marker<AltStream<X1, X3>>);
+tola_caps::impl_capability!(
+    AltC,
+    Cons<X1, Cons<X2, AltStream<X3, X2>>>,
+    Marker<Cons<X1, Cons<X2, AltStream<X3, X2>>>>
+);
+tola_caps::impl_capability!(AltD, AltStream<X2, X1>, Marker<AltStream<X2, X1>>);
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+fn stream_eq<A: Capability, B: Capability>() -> bool
+where
+    A::Stream: StreamEq<B::Stream, DefaultMaxDepth>,
+{
+    <<A::Stream as StreamEq<B::Stream, DefaultMaxDepth>>::Out as Bool>::VALUE
+}
+
+#[test]
+fn test_alt_stream_tail_and_path() {
+    use tola_caps::{S, Z};
+    // Odd depths swap the phase, even depths restore it.
+    assert_eq!(
+        TypeId::of::<<AltStream<X1, X2> as GetTail<S<Z>>>::Out>(),
+        TypeId::of::<AltStream<X2, X1>>()
+    );
+    assert_eq!(
+        TypeId::of::<<AltStream<X1, X2> as GetTail<S<S<Z>>>>::Out>(),
+        TypeId::of::<AltStream<X1, X2>>()
+    );
+    assert_eq!(<AltA as CapabilityPath>::PATH, [1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2]);
+    assert_eq!(<AltD as CapabilityPath>::PATH[0], 2);
+
+    assert!(stream_eq::<AltA, AltA>());
+    assert!(!stream_eq::<AltA, AltB>());
+    assert!(!stream_eq::<AltA, AltC>());
+    assert!(!stream_eq::<AltA, AltD>());
+}
+
+#[test]
+fn test_alt_stream_single() {
+    assert!(has::<caps![AltA], AltA>());
+    assert!(!has::<caps![AltA], AltB>());
+    assert!(!has::<caps![AltA], AltC>());
+    assert!(!has::<caps![AltA], AltD>());
+}
+
+#[test]
+fn test_alt_stream_split_at_depth_1() {
+    type Set = caps![AltA, AltB];
+    assert!(has::<Set, AltA>());
+    assert!(has::<Set, AltB>());
+    assert!(!has::<Set, AltC>());
+    assert!(!has::<Set, AltD>());
+}
+
+#[test]
+fn test_alt_stream_split_at_depth_2() {
+    type Set = caps![AltA, AltC];
+    assert!(has::<Set, AltA>());
+    assert!(has::<Set, AltC>());
+    assert!(!has::<Set, AltB>());
+
+    type All = caps![AltA, AltB, AltC, AltD];
+    assert!(has::<All, AltA>());
+    assert!(has::<All, AltB>());
+    assert!(has::<All, AltC>());
+    assert!(has::<All, AltD>());
+}
+
+#[test]
+fn test_alt_stream_insert_order() {
+    type Fwd = caps![AltA, AltC, AltD];
+    type Rev = caps![AltD, AltC, AltA];
+    assert!(has::<Fwd, AltC>() && has::<Rev, AltC>());
+    assert!(has::<Fwd, AltA>() && has::<Rev, AltA>());
+    assert!(!has::<Fwd, AltB>() && !has::<Rev, AltB>());
+}
+
+#[test]
+fn test_alt_stream_without() {
+    type Set = caps![AltA, AltB, AltC];
+    type Less = <Set as tola_caps::Without<AltB>>::Out;
+    assert!(has::<Less, AltA>());
+    assert!(!has::<Less, AltB>());
+    assert!(has::<Less, AltC>());
+}
@@ -47,11 +47,13 @@ macro_rules! assert_not_caps {
 
 // --- Simple custom traits ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Serializable {
     fn serialize(&self) -> Vec<u8>;
 }
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Deserializable {
     fn deserialize(data: &[u8]) -> Self;
@@ -59,6 +61,7 @@ trait Deserializable {
 
 // --- Super trait (parent-child relationship) ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait SerializableExt: Serializable {
     fn serialize_compressed(&self) -> Vec<u8>;
@@ -66,11 +69,13 @@ trait SerializableExt: Serializable {
 
 // --- Generic parameter traits ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Converter<T> {
     fn convert(&self) -> T;
 }
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Transform<From, To> {
     fn transform(input: From) -> To;
@@ -90,6 +95,7 @@ trait Reference<'a, T: 'a> {
 
 // --- Where clause trait ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Processor<T>
 where
@@ -100,21 +106,46 @@ where
 
 // --- Associated type trait ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait Container {
     type Item;
     fn get_item(&self) -> Self::Item;
 }
 
+// --- Associated const trait ---
+
+#[trait_autocaps]
+trait Limits {
+    const MAX: usize;
+    const MIN: usize = 0;
+    fn clamp(&self, n: usize) -> usize {
+        n.clamp(Self::MIN, Self::MAX)
+    }
+}
+
 // --- Const generic trait ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait FixedBuffer<const N: usize> {
     fn buffer(&self) -> [u8; N];
 }
 
+// --- Generic method trait ---
+
+#[allow(dead_code)]
+#[trait_autocaps]
+trait Encode {
+    fn encode<W: std::io::Write>(&self, w: W) -> std::io::Result<()>;
+    fn encode_all<'a, I>(&self, items: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = &'a u8>;
+}
+
 // --- Multiple where clause constraints ---
 
+#[allow(dead_code)]
 #[trait_autocaps]
 trait MultiConstraint<T, U>
 where
@@ -130,27 +161,32 @@ where
 
 // --- Basic generic types ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct Simple<T> {
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithCloneBound<T: Clone> {
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithMultipleBounds<T: Clone + Send + Sync> {
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct MultiGeneric<T: Clone, U: Send> {
     t_value: T,
     u_value: U,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithWhereClause<T>
 where
@@ -159,17 +195,20 @@ where
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithLifetime<'a, T: Clone + 'a> {
     reference: &'a T,
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithConstGeneric<T: Clone, const N: usize> {
     array: [T; N],
 }
 
+#[allow(dead_code)]
 #[derive(Copy, Clone, Debug, AutoCaps)]
 struct CopyGeneric<T: Copy> {
     value: T,
@@ -177,16 +216,19 @@ struct CopyGeneric<T: Copy> {
 
 // --- Negative test types (missing derives) ---
 
+#[allow(dead_code)]
 #[derive(Debug, AutoCaps)] // NO Clone!
 struct NoCloneGeneric<T> {
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, AutoCaps)] // NO Debug!
 struct NoDebugGeneric<T: Clone> {
     value: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithNonSend<T> {
     value: T,
@@ -194,6 +236,7 @@ struct WithNonSend<T> {
 
 // --- Custom trait implementation types ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct ImplementsSerializable<T> {
     data: T,
@@ -205,11 +248,13 @@ impl<T> Serializable for ImplementsSerializable<T> {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct NoSerializable<T> {
     data: T,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct ConditionalSerializable<T> {
     data: T,
@@ -223,6 +268,7 @@ impl<T: Serializable> Serializable for ConditionalSerializable<T> {
 
 // --- Super trait implementation ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct ExtendedSerializer<T> {
     data: T,
@@ -242,6 +288,7 @@ impl<T> SerializableExt for ExtendedSerializer<T> {
 
 // --- Generic trait implementations ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct DualConverter<A, B> {
     a: A,
@@ -282,6 +329,7 @@ impl<'a, T: 'a> Reference<'a, T> for BorrowableWrapper<'a, T> {
 
 // --- Where clause trait implementations ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct ComplexProcessor<T, U>
 where
@@ -304,6 +352,7 @@ where
 
 // --- Associated type trait implementations ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct VecContainer<T> {
     items: Vec<T>,
@@ -316,8 +365,15 @@ impl<T: Clone> Container for VecContainer<T> {
     }
 }
 
+// --- Associated const trait implementations ---
+
+impl<T> Limits for VecContainer<T> {
+    const MAX: usize = 16;
+}
+
 // --- Const generic trait implementations ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct FixedArray<T, const N: usize> {
     data: [T; N],
@@ -332,18 +388,35 @@ where
     }
 }
 
+// --- Generic method trait implementations ---
+
+impl<T> Encode for VecContainer<T> {
+    fn encode<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        w.write_all(&[self.items.len() as u8])
+    }
+    fn encode_all<'a, I>(&self, items: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = &'a u8>,
+    {
+        items.into_iter().copied().collect()
+    }
+}
+
 // --- Nested generic types ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct NestedOption<T> {
     value: Option<T>,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct NestedVec<T> {
     items: Vec<Option<T>>,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct DeeplyNested<T> {
     data: Vec<Option<Box<T>>>,
@@ -358,6 +431,7 @@ struct EmptyGeneric<T> {
 
 // --- Default type parameter ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct WithDefault<T = String> {
     value: T,
@@ -365,6 +439,7 @@ struct WithDefault<T = String> {
 
 // --- Multiple where clause type ---
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, AutoCaps)]
 struct MultiWhereType<T, U>
 where
@@ -801,6 +876,27 @@ mod associated_type_tests {
     }
 }
 
+/// Tests for associated const trait detection
+mod associated_const_tests {
+    use super::*;
+
+    #[test]
+    fn limits_trait() {
+        assert_caps!(VecContainer<i32>: Limits);
+        assert_caps!(VecContainer<i32>: Limits & Container);
+        let detected = __Detect_Limits::<VecContainer<u8>>::IS_LIMITS;
+        assert!(detected);
+        assert_eq!(VecContainer { items: vec![0u8] }.clamp(100), 16);
+    }
+
+    #[test]
+    fn limits_trait_negative() {
+        assert_not_caps!(Simple<u8>: Limits);
+        let detected = __Detect_Limits::<Simple<u8>>::IS_LIMITS;
+        assert!(!detected);
+    }
+}
+
 /// Tests for const generic trait detection
 mod const_generic_trait_tests {
     use super::*;
@@ -830,6 +926,32 @@ mod const_generic_trait_tests {
     }
 }
 
+/// Tests for traits whose methods are generic
+mod generic_method_trait_tests {
+    use super::*;
+
+    #[test]
+    fn encode_trait() {
+        assert_caps!(VecContainer<i32>: Encode);
+        assert_caps!(VecContainer<String>: Encode & Container);
+        let detected = __Detect_Encode::<VecContainer<u8>>::IS_ENCODE;
+        assert!(detected);
+    }
+
+    #[test]
+    fn encode_trait_negative() {
+        assert_not_caps!(Simple<u8>: Encode);
+        let detected = __Detect_Encode::<Simple<u8>>::IS_ENCODE;
+        assert!(!detected);
+    }
+
+    #[test]
+    fn encode_marker_is_capability() {
+        type Set = caps![IsEncode];
+        assert_eq!(<Set as InspectNames>::names(), ["IsEncode"]);
+    }
+}
+
 /// Tests for complex boolean expressions
 mod complex_expression_tests {
     use super::*;
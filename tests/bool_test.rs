@@ -0,0 +1,47 @@
+//! Type-level booleans and their const-fn view.
+
+use core::any::TypeId;
+use tola_caps::{Absent, Bool, BoolAnd, BoolNot, BoolOr, ConstBool, Present};
+
+fn same<A: 'static, B: 'static>() -> bool {
+    TypeId::of::<A>() == TypeId::of::<B>()
+}
+
+#[test]
+fn test_if_selects_branch() {
+    assert!(same::<<Present as Bool>::If<u8, u16>, u8>());
+    assert!(same::<<Absent as Bool>::If<u8, u16>, u16>());
+    assert!(same::<tola_caps::primitives::bool::If<true, u8, u16>, u8>());
+
+    let x: <Present as Bool>::If<u8, u16> = 7u8;
+    assert_eq!(x, 7);
+}
+
+#[test]
+fn test_const_bool_ops() {
+    const AND: bool = ConstBool::<Present>::and(true);
+    const OR: bool = ConstBool::<Absent>::or(false);
+    const XOR: bool = ConstBool::<Present>::xor(true);
+    const NOT: bool = ConstBool::<Absent>::not();
+    let (and, or, xor, not) = (AND, OR, XOR, NOT);
+    assert!(and && !or && !xor && not);
+
+    assert!(!ConstBool::<Absent>::and(true));
+    assert!(ConstBool::<Absent>::or(true));
+    assert!(ConstBool::<Absent>::xor(true));
+    assert!(!ConstBool::<Present>::not());
+}
+
+#[test]
+fn test_const_bool_of_type_ops() {
+    type Both = <Present as BoolAnd<Absent>>::Out;
+    type Either = <Present as BoolOr<Absent>>::Out;
+    type Neither = <Present as BoolNot>::Out;
+    let values = [ConstBool::<Both>::VALUE, ConstBool::<Either>::VALUE, ConstBool::<Neither>::VALUE];
+    assert_eq!(values, [false, true, false]);
+
+    const LIMIT: usize = ConstBool::<Either>::select(64, 16);
+    let limit = LIMIT;
+    assert_eq!(limit, 64);
+    assert_eq!(ConstBool::<Both>::select("on", "off"), "off");
+}
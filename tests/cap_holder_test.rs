@@ -0,0 +1,80 @@
+//! Tests for #[cap_holder] / #[derive(CapHolder)]
+
+use core::marker::PhantomData;
+use std::any::TypeId;
+use tola_caps::prelude::*;
+use tola_caps::{cap_holder, CapHolder, Empty};
+
+#[derive(Capability)]
+struct Parsed;
+
+#[derive(Capability)]
+struct Validated;
+
+fn same<A: 'static, B: 'static>() -> bool {
+    TypeId::of::<A>() == TypeId::of::<B>()
+}
+
+#[cap_holder]
+struct Doc;
+
+#[cap_holder]
+struct Page {
+    title: &'static str,
+}
+
+#[cap_holder]
+struct Pair<T>(T, u8);
+
+#[derive(CapHolder)]
+struct Manual<C = Empty> {
+    id: u32,
+    marker: PhantomData<C>,
+}
+
+#[test]
+fn test_unit_struct_gains_param() {
+    let doc: Doc = Doc { _caps: PhantomData };
+    let doc = doc.with_cap::<Parsed>().with_cap::<Validated>();
+    let _: Doc<caps![Parsed, Validated]> = doc;
+    assert!(same::<Doc, Doc<Empty>>());
+}
+
+#[test]
+fn test_builder_threads_caps() {
+    let page: Page = Page { title: "intro", _caps: PhantomData };
+    let page: Page<caps![Parsed]> = page.with_cap::<Parsed>();
+    let page: Page<caps![Parsed, Validated]> = page.with_cap::<Validated>();
+    assert_eq!(page.title, "intro");
+    // Adding a capability already present leaves the set unchanged.
+    let page: Page<caps![Parsed, Validated]> = page.with_cap::<Parsed>();
+    let page: Page<Empty> = page.with_caps();
+    assert_eq!(page.title, "intro");
+}
+
+#[test]
+fn test_try_with_cap() {
+    let page: Page = Page { title: "intro", _caps: PhantomData };
+    let page: Page<caps![Parsed]> = page.try_with_cap::<Parsed>();
+    let page: Page<caps![Parsed]> = page.try_with_cap::<Parsed>();
+    let page: Page<caps![Parsed, Validated]> = page.try_with_cap::<Validated>();
+    assert_eq!(page.title, "intro");
+
+    let m = Manual { id: 7, marker: PhantomData::<caps![Validated]> };
+    let m: Manual<caps![Validated]> = m.try_with_cap::<Validated>();
+    assert_eq!(m.id, 7);
+}
+
+#[test]
+fn test_tuple_and_generic_struct() {
+    let pair: Pair<&str> = Pair("a", 1, PhantomData);
+    let pair: Pair<&str, caps![Validated]> = pair.with_cap::<Validated>();
+    assert_eq!((pair.0, pair.1), ("a", 1));
+}
+
+#[test]
+fn test_derive_uses_existing_field() {
+    let m = Manual { id: 7, marker: PhantomData::<Empty> };
+    let m: Manual<caps![Parsed]> = m.with_cap::<Parsed>();
+    assert_eq!(m.id, 7);
+}
@@ -0,0 +1,57 @@
+//! Capabilities generated from existing traits.
+
+use tola_caps::prelude::*;
+use tola_caps::{capability_from_trait, And, Has, InspectNames};
+
+trait Persist {}
+trait Audit {}
+
+struct Record;
+impl Persist for Record {}
+impl Audit for Record {}
+
+struct Scratch;
+impl Persist for Scratch {}
+
+capability_from_trait!(Persist);
+capability_from_trait!(Audit);
+
+mod io_caps {
+    use tola_caps::capability_from_trait;
+    capability_from_trait!(std::io::Write);
+}
+
+#[derive(Capability)]
+struct CanRead;
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+fn cap_name<T: HasPersistCap + ?Sized>() -> &'static str {
+    <T::Cap as Capability>::NAME
+}
+
+#[test]
+fn test_generated_cap_in_caps() {
+    type Set = caps![PersistCap, CanRead];
+    assert_eq!(<Set as InspectNames>::COUNT, 2);
+    assert!(has::<Set, Has<PersistCap>>());
+    assert!(!has::<Set, Has<AuditCap>>());
+    assert!(has::<caps![PersistCap, AuditCap], And<Has<PersistCap>, Has<AuditCap>>>());
+}
+
+#[test]
+fn test_generated_caps_are_distinct() {
+    type Set = caps![AuditCap, io_caps::WriteCap];
+    assert!(has::<Set, Has<io_caps::WriteCap>>());
+    assert!(!has::<Set, Has<PersistCap>>());
+    assert_eq!(<io_caps::WriteCap as Capability>::NAME, "WriteCap");
+}
+
+#[test]
+fn test_bridge_names_cap() {
+    assert_eq!(cap_name::<Record>(), "PersistCap");
+    assert_eq!(cap_name::<Scratch>(), "PersistCap");
+    let _: <Record as HasAuditCap>::Cap = AuditCap;
+}
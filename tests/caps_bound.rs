@@ -136,6 +136,38 @@ fn test_without_macro() {
     verify_removal(w_b);
 }
 
+// A bare `Doc` return carries the input set through
+#[caps_bound(transparent, requires = CapA)]
+fn id(d: Doc) -> Doc {
+    // Generates: fn id<__C>(d: Doc<__C>) -> Doc<__C>
+    d
+}
+
+#[test]
+fn test_transparent_return() {
+    type Set = caps![CapA, CapB];
+    let out: Doc<Set> = id(Wrapper::<Set>(PhantomData));
+    verify_arbitrary_positional(out);
+}
+
+// Custom carrier types
+struct Node<C>(PhantomData<C>);
+struct Artifact<C>(PhantomData<C>);
+
+#[caps_bound(CapA, transparent, carrier = [Node, Artifact])]
+fn build(node: Node) -> Artifact {
+    // Generates: fn build<__C>(node: Node<__C>) -> Artifact<__C>
+    let _ = node;
+    Artifact(PhantomData)
+}
+
+#[test]
+fn test_transparent_custom_carrier() {
+    type Set = caps![CapA];
+    let out: Artifact<Set> = build(Node::<Set>(PhantomData));
+    let _ = out;
+}
+
 // Arbitrary positional arguments test
 #[caps_bound(CapA, CapB, !PrivateCap, transparent)]
 fn verify_arbitrary_positional(doc: Doc) {
@@ -148,3 +180,191 @@ fn test_arbitrary_positional_support() {
     let doc = Wrapper::<Set>(PhantomData);
     verify_arbitrary_positional(doc);
 }
+
+// =============================================================================
+// 5. Boolean Operators
+// =============================================================================
+
+#[derive(Capability)] struct MemBackend;
+#[derive(Capability)] struct DiskBackend;
+
+#[caps_bound(requires = MemBackend ^ DiskBackend)]
+fn open_store<C>(_: PhantomData<C>) {}
+
+#[test]
+fn test_xor_requirement() {
+    open_store(PhantomData::<caps![MemBackend]>);
+    open_store(PhantomData::<caps![DiskBackend, PublicCap]>);
+    // caps![MemBackend, DiskBackend] and caps![] are rejected at compile time.
+}
+
+#[caps_bound(requires = CapA => CapB)]
+fn dependent<C>(_: PhantomData<C>) {}
+
+#[test]
+fn test_implies_requirement() {
+    dependent(PhantomData::<caps![CapA, CapB]>);
+    dependent(PhantomData::<caps![CapB]>);
+    dependent(PhantomData::<caps![PublicCap]>);
+    // caps![CapA] alone is rejected at compile time.
+}
+
+// =============================================================================
+// 6. Trait Methods
+// =============================================================================
+
+trait Store {
+    fn name(&self) -> &'static str;
+
+    // No type parameter of its own: `__C` is injected
+    #[caps_bound(requires = CapA & !PrivateCap)]
+    fn guarded_name(&self) -> &'static str {
+        self.name()
+    }
+
+    // Bound lands on the method's own parameter
+    #[caps_bound(requires = CapB)]
+    fn tagged<C>(&self, _caps: PhantomData<C>) -> &'static str {
+        self.name()
+    }
+}
+
+struct MemStore;
+
+impl Store for MemStore {
+    fn name(&self) -> &'static str {
+        "mem"
+    }
+}
+
+#[test]
+fn test_trait_method_bound() {
+    let store = MemStore;
+    assert_eq!(store.guarded_name::<caps![CapA]>(), "mem");
+    assert_eq!(store.tagged(PhantomData::<caps![CapA, CapB]>), "mem");
+    // store.guarded_name::<caps![CapB]>() and store.tagged(PhantomData::<caps![CapA]>)
+    // are rejected at compile time.
+}
+
+// =============================================================================
+// 7. Multiple Targets
+// =============================================================================
+
+#[caps_bound(requires = CapA, target = [L, R])]
+fn merge<L, R>(_: Doc<L>, _: Doc<R>) {}
+
+#[caps_bound(requires = CapB, target = L, target = R)]
+fn merge_repeated<L, R>(_: Doc<L>, _: Doc<R>) {}
+
+// Each `T: expr` bounds only its own generic
+#[caps_bound(L: CapA, R: CapB)]
+fn merge_split<L, R>(_: Doc<L>, _: Doc<R>) {}
+
+// Attribute-wide `requires` follows `target`, not the `T: expr` generics
+#[caps_bound(L: CapA, requires = CapB, target = R)]
+fn merge_mixed<L, R>(_: Doc<L>, _: Doc<R>) {}
+
+#[test]
+fn test_per_param_requirements() {
+    merge_split(Wrapper::<caps![CapA]>(PhantomData), Wrapper::<caps![CapB]>(PhantomData));
+    merge_mixed(Wrapper::<caps![CapA]>(PhantomData), Wrapper::<caps![CapB]>(PhantomData));
+    // Swapping the sides is rejected at compile time.
+}
+
+#[test]
+fn test_multiple_targets() {
+    merge(Wrapper::<caps![CapA]>(PhantomData), Wrapper::<caps![CapA, CapB]>(PhantomData));
+    merge_repeated(Wrapper::<caps![CapB]>(PhantomData), Wrapper::<caps![CapB]>(PhantomData));
+    // Missing CapA on either side is rejected: see tests/ui/caps_bound_targets.rs
+}
+
+// =============================================================================
+// 8. Explicit Conflict Semantics
+// =============================================================================
+
+// `conflicts = A | B`: neither may be present
+#[caps_bound(requires = PublicCap, conflicts = CapA | CapB)]
+fn neither<C>(_: PhantomData<C>) {}
+
+// Not both at once
+#[caps_bound(requires = PublicCap, conflicts_any = [CapA, CapB])]
+fn not_both<C>(_: PhantomData<C>) {}
+
+// All absent (same as `conflicts = CapA | CapB`)
+#[caps_bound(requires = PublicCap, conflicts_all = [CapA, CapB])]
+fn none_of<C>(_: PhantomData<C>) {}
+
+// A single operand works for both forms
+#[caps_bound(requires = PublicCap, conflicts_all = CapA)]
+fn without_a<C>(_: PhantomData<C>) {}
+
+#[test]
+fn test_conflict_variants() {
+    neither(PhantomData::<caps![PublicCap]>);
+
+    not_both(PhantomData::<caps![PublicCap]>);
+    not_both(PhantomData::<caps![PublicCap, CapA]>);
+    not_both(PhantomData::<caps![PublicCap, CapB]>);
+
+    none_of(PhantomData::<caps![PublicCap]>);
+    without_a(PhantomData::<caps![PublicCap, CapB]>);
+    // Rejections: see tests/ui/caps_bound_conflicts.rs
+}
+
+// =============================================================================
+// 9. Set Queries
+// =============================================================================
+
+type AdminCaps = caps![CapA, CapB];
+
+#[caps_bound(requires = tola_caps::Has<PublicCap> & Superset<AdminCaps>)]
+fn admin_only<C>(_: PhantomData<C>) {}
+
+#[caps_bound(requires = Superset<AdminCaps> | PublicCap)]
+fn admin_or_public<C>(_: PhantomData<C>) {}
+
+fn is_superset<S: Evaluate<tola_caps::Superset<Q>>, Q>() -> bool {
+    <S as Evaluate<tola_caps::Superset<Q>>>::RESULT
+}
+
+#[test]
+fn test_superset_query() {
+    admin_only(PhantomData::<caps![PublicCap, CapA, CapB]>);
+    admin_or_public(PhantomData::<caps![CapB, CapA]>);
+    admin_or_public(PhantomData::<caps![PublicCap]>);
+
+    assert!(is_superset::<caps![CapA, CapB, PublicCap], AdminCaps>());
+    assert!(!is_superset::<caps![CapA, PublicCap], AdminCaps>());
+    assert!(is_superset::<caps![CapA], tola_caps::Empty>());
+}
+
+type AllCaps = caps![PublicCap, CapA, CapB];
+
+#[caps_bound(requires = ComplementOf<AllCaps, AdminCaps>)]
+fn everything_but_admin<C>(_: PhantomData<C>) {}
+
+#[test]
+fn test_complement_query() {
+    everything_but_admin(PhantomData::<caps![PublicCap]>);
+}
+
+// A local `Superset` / `ComplementOf` shadows the prelude one, as for any
+// other type.
+mod local_queries {
+    use super::*;
+
+    type Superset<Cap> = tola_caps::Has<Cap>;
+    type ComplementOf<A, B> = tola_caps::And<tola_caps::Has<A>, tola_caps::Has<B>>;
+
+    #[caps_bound(requires = Superset<CapA>)]
+    fn has_a<C>(_: PhantomData<C>) {}
+
+    #[caps_bound(requires = ComplementOf<CapA, CapB>)]
+    fn has_both<C>(_: PhantomData<C>) {}
+
+    #[test]
+    fn test_local_queries_not_rewritten() {
+        has_a(PhantomData::<caps![CapA]>);
+        has_both(PhantomData::<caps![CapA, CapB]>);
+    }
+}
@@ -58,8 +58,14 @@
 //!
 //! 1. Add the trait as a bound: `T: CustomTrait`
 //! 2. Register custom capability in AutoCaps (requires extending the trait)
+//!
+//! ### 3. Unbounded Generic T
+//!
+//! Without a `T: AutoCaps` bound, `caps_check!(T: Clone)` falls back to the
+//! Probe and answers `false`. `caps_check_generic!` rejects that case at
+//! compile time (see `tests/ui/caps_check_generic_unbounded.rs`).
 
-use tola_caps::caps_check;
+use tola_caps::{caps_check, caps_check_generic};
 use tola_caps::std_caps::AutoCaps;
 
 #[allow(dead_code)]
@@ -201,3 +207,18 @@ fn test_generic_check_trusts_autocaps() {
         "Generic check trusts AutoCaps, which lies"
     );
 }
+
+#[test]
+fn test_caps_check_generic_bounded() {
+    fn check<T: AutoCaps>() -> bool {
+        caps_check_generic!(T: Clone & !Copy)
+    }
+    fn check_pair<A: AutoCaps, B: AutoCaps>() -> bool {
+        caps_check_generic!(A: Clone, B: Copy)
+    }
+
+    assert!(check::<String>());
+    assert!(!check::<i32>());
+    assert!(check_pair::<String, i32>());
+    assert!(!check_pair::<String, String>());
+}
@@ -79,6 +79,15 @@ fn test_or_clone_copy() {
     assert!(caps_check!(i32: Clone | Copy));
 }
 
+#[test]
+fn test_xor_clone_copy() {
+    // String is Clone only, so exactly one holds
+    assert!(caps_check!(String: Clone ^ Copy));
+
+    // i32 is both, so Clone ^ Copy is false
+    assert!(!caps_check!(i32: Clone ^ Copy));
+}
+
 // =============================================================================
 // Negation Tests
 // =============================================================================
@@ -164,6 +173,121 @@ fn test_generic_with_complex_expr() {
     assert!(has_all_basic::<i32>());
 }
 
+// =============================================================================
+// References - &T and &mut T
+// =============================================================================
+
+#[test]
+fn test_references() {
+    assert!(caps_check!(&i32: Copy));
+    assert!(!caps_check!(&mut i32: Copy));
+    assert!(caps_check!(&String: Clone & Copy & Debug));
+    assert!(!caps_check!(&mut String: Clone));
+    assert!(caps_check!(&str: Clone));
+}
+
+#[test]
+fn test_references_in_generic_context() {
+    fn ref_copy_debug<T: AutoCaps>() -> bool {
+        caps_check!(&T: Copy & Debug)
+    }
+
+    fn ref_send<T: AutoCaps>() -> bool {
+        caps_check!(&T: Send)
+    }
+
+    fn mut_ref_send_not_clone<T: AutoCaps>() -> bool {
+        caps_check!(&mut T: Send & !Clone)
+    }
+
+    assert!(ref_copy_debug::<String>());
+    assert!(ref_send::<i32>());
+    // &Cell<i32> is not Send because Cell<i32> is not Sync
+    assert!(!ref_send::<std::cell::Cell<i32>>());
+    assert!(mut_ref_send_not_clone::<String>());
+}
+
+#[test]
+fn test_reference_cap_set() {
+    use tola_caps::detect::{Cap, IsClone, IsCopy, IsDebug, IsSend};
+    use tola_caps::trie::Evaluate;
+
+    fn has<S: Evaluate<Q>, Q>() -> bool {
+        <S as Evaluate<Q>>::RESULT
+    }
+
+    assert!(has::<Cap<&String>, IsCopy>());
+    assert!(has::<Cap<&String>, IsDebug>());
+    assert!(!has::<Cap<&mut String>, IsClone>());
+    assert!(has::<Cap<&mut String>, IsSend>());
+    assert!(has::<Cap<&&mut i32>, IsCopy>());
+}
+
+/// Std-gated traits delegated to the referent agree with `AutoCaps`.
+#[cfg(feature = "std")]
+#[test]
+fn test_reference_cap_set_gated_traits() {
+    use tola_caps::detect::{Cap, IsError, IsIoWrite, IsRead, IsSeek};
+    use tola_caps::trie::Evaluate;
+
+    fn has<S: Evaluate<Q>, Q>() -> bool {
+        <S as Evaluate<Q>>::RESULT
+    }
+
+    type FileRef = &'static mut std::fs::File;
+    const { assert!(<FileRef as AutoCaps>::IS_READ) };
+    assert_eq!(has::<Cap<FileRef>, IsRead>(), <FileRef as AutoCaps>::IS_READ);
+    assert_eq!(has::<Cap<FileRef>, IsIoWrite>(), <FileRef as AutoCaps>::IS_IO_WRITE);
+    assert_eq!(has::<Cap<FileRef>, IsSeek>(), <FileRef as AutoCaps>::IS_SEEK);
+
+    type StringRef = &'static mut String;
+    const { assert!(!<StringRef as AutoCaps>::IS_READ) };
+    assert_eq!(has::<Cap<StringRef>, IsRead>(), <StringRef as AutoCaps>::IS_READ);
+
+    type ErrorRef = &'static std::io::Error;
+    const { assert!(<ErrorRef as AutoCaps>::IS_ERROR) };
+    assert_eq!(has::<Cap<ErrorRef>, IsError>(), <ErrorRef as AutoCaps>::IS_ERROR);
+}
+
+// =============================================================================
+// Tuples - AND of member facts
+// =============================================================================
+
+#[test]
+fn test_tuples() {
+    assert!(caps_check!((i32, String): Clone & Debug & !Copy));
+    assert!(caps_check!((i32, u8, char): Copy & std::hash::Hash));
+    assert!(!caps_check!((i32, std::rc::Rc<u8>): Send));
+    assert!(caps_check!((u8,): Default & Ord));
+}
+
+#[test]
+fn test_tuples_in_generic_context() {
+    fn pair_clone_send<A: AutoCaps, B: AutoCaps>() -> bool {
+        caps_check!((A, B): Clone & Send)
+    }
+
+    assert!(pair_clone_send::<i32, String>());
+    assert!(!pair_clone_send::<i32, std::rc::Rc<u8>>());
+}
+
+#[test]
+fn test_tuple_cap_set() {
+    use tola_caps::detect::{Cap, IsClone, IsCopy, IsDebug, IsSend};
+    use tola_caps::trie::Evaluate;
+
+    fn has<S: Evaluate<Q>, Q>() -> bool {
+        <S as Evaluate<Q>>::RESULT
+    }
+
+    assert!(has::<Cap<(i32, String)>, IsClone>());
+    assert!(!has::<Cap<(i32, String)>, IsCopy>());
+    assert!(has::<Cap<(i32, u8, bool)>, IsCopy>());
+    assert!(has::<Cap<(i32, &String, (u8, char))>, IsDebug>());
+    assert!(!has::<Cap<(i32, &mut u8)>, IsClone>());
+    assert!(has::<Cap<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>, IsSend>());
+}
+
 // =============================================================================
 // Custom Traits - work with concrete types
 // =============================================================================
@@ -187,3 +311,17 @@ fn test_custom_trait_concrete() {
     assert!(caps_check!(String: Clone & MyCustomTrait));
     assert!(!caps_check!(i32: Clone & MyCustomTrait));
 }
+
+// =============================================================================
+// Generic Traits with a concrete argument - `Trait[Arg]`
+// =============================================================================
+
+#[test]
+fn test_generic_trait_with_argument() {
+    assert!(caps_check!(String: From[&str]));
+    assert!(caps_check!(String: From[char] & Clone));
+    assert!(!caps_check!(u8: From[u32]));
+    assert!(caps_check!(u32: From[u8] & !From[u64]));
+    assert!(caps_check!(Vec<u8>: AsRef[[u8]]));
+    assert!(caps_check!(Vec<u8>: core::convert::From[&[u8]]));
+}
@@ -0,0 +1,49 @@
+//! Capabilities parameterized by a const value.
+
+use tola_caps::prelude::*;
+use tola_caps::{Has, InspectNames};
+
+#[derive(Capability)]
+#[capability(values = [4096, 8192])]
+struct BufferSize<const N: usize>;
+
+#[derive(Capability)]
+#[capability(values = [true, false])]
+struct Flag<const ON: bool>;
+
+#[derive(Capability)]
+struct CanRead;
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+#[test]
+fn test_values_are_distinct_capabilities() {
+    type Small = caps![BufferSize<4096>];
+    assert!(has::<Small, Has<BufferSize<4096>>>());
+    assert!(!has::<Small, Has<BufferSize<8192>>>());
+
+    type Large = caps![CanRead, BufferSize<8192>];
+    assert!(has::<Large, Has<BufferSize<8192>>>());
+    assert!(!has::<Large, Has<BufferSize<4096>>>());
+}
+
+#[test]
+fn test_values_coexist() {
+    type Both = caps![BufferSize<4096>, BufferSize<8192>, Flag<true>];
+    assert_eq!(<Both as InspectNames>::COUNT, 3);
+    assert!(has::<Both, Has<BufferSize<4096>>>());
+    assert!(has::<Both, Has<BufferSize<8192>>>());
+    assert!(!has::<Both, Has<Flag<false>>>());
+}
+
+#[test]
+fn test_value_names() {
+    assert_eq!(<BufferSize<4096> as Capability>::NAME, "BufferSize<4096>");
+    assert_eq!(<Flag<false> as Capability>::NAME, "Flag<false>");
+    assert_ne!(
+        tola_caps::fingerprint::<caps![BufferSize<4096>]>(),
+        tola_caps::fingerprint::<caps![BufferSize<8192>]>()
+    );
+}
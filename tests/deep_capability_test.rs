@@ -0,0 +1,87 @@
+//! `#[capability(deep)]`: 32-nibble streams compared at `D32`.
+
+use tola_caps::prelude::*;
+use tola_caps::{CapabilityConfig, CapabilityPath, EvalPath, Has, InspectNames};
+use tola_caps::primitives::stream::D32;
+
+// Same 16 nibbles: indistinguishable at the default depth
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X5 X6 X7 X8 X9 XA XB XC XD XE XF X0")]
+struct ShallowA;
+
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X5 X6 X7 X8 X9 XA XB XC XD XE XF X0")]
+struct ShallowB;
+
+// Same first 16 nibbles, differing at nibble 20
+#[derive(Capability)]
+#[capability(deep, stream = "X1 X2 X3 X4 X5 X6 X7 X8 X9 XA XB XC XD XE XF X0 X0 X0 X0 X0 X1 X0 X0 X0")]
+struct DeepA;
+
+#[derive(Capability)]
+#[capability(deep, stream = "X1 X2 X3 X4 X5 X6 X7 X8 X9 XA XB XC XD XE XF X0 X0 X0 X0 X0 X2 X0 X0 X0")]
+struct DeepB;
+
+#[derive(Capability)]
+#[capability(deep)]
+struct Hashed;
+
+#[derive(Capability)]
+#[capability(deep, namespace = "acme")]
+struct Namespaced;
+
+#[derive(Capability)]
+struct CanRead;
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+fn same_type<A: 'static, B: 'static>() -> bool {
+    core::any::TypeId::of::<A>() == core::any::TypeId::of::<B>()
+}
+
+#[test]
+fn test_shallow_streams_collide() {
+    // Identity still tells them apart, but only by sharing a Bucket.
+    assert!(!has::<caps![ShallowA], Has<ShallowB>>());
+    assert_eq!(<caps![ShallowA, ShallowB] as InspectNames>::describe(), "bucket(n=2)");
+    assert_eq!(<caps![DeepA, DeepB] as InspectNames>::describe(), "node(n=2)");
+}
+
+#[test]
+fn test_deep_streams_distinguished() {
+    assert!(has::<caps![DeepA], Has<DeepA>>());
+    assert!(!has::<caps![DeepA], Has<DeepB>>());
+    assert!(!has::<caps![DeepB], Has<DeepA>>());
+
+    type Both = caps![DeepA, DeepB, CanRead];
+    assert_eq!(<Both as InspectNames>::COUNT, 3);
+    assert!(has::<Both, Has<DeepA>>());
+    assert!(has::<Both, Has<DeepB>>());
+}
+
+#[test]
+fn test_deep_depth() {
+    assert!(same_type::<<DeepA as CapabilityConfig>::MaxDepth, D32>());
+    assert!(same_type::<<Hashed as CapabilityConfig>::MaxDepth, D32>());
+    assert!(same_type::<<Namespaced as CapabilityConfig>::MaxDepth, D32>());
+}
+
+#[test]
+fn test_deep_eval_path() {
+    let steps = <DeepA as EvalPath>::STEPS;
+    assert_eq!(steps.len(), 32);
+    assert_eq!(steps[..16], <DeepA as CapabilityPath>::PATH);
+    assert_eq!(steps[20], 0x1);
+    assert_eq!(<DeepB as EvalPath>::STEPS[20], 0x2);
+}
+
+#[test]
+fn test_hashed_deep_usable() {
+    type Set = caps![Hashed, Namespaced, CanRead];
+    assert_eq!(<Set as InspectNames>::COUNT, 3);
+    assert!(has::<Set, Has<Hashed>>());
+    assert!(has::<Set, Has<Namespaced>>());
+    assert!(!has::<caps![CanRead], Has<Hashed>>());
+}
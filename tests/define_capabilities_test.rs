@@ -0,0 +1,184 @@
+//! Tests for define_capabilities!
+
+#![allow(clippy::extra_unused_type_parameters)]
+
+use tola_caps::prelude::*;
+use tola_caps::define_capabilities;
+
+define_capabilities! {
+    LinksChecked => "Links have been checked",
+    LinksResolved => "Links have been resolved" requires LinksChecked,
+    AssetsBundled => "Assets have been bundled",
+    Published => "Site has been published" requires (LinksResolved, AssetsBundled),
+}
+
+fn has_checked<C: HasLinksCheckedCap>() -> bool { true }
+fn not_resolved<C: NotHasLinksResolvedCap>() -> bool { true }
+fn resolved<C: HasLinksResolvedCap>() -> bool { true }
+fn published_deps<C: PublishedCapDeps>() -> bool { true }
+
+#[test]
+fn test_generated_markers() {
+    assert_eq!(<LinksCheckedCap as Capability>::NAME, "LinksCheckedCap");
+    assert!(has_checked::<caps![LinksCheckedCap]>());
+    assert!(not_resolved::<caps![LinksCheckedCap]>());
+    assert!(not_resolved::<caps![]>());
+}
+
+#[test]
+fn test_requires_satisfied() {
+    assert!(resolved::<caps![LinksCheckedCap, LinksResolvedCap]>());
+    // Supertrait: HasLinksResolvedCap implies HasLinksCheckedCap
+    fn checked_via_resolved<C: HasLinksResolvedCap>() -> bool { has_checked::<C>() }
+    assert!(checked_via_resolved::<caps![LinksResolvedCap, LinksCheckedCap]>());
+}
+
+#[test]
+fn test_requires_multiple() {
+    // Vacuously satisfied when the dependent cap is absent
+    assert!(published_deps::<caps![]>());
+    assert!(published_deps::<caps![AssetsBundledCap]>());
+    assert!(published_deps::<caps![
+        LinksCheckedCap, LinksResolvedCap, AssetsBundledCap, PublishedCap
+    ]>());
+}
+
+// Missing prerequisites fail to compile: tests/ui/define_capabilities_requires.rs
+
+// =============================================================================
+// Conflict groups
+// =============================================================================
+
+define_capabilities! {
+    SvgOptimized => "SVG content has been optimized",
+    SvgRaw => "SVG content is kept raw",
+    SvgInlined => "SVG content has been inlined",
+    conflicts SvgMode(SvgOptimized, SvgRaw),
+}
+
+fn render_optimized<C: HasSvgOptimizedCap>() -> bool { true }
+fn svg_exclusive<C: SvgModeExclusive>() -> bool { true }
+
+#[test]
+fn test_conflicts_allow_one() {
+    assert!(svg_exclusive::<caps![]>());
+    assert!(svg_exclusive::<caps![SvgRawCap, SvgInlinedCap]>());
+    assert!(render_optimized::<caps![SvgOptimizedCap, SvgInlinedCap]>());
+}
+
+// Unnamed groups whose member names concatenate to the same string still
+// get distinct check traits.
+define_capabilities! {
+    Cold => "Cold",
+    StartFast => "Start fast",
+    ColdStart => "Cold start",
+    Fast => "Fast",
+    conflicts(Cold, StartFast),
+    conflicts(ColdStart, Fast),
+}
+
+#[test]
+fn test_conflicts_unnamed_no_clash() {
+    fn cold<C: HasColdCap>() -> bool { true }
+    fn cold_start<C: HasColdStartCap>() -> bool { true }
+    assert!(cold::<caps![ColdCap, FastCap]>());
+    assert!(cold_start::<caps![ColdStartCap, StartFastCap]>());
+}
+
+// Both members of a conflict group fail to compile: tests/ui/define_capabilities_conflicts.rs.
+// Building such a set is not rejected by itself; see that test.
+
+// =============================================================================
+// Aliases
+// =============================================================================
+
+define_capabilities! {
+    CanRead => "Can read the document",
+}
+
+tola_caps::capability_alias!(
+    /// Renamed from `CanRead`.
+    CanView = CanRead
+);
+
+fn view<C: HasCanViewCap>() -> bool { true }
+fn not_view<C: NotHasCanViewCap>() -> bool { true }
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+#[test]
+fn test_alias_same_capability() {
+    assert!(has::<caps![CanReadCap], CanViewCap>());
+    assert!(has::<caps![CanViewCap], CanReadCap>());
+    assert!(view::<caps![CanReadCap]>());
+    assert!(not_view::<caps![LinksCheckedCap]>());
+    assert_eq!(<CanViewCap as Capability>::NAME, "CanReadCap");
+}
+
+#[derive(Capability)]
+struct Minified;
+
+#[test]
+fn test_mixed_with_derived_capability() {
+    type Set = caps![Minified, LinksCheckedCap];
+    assert!(has::<Set, Minified>());
+    assert!(has::<Set, LinksCheckedCap>());
+    assert!(has_checked::<Set>());
+    assert!(not_resolved::<Set>());
+
+    type Grown = <caps![Minified] as With<LinksCheckedCap>>::Out;
+    assert!(has_checked::<Grown>());
+    assert!(has::<Grown, Minified>());
+}
+
+#[derive(Capability)]
+#[capability(gen_traits)]
+struct Bundled;
+
+#[derive(Capability)]
+#[capability(gen_traits)]
+struct HashedCap;
+
+fn bundled<C: HasBundledCap>() -> bool { true }
+fn not_bundled<C: NotHasBundledCap>() -> bool { true }
+fn hashed<C: HasHashedCap>() -> bool { true }
+
+#[test]
+fn test_derived_gen_traits() {
+    assert!(bundled::<caps![Bundled]>());
+    assert!(bundled::<caps![Minified, Bundled, LinksCheckedCap]>());
+    assert!(not_bundled::<caps![Minified]>());
+    assert!(not_bundled::<caps![]>());
+    assert!(hashed::<caps![HashedCap, Bundled]>());
+
+    // Derived and generated traits compose in one bound
+    fn ready<C: HasBundledCap + HasLinksCheckedCap>() -> bool { bundled::<C>() }
+    assert!(ready::<caps![LinksCheckedCap, Bundled]>());
+}
+
+// =============================================================================
+// Groups
+// =============================================================================
+
+define_capabilities! {
+    group AssetCaps {
+        ImagesResized => "Images have been resized",
+        FontsSubset => "Fonts have been subset" requires ImagesResized,
+    },
+    CssMinified => "CSS has been minified",
+    conflicts(FontsSubset, CssMinified),
+}
+
+fn fonts_subset<C: HasFontsSubsetCap>() -> bool { true }
+
+#[test]
+fn test_group_alias() {
+    assert!(has::<AssetCaps, ImagesResizedCap>());
+    assert!(has::<AssetCaps, FontsSubsetCap>());
+    assert!(!has::<AssetCaps, CssMinifiedCap>());
+    // Members keep their requires / conflicts checks.
+    assert!(fonts_subset::<AssetCaps>());
+    tola_caps::caps_eq!(AssetCaps, caps![FontsSubsetCap, ImagesResizedCap]);
+}
@@ -0,0 +1,298 @@
+//! Tests for query evaluation against capability sets.
+
+use tola_caps::prelude::*;
+use tola_caps::{at_least, at_most, exactly, Evaluated, Has, Present, Absent, Require, Resolve, Xor};
+
+#[derive(Capability)]
+struct CanRead;
+
+#[derive(Capability)]
+struct CanWrite;
+
+#[derive(Capability)]
+struct CanExec;
+
+fn eval<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+// =============================================================================
+// Sets produced by type-level functions
+// =============================================================================
+
+trait Pipeline {
+    type FinalCaps;
+}
+
+struct ReadWritePipeline;
+
+impl Pipeline for ReadWritePipeline {
+    type FinalCaps = caps![CanRead, CanWrite];
+}
+
+fn requires_read<T: Pipeline>() -> bool
+where
+    <T::FinalCaps as Resolve>::Out: Evaluate<Has<CanRead>, Out = Present>,
+{
+    <<T::FinalCaps as Resolve>::Out as Evaluate<Has<CanRead>>>::RESULT
+}
+
+#[test]
+fn test_has_on_projected_set() {
+    type Projected = <ReadWritePipeline as Pipeline>::FinalCaps;
+    assert!(eval::<Projected, Has<CanRead>>());
+    assert!(eval::<Projected, Has<CanWrite>>());
+    assert!(!eval::<Projected, Has<CanExec>>());
+}
+
+#[test]
+fn test_has_through_resolve() {
+    assert!(requires_read::<ReadWritePipeline>());
+}
+
+#[test]
+fn test_has_matches_bare_query() {
+    type Set = caps![CanRead];
+    assert_eq!(eval::<Set, Has<CanRead>>(), eval::<Set, CanRead>());
+    assert_eq!(eval::<Set, Has<CanExec>>(), eval::<Set, CanExec>());
+    let _: <Set as Evaluate<Has<CanExec>>>::Out = Absent;
+}
+
+// =============================================================================
+// Xor
+// =============================================================================
+
+#[test]
+fn test_xor_truth_table() {
+    type Neither = caps![CanExec];
+    type OnlyRead = caps![CanRead];
+    type OnlyWrite = caps![CanWrite];
+    type Both = caps![CanRead, CanWrite];
+    type Q = Xor<Has<CanRead>, Has<CanWrite>>;
+
+    assert!(!eval::<Neither, Q>());
+    assert!(eval::<OnlyRead, Q>());
+    assert!(eval::<OnlyWrite, Q>());
+    assert!(!eval::<Both, Q>());
+}
+
+// =============================================================================
+// Implies
+// =============================================================================
+
+#[test]
+fn test_implies() {
+    type Q = Implies<Has<CanRead>, Has<CanWrite>>;
+
+    assert!(eval::<caps![CanRead, CanWrite], Q>());
+    assert!(!eval::<caps![CanRead], Q>());
+    // Vacuously true when the premise is absent
+    assert!(eval::<caps![CanWrite], Q>());
+    assert!(eval::<caps![CanExec], Q>());
+}
+
+// =============================================================================
+// AtLeast
+// =============================================================================
+
+#[derive(Capability)]
+struct CanNet;
+
+type TwoOfFour = at_least![2; Has<CanRead>, Has<CanWrite>, Has<CanExec>, Has<CanNet>];
+
+#[test]
+fn test_at_least_boundaries() {
+    // Exactly N hold
+    assert!(eval::<caps![CanRead, CanExec], TwoOfFour>());
+    // N - 1 hold
+    assert!(!eval::<caps![CanRead], TwoOfFour>());
+    // More than N hold
+    assert!(eval::<caps![CanRead, CanWrite, CanExec, CanNet], TwoOfFour>());
+    // None hold
+    assert!(!eval::<caps![], TwoOfFour>());
+}
+
+#[test]
+fn test_at_least_zero_is_always_true() {
+    assert!(eval::<caps![], at_least![0; Has<CanRead>]>());
+    assert!(eval::<caps![CanRead], at_least![0;]>());
+    assert!(!eval::<caps![CanRead], at_least![1;]>());
+}
+
+// =============================================================================
+// AtMost / ExactlyN
+// =============================================================================
+
+type OneMode = exactly![1; Has<CanRead>, Has<CanWrite>, Has<CanExec>];
+type AtMostOne = at_most![1; Has<CanRead>, Has<CanWrite>, Has<CanExec>];
+
+#[test]
+fn test_exactly_n() {
+    assert!(eval::<caps![CanWrite], OneMode>());
+    assert!(eval::<caps![CanExec, CanNet], OneMode>());
+    assert!(!eval::<caps![], OneMode>());
+    // Over-count
+    assert!(!eval::<caps![CanRead, CanWrite], OneMode>());
+}
+
+#[test]
+fn test_at_most_n() {
+    assert!(eval::<caps![], AtMostOne>());
+    assert!(eval::<caps![CanRead, CanNet], AtMostOne>());
+    // Over-count
+    assert!(!eval::<caps![CanRead, CanWrite], AtMostOne>());
+    assert!(!eval::<caps![CanRead, CanWrite, CanExec], AtMostOne>());
+}
+
+// =============================================================================
+// RequireAll / RequireAny bounds
+// =============================================================================
+
+fn needs_read_write<C: RequireAll<CanRead, CanWrite>>() {}
+
+fn needs_read_or_exec<C: RequireAny<CanRead, CanExec>>() {}
+
+fn needs_all_three<C: RequireAll<CanRead, CanWrite, CanExec>>() {}
+
+#[test]
+fn test_require_all() {
+    needs_read_write::<caps![CanRead, CanWrite]>();
+    needs_read_write::<caps![CanWrite, CanExec, CanRead]>();
+    needs_all_three::<caps![CanRead, CanWrite, CanExec]>();
+}
+
+#[test]
+fn test_require_any() {
+    needs_read_or_exec::<caps![CanRead]>();
+    needs_read_or_exec::<caps![CanExec, CanWrite]>();
+    needs_read_or_exec::<caps![CanRead, CanExec]>();
+}
+
+// =============================================================================
+// Evaluated
+// =============================================================================
+
+type ReadOnly = caps![CanRead];
+
+const CAN_READ: bool = Evaluated::<ReadOnly, Has<CanRead>>::VALUE;
+const CAN_WRITE: bool = Evaluated::<ReadOnly, CanWrite>::VALUE;
+
+#[test]
+fn test_evaluated_in_const_position() {
+    let read_buf = [0u8; if CAN_READ { 1 } else { 0 }];
+    let write_buf = [0u8; if CAN_WRITE { 1 } else { 0 }];
+    assert_eq!((read_buf.len(), write_buf.len()), (1, 0));
+
+    const EITHER: bool = Evaluated::<ReadOnly, Xor<CanWrite, CanRead>>::VALUE;
+    let either = EITHER;
+    assert!(either);
+}
+
+// =============================================================================
+// Versioned capabilities
+// =============================================================================
+
+mod versioned {
+    use super::eval;
+    use tola_caps::prelude::*;
+    use tola_caps::{Has, HasAtLeast, NoneOfFamily, Require, VersionedCapability};
+
+    #[derive(Capability)]
+    #[capability(version = 1)]
+    pub struct CanRead;
+
+    #[derive(Capability)]
+    #[capability(alias_of = CanRead, version = 2)]
+    pub struct CanReadV2;
+
+    #[derive(Capability)]
+    pub struct CanWrite;
+
+    #[derive(Capability)]
+    pub struct CanExec;
+
+    #[test]
+    fn test_version_consts() {
+        assert_eq!(<CanRead as VersionedCapability>::VERSION, 1);
+        assert_eq!(<CanReadV2 as VersionedCapability>::VERSION, 2);
+    }
+
+    #[test]
+    fn test_has_at_least() {
+        type V2 = caps![CanReadV2];
+        assert!(eval::<V2, HasAtLeast<CanRead, 1>>());
+        assert!(eval::<V2, HasAtLeast<CanRead, 2>>());
+        assert!(!eval::<V2, HasAtLeast<CanRead, 3>>());
+
+        type V1 = caps![CanRead];
+        assert!(eval::<V1, HasAtLeast<CanRead, 1>>());
+        assert!(!eval::<V1, HasAtLeast<CanRead, 2>>());
+    }
+
+    #[test]
+    fn test_has_at_least_absent_family() {
+        assert!(!eval::<caps![CanWrite], HasAtLeast<CanRead, 0>>());
+        assert!(!eval::<caps![], HasAtLeast<CanRead, 0>>());
+    }
+
+    #[test]
+    fn test_has_at_least_in_wider_set() {
+        type Set = caps![CanWrite, CanReadV2, CanExec];
+        assert!(eval::<Set, HasAtLeast<CanRead, 2>>());
+        assert!(!eval::<Set, HasAtLeast<CanRead, 3>>());
+        assert!(eval::<Set, Has<CanWrite>>());
+    }
+
+    fn needs_v2<C: Require<HasAtLeast<CanRead, 2>>>() {}
+
+    #[test]
+    fn test_require_at_least() {
+        needs_v2::<caps![CanReadV2]>();
+    }
+
+    #[test]
+    fn test_none_of_family() {
+        // A member of the family, whichever version: fails
+        assert!(!eval::<caps![CanReadV2], NoneOfFamily<CanRead>>());
+        assert!(!eval::<caps![CanWrite, CanRead], NoneOfFamily<CanRead>>());
+        // Any member stands for the family
+        assert!(!eval::<caps![CanRead], NoneOfFamily<CanReadV2>>());
+
+        // No member: passes
+        assert!(eval::<caps![CanWrite, CanExec], NoneOfFamily<CanRead>>());
+        assert!(eval::<caps![], NoneOfFamily<CanRead>>());
+    }
+
+    fn before_provider<C: Require<NoneOfFamily<CanRead>>>() {}
+
+    #[test]
+    fn test_require_none_of_family() {
+        before_provider::<caps![CanWrite]>();
+        // before_provider::<caps![CanReadV2]>() is rejected at compile time.
+    }
+}
+
+// =============================================================================
+// User-defined queries
+// =============================================================================
+
+/// "Holds at most one capability": a query defined outside the crate.
+/// The orphan rule asks for one impl per node type; two are enough here.
+struct SmallSet;
+
+impl Evaluate<SmallSet> for tola_caps::Empty {
+    type Out = Present;
+}
+
+impl<C> Evaluate<SmallSet> for tola_caps::Leaf<C> {
+    type Out = Present;
+}
+
+fn needs_small_set<C: Require<SmallSet>>() {}
+
+#[test]
+fn test_require_custom_query() {
+    assert!(eval::<caps![CanWrite], SmallSet>());
+    needs_small_set::<caps![]>();
+    needs_small_set::<caps![CanRead]>();
+}
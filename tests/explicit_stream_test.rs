@@ -0,0 +1,383 @@
+//! Test #[capability(stream = ...)] / #[capability(alias_of = ...)] overrides
+
+use tola_caps::prelude::*;
+use tola_caps::primitives::stream::{DefaultMaxDepth, StreamEq};
+use tola_caps::{Bool, CapabilityIdentityEq, CapabilityPath, Evaluate, SetEq, Without};
+
+mod legacy {
+    use super::*;
+    #[derive(Capability)]
+    pub struct CanRead;
+}
+
+mod current {
+    use super::*;
+    #[derive(Capability)]
+    #[capability(alias_of = crate::legacy::CanRead)]
+    pub struct CanRead;
+}
+
+#[derive(Capability)]
+#[capability(stream = "X0 X1 XF")]
+struct ShimA;
+
+#[derive(Capability)]
+#[capability(stream = "0 1 F")]
+struct ShimB;
+
+#[derive(Capability)]
+#[capability(stream = "X0 X1 XE")]
+struct ShimC;
+
+/// Hand-written capability whose stream is `X5` forever.
+struct Fives;
+impl Capability for Fives {
+    type Stream = tola_caps::ConstStream<tola_caps::X5>;
+    type Identity = ();
+    const NAME: &'static str = "Fives";
+    type At<D: tola_caps::Peano> = <<Self::Stream as tola_caps::GetTail<D>>::Out as tola_caps::HashStream>::Head
+    where
+        Self::Stream: tola_caps::GetTail<D>;
+}
+
+/// Hand-written capability whose stream is `X3` forever.
+struct Threes;
+impl Capability for Threes {
+    type Stream = tola_caps::ConstStream<tola_caps::X3>;
+    type Identity = ();
+    const NAME: &'static str = "Threes";
+    type At<D: tola_caps::Peano> = <<Self::Stream as tola_caps::GetTail<D>>::Out as tola_caps::HashStream>::Head
+    where
+        Self::Stream: tola_caps::GetTail<D>;
+}
+
+fn stream_eq<A: Capability, B: Capability>() -> bool
+where
+    A::Stream: StreamEq<B::Stream, DefaultMaxDepth>,
+{
+    <<A::Stream as StreamEq<B::Stream, DefaultMaxDepth>>::Out as Bool>::VALUE
+}
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+#[test]
+fn test_explicit_stream_eq() {
+    assert!(stream_eq::<ShimA, ShimB>());
+    assert!(!stream_eq::<ShimA, ShimC>());
+}
+
+#[test]
+fn test_alias_of_shares_identity() {
+    assert!(stream_eq::<current::CanRead, legacy::CanRead>());
+    assert!(has::<caps![legacy::CanRead], current::CanRead>());
+    assert!(has::<caps![current::CanRead], legacy::CanRead>());
+    assert_eq!(<current::CanRead as Capability>::NAME, "CanRead");
+}
+
+#[test]
+fn test_explicit_stream_routing() {
+    assert!(has::<caps![ShimA, ShimC], ShimA>());
+    assert!(has::<caps![ShimA, ShimC], ShimC>());
+    // ShimB routes to ShimA's leaf, where the identity check rejects it.
+    assert!(!has::<caps![ShimA, ShimC], ShimB>());
+    assert!(!has::<caps![ShimC], ShimA>());
+}
+
+// =============================================================================
+// CapabilityConfig::MaxDepth
+// =============================================================================
+
+// Agree on the first 4 nibbles, differ at the 5th.
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X5", max_depth = 4)]
+struct ShallowA;
+
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X6", max_depth = 4)]
+struct ShallowB;
+
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X5")]
+struct FullA;
+
+#[derive(Capability)]
+#[capability(stream = "X1 X2 X3 X4 X6")]
+struct FullB;
+
+#[test]
+fn test_default_max_depth() {
+    use std::any::TypeId;
+    use tola_caps::CapabilityConfig;
+
+    assert_eq!(
+        TypeId::of::<<FullA as CapabilityConfig>::MaxDepth>(),
+        TypeId::of::<DefaultMaxDepth>()
+    );
+    assert_eq!(
+        TypeId::of::<<legacy::CanRead as CapabilityConfig>::MaxDepth>(),
+        TypeId::of::<DefaultMaxDepth>()
+    );
+    // Hand-written impls get the default without implementing CapabilityConfig
+    assert_eq!(
+        TypeId::of::<<Fives as CapabilityConfig>::MaxDepth>(),
+        TypeId::of::<DefaultMaxDepth>()
+    );
+    assert_eq!(
+        TypeId::of::<<ShallowA as CapabilityConfig>::MaxDepth>(),
+        TypeId::of::<tola_caps::primitives::stream::D4>()
+    );
+}
+
+#[test]
+fn test_shallow_depth_collision() {
+    // Only 4 nibbles are compared, so the streams collide...
+    const SHALLOW_EQ: bool = <<<ShallowA as Capability>::Stream as StreamEq<
+        <ShallowB as Capability>::Stream,
+        <ShallowA as tola_caps::CapabilityConfig>::MaxDepth,
+    >>::Out as Bool>::VALUE;
+    const { assert!(SHALLOW_EQ) };
+    // ...and identity keeps ShallowB from being mistaken for ShallowA.
+    assert!(!has::<caps![ShallowA], ShallowB>());
+    assert!(!has::<caps![ShallowB], ShallowA>());
+    assert_eq!(<caps![ShallowA, ShallowB] as InspectNames>::describe(), "bucket(n=2)");
+
+    // The full default depth tells the same streams apart.
+    assert!(!has::<caps![FullA], FullB>());
+    assert!(!has::<caps![FullB], FullA>());
+}
+
+// =============================================================================
+// CapabilityIdentityEq
+// =============================================================================
+
+fn identity_eq<A: CapabilityIdentityEq<B>, B: Capability>() -> bool {
+    <<A as CapabilityIdentityEq<B>>::Out as Bool>::VALUE
+}
+
+#[test]
+fn test_identity_distinguishes_colliding_streams() {
+    // ShimA and ShimB share a stream, but their identities tell them apart...
+    assert!(stream_eq::<ShimA, ShimB>());
+    assert!(!identity_eq::<ShimA, ShimB>());
+    assert!(identity_eq::<ShimA, ShimA>());
+    // ...so a lone Leaf does not answer for the other one.
+    assert!(!has::<caps![ShimA], ShimB>());
+    assert!(!has::<caps![ShimB], ShimA>());
+    assert!(has::<caps![ShimA], ShimA>());
+}
+
+#[test]
+fn test_colliding_leaf_remove() {
+    type Kept = <caps![ShimA] as Without<ShimB>>::Out;
+    assert_eq!(<Kept as InspectNames>::COUNT, 1);
+    assert!(has::<Kept, ShimA>());
+    assert_eq!(<<caps![ShimA] as Without<ShimA>>::Out as InspectNames>::COUNT, 0);
+}
+
+#[test]
+fn test_colliding_leaf_intersect() {
+    type Inter = <caps![ShimA] as SetIntersect<caps![ShimB]>>::Out;
+    assert_eq!(<Inter as InspectNames>::COUNT, 0);
+    type Same = <caps![ShimA] as SetIntersect<caps![ShimA]>>::Out;
+    assert_eq!(<Same as InspectNames>::COUNT, 1);
+    // An alias shares the aliased identity, so it is kept.
+    type Alias = <caps![current::CanRead] as SetIntersect<caps![legacy::CanRead]>>::Out;
+    assert_eq!(<Alias as InspectNames>::COUNT, 1);
+}
+
+#[test]
+fn test_identity_same_name_different_module() {
+    assert!(identity_eq::<legacy::CanRead, legacy::CanRead>());
+    // alias_of shares the aliased identity
+    assert!(identity_eq::<current::CanRead, legacy::CanRead>());
+    assert!(!identity_eq::<legacy::CanRead, ShimA>());
+}
+
+#[test]
+fn test_identity_std_markers() {
+    use tola_caps::detect::{IsClone, IsCopy};
+
+    assert!(identity_eq::<IsClone, IsClone>());
+    assert!(!identity_eq::<IsClone, IsCopy>());
+}
+
+// =============================================================================
+// Hash collision detection on insert
+// =============================================================================
+
+#[test]
+fn test_alias_insert_dedupes() {
+    // Same stream and same identity: the second insert is a no-op.
+    type Both = caps![legacy::CanRead, current::CanRead];
+    assert_eq!(<Both as InspectNames>::COUNT, 1);
+    assert!(has::<Both, legacy::CanRead>());
+    assert!(has::<Both, current::CanRead>());
+}
+
+// Same stream, different identity - both are kept in a Bucket.
+type Collided = caps![ShimA, ShimB];
+
+#[test]
+fn test_collision_keeps_both() {
+    assert_eq!(<Collided as InspectNames>::COUNT, 2);
+    assert!(has::<Collided, ShimA>());
+    assert!(has::<Collided, ShimB>());
+    assert!(!has::<Collided, ShimC>());
+
+    let mut names = Vec::new();
+    Collided::each_name(&mut |n| names.push(n));
+    names.sort();
+    assert_eq!(names, ["ShimA", "ShimB"]);
+    assert!(tola_caps::dump_trie!(Collided).starts_with("Bucket "));
+}
+
+#[test]
+fn test_collision_dedupes_members() {
+    type Again = <Collided as With<ShimB>>::Out;
+    assert_eq!(<Again as InspectNames>::COUNT, 2);
+    type Alias = caps![legacy::CanRead, ShimA, current::CanRead, ShimB];
+    assert_eq!(<Alias as InspectNames>::COUNT, 3);
+}
+
+#[test]
+fn test_collision_remove() {
+    type OnlyB = <Collided as Without<ShimA>>::Out;
+    assert_eq!(<OnlyB as InspectNames>::COUNT, 1);
+    // A bucket left with one member is a plain Leaf again.
+    assert_eq!(tola_caps::dump_trie!(OnlyB), "Leaf ShimB\n");
+    assert_eq!(<<OnlyB as Without<ShimB>>::Out as InspectNames>::COUNT, 0);
+}
+
+#[test]
+fn test_collision_shares_prefix() {
+    // ShimC diverges at depth 2, so the bucket is pushed down beside it.
+    type Three = caps![ShimA, ShimC, ShimB];
+    assert_eq!(<Three as InspectNames>::COUNT, 3);
+    assert!(has::<Three, ShimA>());
+    assert!(has::<Three, ShimB>());
+    assert!(has::<Three, ShimC>());
+    assert!(tola_caps::dump_trie!(Three).contains("[F] Bucket "));
+
+    type Back = <Three as Without<ShimC>>::Out;
+    assert_eq!(<Back as InspectNames>::COUNT, 2);
+    assert!(tola_caps::dump_trie!(Back).starts_with("Bucket "));
+}
+
+#[test]
+fn test_collision_set_ops() {
+    type Union = <caps![ShimA] as SetUnion<caps![ShimB, ShimC]>>::Out;
+    assert_eq!(<Union as InspectNames>::COUNT, 3);
+    type Inter = <Collided as SetIntersect<Union>>::Out;
+    assert_eq!(<Inter as InspectNames>::COUNT, 2);
+    type Neither = <Collided as SetIntersect<caps![ShimC]>>::Out;
+    assert_eq!(<Neither as InspectNames>::COUNT, 0);
+    let eq = <<Collided as SetEq<caps![ShimB, ShimA]>>::Out as Bool>::VALUE;
+    assert!(eq);
+}
+
+#[test]
+fn test_describe_node_shapes() {
+    assert_eq!(<caps![] as InspectNames>::describe(), "empty");
+    assert_eq!(<caps![ShimC] as InspectNames>::describe(), "leaf");
+    assert_eq!(<Collided as InspectNames>::describe(), "bucket(n=2)");
+    // The bucket sits two levels down, below the shared ShimC prefix.
+    assert_eq!(<caps![ShimA, ShimC, ShimB] as InspectNames>::describe(), "node(n=3)");
+    assert_eq!(<caps![legacy::CanRead, ShimA, ShimB, ShimC] as InspectNames>::describe(), "node(n=4)");
+}
+
+// =============================================================================
+// Namespaces
+// =============================================================================
+
+mod auth {
+    use super::*;
+    #[derive(Capability)]
+    #[capability(namespace = "acme-auth")]
+    pub struct Cache;
+}
+
+mod billing {
+    use super::*;
+    #[derive(Capability)]
+    #[capability(namespace = "acme-billing")]
+    pub struct Cache;
+
+    pub mod same_ns {
+        use super::*;
+        #[derive(Capability)]
+        #[capability(namespace = "acme-billing", max_depth = 8)]
+        pub struct Cache;
+    }
+}
+
+#[derive(Capability)]
+#[capability(stream = "X0 X1 XF", namespace = "acme-auth")]
+struct ShimNs;
+
+#[test]
+fn test_namespace_separates_streams() {
+    assert!(!stream_eq::<auth::Cache, billing::Cache>());
+    assert!(!identity_eq::<auth::Cache, billing::Cache>());
+    assert!(!has::<caps![auth::Cache], billing::Cache>());
+    assert!(has::<caps![auth::Cache, billing::Cache], billing::Cache>());
+    assert_eq!(<auth::Cache as Capability>::NAME, "Cache");
+}
+
+#[test]
+fn test_namespace_changes_hash_key() {
+    use std::any::TypeId;
+
+    // Without a namespace the stream is the hash of `module_path::Name`...
+    assert_eq!(
+        TypeId::of::<<legacy::CanRead as Capability>::Stream>(),
+        TypeId::of::<tola_caps::make_routing_stream!(concat!(module_path!(), "::legacy::CanRead"))>()
+    );
+    // ...with one, the same path hashes differently.
+    assert_ne!(
+        TypeId::of::<<auth::Cache as Capability>::Stream>(),
+        TypeId::of::<tola_caps::make_routing_stream!(concat!(module_path!(), "::auth::Cache"))>()
+    );
+}
+
+#[test]
+fn test_namespace_with_explicit_stream() {
+    // Routing is explicit, but the identity is namespaced.
+    assert!(stream_eq::<ShimNs, ShimA>());
+    assert!(!identity_eq::<ShimNs, ShimA>());
+    assert!(!stream_eq::<billing::same_ns::Cache, billing::Cache>());
+}
+
+#[test]
+fn test_capability_path() {
+    assert_eq!(<Fives as CapabilityPath>::PATH, [5; 16]);
+    // Explicit streams are padded with `X0`.
+    let mut expected = [0; 16];
+    expected[1] = 0x1;
+    expected[2] = 0xF;
+    assert_eq!(<ShimA as CapabilityPath>::PATH, expected);
+    assert_eq!(<ShimA as CapabilityPath>::PATH, <ShimB as CapabilityPath>::PATH);
+    assert_ne!(<ShimA as CapabilityPath>::PATH, <ShimC as CapabilityPath>::PATH);
+    // Aliases route identically.
+    assert_eq!(
+        <legacy::CanRead as CapabilityPath>::PATH,
+        <current::CanRead as CapabilityPath>::PATH
+    );
+}
+
+#[test]
+fn test_eval_path() {
+    use tola_caps::EvalPath;
+
+    assert_eq!(<Threes as EvalPath>::STEPS, [3; 16]);
+    assert_eq!(<Fives as EvalPath>::STEPS, <Fives as CapabilityPath>::PATH);
+
+    let steps = <ShimA as EvalPath>::STEPS;
+    assert_eq!(steps.len(), 16);
+    assert_eq!(&steps[..4], [0x0, 0x1, 0xF, 0x0]);
+    assert_eq!(steps, <ShimB as EvalPath>::STEPS);
+
+    // Only the compared depth is reported.
+    assert_eq!(<ShallowA as EvalPath>::STEPS, [0x1, 0x2, 0x3, 0x4]);
+}
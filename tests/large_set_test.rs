@@ -0,0 +1,139 @@
+//! Compile-time stress test: a 50-capability set.
+//!
+//! Building and querying this set exercises `StreamEq` on every insert and
+//! lookup; `CARGO_INCREMENTAL=0 cargo test --test large_set_test --no-run`
+//! times it (about 3s here). Most of that goes to the
+//! `#[derive(Capability)]` expansions: extra lookups, present or absent,
+//! add little.
+
+use tola_caps::prelude::*;
+use tola_caps::{And, InspectNames};
+
+#[derive(Capability)]
+struct Cap00;
+#[derive(Capability)]
+struct Cap01;
+#[derive(Capability)]
+struct Cap02;
+#[derive(Capability)]
+struct Cap03;
+#[derive(Capability)]
+struct Cap04;
+#[derive(Capability)]
+struct Cap05;
+#[derive(Capability)]
+struct Cap06;
+#[derive(Capability)]
+struct Cap07;
+#[derive(Capability)]
+struct Cap08;
+#[derive(Capability)]
+struct Cap09;
+#[derive(Capability)]
+struct Cap10;
+#[derive(Capability)]
+struct Cap11;
+#[derive(Capability)]
+struct Cap12;
+#[derive(Capability)]
+struct Cap13;
+#[derive(Capability)]
+struct Cap14;
+#[derive(Capability)]
+struct Cap15;
+#[derive(Capability)]
+struct Cap16;
+#[derive(Capability)]
+struct Cap17;
+#[derive(Capability)]
+struct Cap18;
+#[derive(Capability)]
+struct Cap19;
+#[derive(Capability)]
+struct Cap20;
+#[derive(Capability)]
+struct Cap21;
+#[derive(Capability)]
+struct Cap22;
+#[derive(Capability)]
+struct Cap23;
+#[derive(Capability)]
+struct Cap24;
+#[derive(Capability)]
+struct Cap25;
+#[derive(Capability)]
+struct Cap26;
+#[derive(Capability)]
+struct Cap27;
+#[derive(Capability)]
+struct Cap28;
+#[derive(Capability)]
+struct Cap29;
+#[derive(Capability)]
+struct Cap30;
+#[derive(Capability)]
+struct Cap31;
+#[derive(Capability)]
+struct Cap32;
+#[derive(Capability)]
+struct Cap33;
+#[derive(Capability)]
+struct Cap34;
+#[derive(Capability)]
+struct Cap35;
+#[derive(Capability)]
+struct Cap36;
+#[derive(Capability)]
+struct Cap37;
+#[derive(Capability)]
+struct Cap38;
+#[derive(Capability)]
+struct Cap39;
+#[derive(Capability)]
+struct Cap40;
+#[derive(Capability)]
+struct Cap41;
+#[derive(Capability)]
+struct Cap42;
+#[derive(Capability)]
+struct Cap43;
+#[derive(Capability)]
+struct Cap44;
+#[derive(Capability)]
+struct Cap45;
+#[derive(Capability)]
+struct Cap46;
+#[derive(Capability)]
+struct Cap47;
+#[derive(Capability)]
+struct Cap48;
+#[derive(Capability)]
+struct Cap49;
+
+#[derive(Capability)]
+struct Outsider;
+
+type Fifty = caps![
+    Cap00, Cap01, Cap02, Cap03, Cap04, Cap05, Cap06, Cap07, Cap08, Cap09,
+    Cap10, Cap11, Cap12, Cap13, Cap14, Cap15, Cap16, Cap17, Cap18, Cap19,
+    Cap20, Cap21, Cap22, Cap23, Cap24, Cap25, Cap26, Cap27, Cap28, Cap29,
+    Cap30, Cap31, Cap32, Cap33, Cap34, Cap35, Cap36, Cap37, Cap38, Cap39,
+    Cap40, Cap41, Cap42, Cap43, Cap44, Cap45, Cap46, Cap47, Cap48, Cap49,
+];
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+#[test]
+fn test_fifty_caps_count() {
+    assert_eq!(<Fifty as InspectNames>::COUNT, 50);
+    assert_eq!(Fifty::names().len(), 50);
+}
+
+#[test]
+fn test_fifty_caps_lookup() {
+    assert!(has::<Fifty, Cap00>() && has::<Fifty, Cap25>() && has::<Fifty, Cap49>());
+    assert!(has::<Fifty, And<Cap07, And<Cap13, And<Cap31, Cap42>>>>());
+    assert!(!has::<Fifty, Outsider>());
+}
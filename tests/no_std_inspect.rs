@@ -0,0 +1,52 @@
+//! Name enumeration without `alloc`.
+
+#![no_std]
+
+use tola_caps::prelude::*;
+use tola_caps::InspectNames;
+
+#[derive(Capability)]
+struct CanRead;
+#[derive(Capability)]
+struct CanWrite;
+#[derive(Capability)]
+struct CanExec;
+
+fn collect<S: InspectNames>(buf: &mut [&'static str; 8]) -> usize {
+    let mut len = 0;
+    S::for_each_name(&mut |name| {
+        buf[len] = name;
+        len += 1;
+    });
+    len
+}
+
+#[test]
+fn test_for_each_name_fixed_buffer() {
+    let mut buf = [""; 8];
+    let len = collect::<caps![CanRead, CanWrite, CanExec]>(&mut buf);
+    assert_eq!(len, 3);
+    let names = &mut buf[..len];
+    names.sort_unstable();
+    assert_eq!(names, ["CanExec", "CanRead", "CanWrite"]);
+}
+
+#[test]
+fn test_for_each_name_empty() {
+    let mut buf = [""; 8];
+    assert_eq!(collect::<caps![]>(&mut buf), 0);
+}
+
+#[test]
+fn test_for_each_name_matches_each_name() {
+    type Set = caps![CanRead, CanWrite];
+    let mut a = [""; 8];
+    let len = collect::<Set>(&mut a);
+    let mut b = [""; 8];
+    let mut i = 0;
+    Set::each_name(&mut |name| {
+        b[i] = name;
+        i += 1;
+    });
+    assert_eq!(a[..len], b[..i]);
+}
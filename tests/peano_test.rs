@@ -0,0 +1,71 @@
+//! Type-level Peano comparisons.
+
+use std::cmp::Ordering;
+use tola_caps::primitives::stream::{D0, D3, D4, D5, D7, D16};
+use tola_caps::{Bool, Ordering3, Peano, PeanoAdd, PeanoCmp, PeanoEq, PeanoLess, PeanoLessEq, PeanoVal};
+
+fn less<A: PeanoLess<B>, B: Peano>() -> bool {
+    <A::Out as Bool>::VALUE
+}
+
+fn less_eq<A: PeanoLessEq<B>, B: Peano>() -> bool {
+    <A::Out as Bool>::VALUE
+}
+
+fn eq<A: PeanoEq<B>, B: Peano>() -> bool {
+    <A::Out as Bool>::VALUE
+}
+
+fn cmp<A: PeanoCmp<B>, B: Peano>() -> Ordering {
+    <A::Out as Ordering3>::VALUE
+}
+
+fn sum<A: PeanoAdd<B>, B: Peano>() -> usize
+where
+    A::Out: PeanoVal,
+{
+    <A::Out as PeanoVal>::VAL
+}
+
+#[test]
+fn test_peano_val() {
+    assert_eq!(<D0 as PeanoVal>::VAL, 0);
+    assert_eq!(<D5 as PeanoVal>::VAL, 5);
+    assert_eq!(<D16 as PeanoVal>::VAL, 16);
+}
+
+#[test]
+fn test_peano_add() {
+    assert_eq!(sum::<D3, D4>(), 7);
+    assert_eq!(sum::<D0, D5>(), 5);
+    assert_eq!(sum::<D5, D0>(), 5);
+    assert!(eq::<<D3 as PeanoAdd<D4>>::Out, D7>());
+}
+
+#[test]
+fn test_peano_less() {
+    assert!(less::<D3, D5>());
+    assert!(!less::<D5, D3>());
+    assert!(!less::<D3, D3>());
+    assert!(less::<D0, D16>());
+    assert!(!less::<D0, D0>());
+}
+
+#[test]
+fn test_peano_less_eq_and_eq() {
+    assert!(less_eq::<D3, D5>());
+    assert!(less_eq::<D3, D3>());
+    assert!(!less_eq::<D5, D3>());
+    assert!(eq::<D5, D5>());
+    assert!(!eq::<D3, D5>());
+    assert!(!eq::<D16, D0>());
+}
+
+#[test]
+fn test_peano_cmp() {
+    assert_eq!(cmp::<D3, D5>(), Ordering::Less);
+    assert_eq!(cmp::<D5, D3>(), Ordering::Greater);
+    assert_eq!(cmp::<D5, D5>(), Ordering::Equal);
+    assert_eq!(cmp::<D0, D0>(), Ordering::Equal);
+    assert_eq!(cmp::<D16, D0>(), Ordering::Greater);
+}
@@ -0,0 +1,118 @@
+//! Truth tables for list queries on empty lists and zero thresholds.
+
+use tola_caps::prelude::*;
+use tola_caps::primitives::stream::{D0, D1, D2};
+use tola_caps::{all_absent, any_absent, All, AllAbsent, Any, AnyAbsent, AtLeast, AtMost, ExactlyN, Has, HCons, HNil, Require};
+
+#[derive(Capability)]
+struct CanRead;
+
+#[derive(Capability)]
+struct CanWrite;
+
+fn eval<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+type Empty = caps![];
+type Read = caps![CanRead];
+type Both = caps![CanRead, CanWrite];
+
+/// One query, which holds on `Read` and `Both`.
+type OneRead = HCons<Has<CanRead>, HNil>;
+/// Two queries; one holds on `Read`, both on `Both`.
+type ReadWrite = HCons<Has<CanRead>, HCons<Has<CanWrite>, HNil>>;
+
+#[test]
+fn test_all_any_empty_list() {
+    assert!(eval::<Empty, All<HNil>>());
+    assert!(eval::<Both, All<HNil>>());
+    assert!(!eval::<Empty, Any<HNil>>());
+    assert!(!eval::<Both, Any<HNil>>());
+}
+
+#[test]
+fn test_absent_empty_list() {
+    assert!(eval::<Both, AllAbsent<HNil>>());
+    assert!(!eval::<Empty, AnyAbsent<HNil>>());
+}
+
+#[test]
+fn test_all_absent() {
+    assert!(eval::<Empty, all_absent![CanRead, CanWrite]>());
+    // One listed capability present is enough to fail
+    assert!(!eval::<Read, all_absent![CanRead, CanWrite]>());
+    assert!(!eval::<Read, all_absent![CanWrite, CanRead]>());
+    assert!(!eval::<Both, all_absent![CanRead, CanWrite]>());
+    assert!(eval::<Read, all_absent![CanWrite]>());
+    // Members are queries, as in `All`
+    assert!(!eval::<Read, AllAbsent<OneRead>>());
+
+    fn before_io<C: Require<all_absent![CanRead, CanWrite]>>() {}
+    before_io::<Empty>();
+}
+
+#[test]
+fn test_any_absent() {
+    assert!(eval::<Empty, any_absent![CanRead, CanWrite]>());
+    assert!(eval::<Read, any_absent![CanRead, CanWrite]>());
+    assert!(!eval::<Both, any_absent![CanRead, CanWrite]>());
+    assert!(!eval::<Both, AnyAbsent<ReadWrite>>());
+}
+
+#[test]
+fn test_at_least_zero() {
+    // Always Present, whatever the list or set
+    assert!(eval::<Empty, AtLeast<D0, HNil>>());
+    assert!(eval::<Empty, AtLeast<D0, OneRead>>());
+    assert!(eval::<Both, AtLeast<D0, ReadWrite>>());
+}
+
+#[test]
+fn test_at_least_empty_list() {
+    assert!(!eval::<Both, AtLeast<D1, HNil>>());
+    assert!(!eval::<Both, AtLeast<D2, HNil>>());
+}
+
+#[test]
+fn test_at_most_zero() {
+    assert!(eval::<Both, AtMost<D0, HNil>>());
+    assert!(eval::<Empty, AtMost<D0, ReadWrite>>());
+    // Any holding query breaks AtMost<0>
+    assert!(!eval::<Read, AtMost<D0, OneRead>>());
+    assert!(!eval::<Read, AtMost<D0, ReadWrite>>());
+    assert!(!eval::<Both, AtMost<D0, ReadWrite>>());
+}
+
+#[test]
+fn test_at_most_empty_list() {
+    assert!(eval::<Both, AtMost<D1, HNil>>());
+    assert!(eval::<Empty, AtMost<D2, HNil>>());
+}
+
+#[test]
+fn test_exactly_zero() {
+    assert!(eval::<Empty, ExactlyN<D0, HNil>>());
+    assert!(eval::<Both, ExactlyN<D0, HNil>>());
+    assert!(eval::<Empty, ExactlyN<D0, ReadWrite>>());
+    assert!(!eval::<Read, ExactlyN<D0, ReadWrite>>());
+}
+
+#[test]
+fn test_exactly_empty_list() {
+    assert!(!eval::<Both, ExactlyN<D1, HNil>>());
+    assert!(!eval::<Empty, ExactlyN<D2, HNil>>());
+}
+
+#[test]
+fn test_exactly_zero_matches_at_most_zero() {
+    fn agree<S>() -> bool
+    where
+        S: Evaluate<ExactlyN<D0, ReadWrite>> + Evaluate<AtMost<D0, ReadWrite>>,
+    {
+        eval::<S, ExactlyN<D0, ReadWrite>>() == eval::<S, AtMost<D0, ReadWrite>>()
+    }
+    assert!(agree::<Empty>());
+    assert!(agree::<Read>());
+    assert!(agree::<Both>());
+}
@@ -1,7 +1,7 @@
 //! Test runtime reflection via the Inspect trait
 
 use tola_caps::prelude::*;
-use tola_caps::capability::Inspect;
+use tola_caps::capability::{Inspect, InspectNames};
 
 #[derive(Capability)]
 struct CapA;
@@ -44,3 +44,360 @@ fn test_inspect_multiple_caps() {
     assert!(names_str.contains("CapB"), "Missing CapB in {}", names_str);
     assert!(names_str.contains("CapC"), "Missing CapC in {}", names_str);
 }
+
+#[derive(Capability)]
+struct CanRead;
+
+#[derive(Capability)]
+struct CanWrite;
+
+#[test]
+fn test_names_empty() {
+    assert!(tola_caps::capability::Empty::names().is_empty());
+}
+
+#[test]
+fn test_names_uses_capability_name() {
+    assert_eq!(<caps![CapA]>::names(), ["CapA"]);
+}
+
+#[test]
+fn test_names_independent_of_trie_shape() {
+    let mut forward = <caps![CanRead, CanWrite]>::names();
+    let mut backward = <caps![CanWrite, CanRead]>::names();
+    forward.sort_unstable();
+    backward.sort_unstable();
+    assert_eq!(forward, ["CanRead", "CanWrite"]);
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn test_names_nested_node() {
+    let mut names = <caps![CapA, CapB, CapC, CanRead, CanWrite]>::names();
+    names.sort_unstable();
+    assert_eq!(names, ["CanRead", "CanWrite", "CapA", "CapB", "CapC"]);
+}
+
+#[test]
+fn test_count_capset_aliases() {
+    use tola_caps::{CapSet0, CapSet1, CapSet2, CapSet3, CapSet4};
+
+    assert_eq!(<CapSet0 as InspectNames>::count(), 0);
+    assert_eq!(<CapSet1<CapA> as InspectNames>::count(), 1);
+    assert_eq!(<CapSet2<CapA, CapB> as InspectNames>::count(), 2);
+    assert_eq!(<CapSet3<CapA, CapB, CapC> as InspectNames>::count(), 3);
+    assert_eq!(<CapSet4<CapA, CapB, CapC, CanRead> as InspectNames>::count(), 4);
+}
+
+#[test]
+fn test_count_in_const_context() {
+    const N: usize = <caps![CapA, CapB, CanRead, CanWrite] as InspectNames>::COUNT;
+    let counters = [0u32; N];
+    assert_eq!(counters.len(), 4);
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(tola_caps::capability::Empty::render(), "{}");
+
+    let rendered = <caps![CanRead, CanWrite]>::render();
+    assert!(rendered.starts_with('{') && rendered.ends_with('}'));
+    assert!(rendered.contains("CanRead"), "Missing CanRead in {}", rendered);
+    assert!(rendered.contains("CanWrite"), "Missing CanWrite in {}", rendered);
+    assert_eq!(rendered.len(), "{CanRead, CanWrite}".len());
+}
+
+#[test]
+fn test_cap_set_display() {
+    use tola_caps::CapSetDisplay;
+
+    type Set = caps![CanRead, CanWrite];
+    let shown = format!("{:?}", CapSetDisplay::<Set>::default());
+    assert_eq!(shown, <Set>::render());
+    assert_eq!(format!("{}", CapSetDisplay::<Set>::new()), shown);
+}
+
+// =============================================================================
+// Bitmask encoding
+// =============================================================================
+
+tola_caps::register_caps!(CanRead = 0, CanWrite = 1, CapA = 5, CapB = 63);
+
+type Registry = tola_caps::hlist![CanRead, CanWrite, CapA, CapB];
+
+#[test]
+fn test_to_bits() {
+    use tola_caps::to_bits;
+
+    assert_eq!(to_bits::<caps![]>(), 0);
+    assert_eq!(to_bits::<caps![CanWrite]>(), 0b10);
+    assert_eq!(to_bits::<caps![CanRead, CanWrite, CapA]>(), 0b100011);
+    assert_eq!(to_bits::<caps![CapB, CanRead]>(), (1 << 63) | 1);
+
+    const BITS: u64 = tola_caps::to_bits::<caps![CapA]>();
+    assert_eq!(BITS, 1 << 5);
+}
+
+#[test]
+fn test_bits_round_trip() {
+    use tola_caps::{from_bits, to_bits, BitRegistry};
+
+    type Set = caps![CapB, CanWrite, CanRead];
+    let bits = to_bits::<Set>();
+    assert_eq!(from_bits::<Registry>(bits), ["CanRead", "CanWrite", "CapB"]);
+
+    let mut decoded = from_bits::<Registry>(bits);
+    let mut names = Set::names();
+    decoded.sort();
+    names.sort();
+    assert_eq!(decoded, names);
+
+    // Unregistered bits are ignored, and visible via MASK
+    assert_eq!(from_bits::<Registry>(1 << 7), Vec::<&str>::new());
+    assert_eq!(<Registry as BitRegistry>::MASK, (1 << 63) | (1 << 5) | 0b11);
+}
+
+#[test]
+fn test_fingerprint() {
+    use std::collections::HashMap;
+    use tola_caps::{fingerprint, SetUnion};
+
+    let ab = fingerprint::<caps![CapA, CapB]>();
+    assert_eq!(ab, fingerprint::<caps![CapB, CapA]>());
+    assert_ne!(ab, fingerprint::<caps![CapA]>());
+    assert_ne!(fingerprint::<caps![CapA]>(), fingerprint::<caps![]>());
+    assert_eq!(fingerprint::<<caps![CapA] as SetUnion<caps![CapB]>>::Out>(), ab);
+
+    const ABC: u64 = fingerprint::<caps![CapA, CapB, CapC]>();
+    let mut cache = HashMap::new();
+    cache.insert(ABC, "full");
+    cache.insert(ab, "partial");
+    assert_eq!(cache[&fingerprint::<caps![CapC, CapA, CapB]>()], "full");
+}
+
+#[test]
+fn test_inspect_has() {
+    assert!(<caps![CapA] as Inspect>::has::<CapA>());
+    assert!(!<caps![CapA] as Inspect>::has::<CapB>());
+    assert!(!<caps![] as Inspect>::has::<CapA>());
+
+    type Many = caps![CapA, CapB, CapC];
+    assert!(Many::has::<CapA>() && Many::has::<CapB>() && Many::has::<CapC>());
+    assert!(!<caps![CapA, CapC] as Inspect>::has::<CapB>());
+}
+
+#[test]
+fn test_inspect_has_from_value() {
+    struct Doc<C>(std::marker::PhantomData<C>);
+
+    fn readable<C: Inspect + tola_caps::Evaluate<tola_caps::Has<CapA>>>(_: &Doc<C>) -> bool {
+        C::has::<CapA>()
+    }
+
+    assert!(readable(&Doc::<caps![CapA, CapB]>(std::marker::PhantomData)));
+    assert!(!readable(&Doc::<caps![CapB]>(std::marker::PhantomData)));
+}
+
+#[test]
+fn test_inspect_present_token() {
+    let token: Option<tola_caps::CapToken<CapA>> = <caps![CapA, CapB]>::present::<CapA>();
+    assert!(token.is_some());
+    assert!(<caps![CapB]>::present::<CapA>().is_none());
+    assert!(<caps![]>::present::<CapA>().is_none());
+    assert_eq!(format!("{:?}", token.unwrap()), "CapToken<CapA>");
+    assert_eq!(core::mem::size_of::<tola_caps::CapToken<CapA>>(), 0);
+}
+
+#[derive(Capability)]
+#[capability(stream = "X2 X5 X0")]
+struct Deep1;
+
+#[derive(Capability)]
+#[capability(stream = "X2 X5 X9")]
+struct Deep2;
+
+#[derive(Capability)]
+#[capability(stream = "XA")]
+struct Shallow;
+
+#[test]
+fn test_dump_trie_shapes() {
+    assert_eq!(tola_caps::dump_trie!(caps![]), "Empty\n");
+    assert_eq!(tola_caps::dump_trie!(caps![Shallow]), "Leaf Shallow\n");
+    assert_eq!(
+        tola_caps::dump_trie!(caps![Shallow, Deep1]),
+        "Node16\n  [2] Leaf Deep1\n  [A] Leaf Shallow\n"
+    );
+}
+
+#[test]
+fn test_dump_trie_shared_prefix() {
+    // Deep1 and Deep2 agree on two nibbles, so they split two levels down.
+    const LAYOUT: &str = tola_caps::dump_trie!(caps![Deep1, Deep2, Shallow]);
+    assert_eq!(
+        LAYOUT,
+        concat!(
+            "Node16\n",
+            "  [2] Node16\n",
+            "    [5] Node16\n",
+            "      [0] Leaf Deep1\n",
+            "      [9] Leaf Deep2\n",
+            "  [A] Leaf Shallow\n",
+        )
+    );
+}
+
+#[test]
+fn test_dump_trie_lists_every_member() {
+    let layout = tola_caps::dump_trie!(caps![CapA, CapB, CapC]);
+    assert_eq!(layout.matches("Leaf ").count(), 3);
+    for name in ["CapA", "CapB", "CapC"] {
+        assert!(layout.contains(name), "{layout}");
+    }
+}
+
+#[test]
+fn test_dyn_cap_set() {
+    use tola_caps::DynCapSet;
+
+    let mut set = DynCapSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert::<CapA>());
+    assert!(!set.insert::<CapA>());
+    assert!(set.insert::<CapB>());
+    assert_eq!(set.len(), 2);
+
+    assert!(set.contains::<CapA>());
+    assert!(set.contains::<CapB>());
+    assert!(!set.contains::<CapC>());
+
+    assert!(set.remove::<CapB>());
+    assert!(!set.remove::<CapB>());
+    assert!(!set.contains::<CapB>());
+}
+
+#[test]
+fn test_dyn_cap_set_superset() {
+    use tola_caps::DynCapSet;
+
+    let all = DynCapSet::from_set::<caps![CapA, CapB, CapC]>();
+    let some = DynCapSet::from_set::<caps![CapA, CapC]>();
+    let empty = DynCapSet::new();
+
+    assert_eq!(all.len(), 3);
+    assert!(all.is_superset(&some));
+    assert!(!some.is_superset(&all));
+    assert!(all.is_superset(&all));
+    assert!(some.is_superset(&empty));
+    assert!(empty.is_superset(&empty));
+
+    let mut built = DynCapSet::new();
+    built.insert::<CapC>();
+    built.insert::<CapA>();
+    assert_eq!(built, some);
+}
+
+mod fs {
+    use tola_caps::prelude::*;
+
+    #[derive(Capability)]
+    pub struct Access;
+}
+
+mod net {
+    use tola_caps::prelude::*;
+
+    #[derive(Capability)]
+    pub struct Access;
+}
+
+#[test]
+fn test_dyn_cap_set_same_name_different_modules() {
+    use tola_caps::{validate_plugin_caps, DynCapSet};
+
+    let mut set = DynCapSet::new();
+    assert!(set.insert::<fs::Access>());
+    assert!(!set.contains::<net::Access>());
+    assert!(set.insert::<net::Access>());
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.names().collect::<Vec<_>>(), ["Access", "Access"]);
+
+    let both = DynCapSet::from_set::<caps![fs::Access, net::Access]>();
+    assert_eq!(both, set);
+
+    set.remove::<fs::Access>();
+    assert!(set.contains::<net::Access>());
+    assert_eq!(validate_plugin_caps(&set, &both), Err(vec!["Access"]));
+}
+
+#[test]
+fn test_validate_plugin_caps() {
+    use tola_caps::{validate_plugin_caps, DynCapSet};
+
+    let required = DynCapSet::from_set::<caps![CapA, CapC]>();
+
+    let full = DynCapSet::from_set::<caps![CapA, CapB, CapC]>();
+    assert_eq!(validate_plugin_caps(&full, &required), Ok(()));
+    assert_eq!(validate_plugin_caps(&full, &DynCapSet::new()), Ok(()));
+
+    let mut partial = DynCapSet::new();
+    partial.insert::<CapB>();
+    partial.insert::<CapC>();
+    assert_eq!(validate_plugin_caps(&partial, &required), Err(vec!["CapA"]));
+    assert_eq!(validate_plugin_caps(&DynCapSet::new(), &required), Err(vec!["CapA", "CapC"]));
+}
+
+#[test]
+fn test_cap_names_const() {
+    use tola_caps::{set_contains, CapNames};
+
+    const NAMES: &[&str] = <caps![CapA, CapB] as CapNames>::NAMES;
+    const HAS_A: bool = set_contains(NAMES, "CapA");
+    const HAS_B: bool = set_contains(NAMES, "CapB");
+    const HAS_C: bool = set_contains(NAMES, "CapC");
+    let (has_a, has_b, has_c) = (HAS_A, HAS_B, HAS_C);
+    assert!(has_a && has_b && !has_c);
+    assert_eq!(NAMES.len(), 2);
+
+    const EMPTY: &[&str] = tola_caps::cap_names!(caps![]);
+    assert!(EMPTY.is_empty());
+    assert!(!set_contains(EMPTY, "CapA"));
+
+    let mut names = tola_caps::cap_names!(caps![CapC, CapA, CapB]).to_vec();
+    names.sort();
+    assert_eq!(names, ["CapA", "CapB", "CapC"]);
+}
+
+/// A downstream set type that keeps the default `describe`.
+#[derive(Default)]
+struct Pair;
+
+/// Only `inspect` is required, as before `InspectNames` existed.
+impl Inspect for Pair {
+    fn inspect<F: FnMut(&'static str)>(&self, mut f: F) {
+        f("CapA");
+        f("CapB");
+    }
+}
+
+impl InspectNames for Pair {
+    const COUNT: usize = 2;
+
+    fn each_cap<F: FnMut(std::any::TypeId, &'static str)>(f: &mut F) {
+        f(std::any::TypeId::of::<CapA>(), "CapA");
+        f(std::any::TypeId::of::<CapB>(), "CapB");
+    }
+}
+
+#[test]
+fn test_inspect_only_impl() {
+    let mut seen = Vec::new();
+    Pair.inspect(|name| seen.push(name));
+    assert_eq!(seen, ["CapA", "CapB"]);
+}
+
+#[test]
+fn test_describe_default() {
+    assert_eq!(Pair::describe(), "set(n=2)");
+    assert_eq!(Pair::names(), ["CapA", "CapB"]);
+}
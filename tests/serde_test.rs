@@ -0,0 +1,30 @@
+//! Test CapSetSerde (requires the `serde` feature)
+
+#![cfg(feature = "serde")]
+
+use tola_caps::prelude::*;
+use tola_caps::CapSetSerde;
+
+#[derive(Capability)]
+struct CanRead;
+
+#[derive(Capability)]
+struct CanWrite;
+
+fn to_json<C: InspectNames>() -> String {
+    serde_json::to_string(&CapSetSerde::<C>::new()).unwrap()
+}
+
+#[test]
+fn test_serialize_names() {
+    assert_eq!(to_json::<caps![CanRead]>(), r#"["CanRead"]"#);
+    assert_eq!(to_json::<caps![CanRead, CanWrite]>(), r#"["CanRead","CanWrite"]"#);
+    // Sorted by name, whatever the trie order
+    assert_eq!(<caps![CanRead, CanWrite]>::names(), ["CanWrite", "CanRead"]);
+    assert_eq!(to_json::<caps![CanWrite, CanRead]>(), r#"["CanRead","CanWrite"]"#);
+}
+
+#[test]
+fn test_serialize_empty() {
+    assert_eq!(to_json::<caps![]>(), "[]");
+}
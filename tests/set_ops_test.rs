@@ -0,0 +1,583 @@
+//! Tests for type-level set operations on capability tries.
+
+use std::any::TypeId;
+use tola_caps::prelude::*;
+use tola_caps::primitives::stream::{D0, D2, D4};
+use tola_caps::{caps_eq, diff, And, WithIfAbsent, Complement, ComplementOf, with_all, AssertDisjoint, Disjoint, intersect, union, Bool, Cardinality, Count, SetEq, CapSet0, CapSet1, CapSet2, CapSet3, CapSet4, Absent, CapFn, CapPredicate, Empty, ForEach, Has, LeafQuery, Or, Partition, Present, RemoveIf, SetMap, CapabilityOrd, InspectNames, Ordering3, SortSet, WithLimit};
+
+#[derive(Capability)]
+struct A;
+
+#[derive(Capability)]
+struct B;
+
+#[derive(Capability)]
+struct C;
+
+// Routing nibbles in this module (first nibble pairs up across the sets):
+//   Read d0.., Cache de..   Write 40.., Exec 4d..   Net 04.., Audit 03..
+#[derive(Capability)]
+struct Read;
+#[derive(Capability)]
+struct Write;
+#[derive(Capability)]
+struct Net;
+#[derive(Capability)]
+struct Cache;
+#[derive(Capability)]
+struct Exec;
+#[derive(Capability)]
+struct Audit;
+
+fn same_set<L: 'static, R: 'static>() -> bool {
+    TypeId::of::<L>() == TypeId::of::<R>()
+}
+
+fn has<S: Evaluate<Q>, Q>() -> bool {
+    <S as Evaluate<Q>>::RESULT
+}
+
+// =============================================================================
+// SetUnion
+// =============================================================================
+
+#[test]
+fn test_union_node16_with_node16() {
+    type Left = caps![Read, Write, Net];
+    type Right = caps![Cache, Exec, Audit];
+    type Union = union![Left, Right];
+
+    assert!(same_set::<Union, caps![Read, Write, Net, Cache, Exec, Audit]>());
+    assert!(has::<Union, Read>() && has::<Union, Write>() && has::<Union, Net>());
+    assert!(has::<Union, Cache>() && has::<Union, Exec>() && has::<Union, Audit>());
+    assert!(!has::<Union, A>());
+}
+
+#[test]
+fn test_union_node16_overlapping() {
+    type Left = caps![Read, Write, Net];
+    type Right = caps![Read, Exec, Net];
+    type Union = union![Left, Right];
+
+    assert!(same_set::<Union, caps![Read, Write, Net, Exec]>());
+    assert!(same_set::<union![Left, Left], Left>());
+}
+
+#[test]
+fn test_union_variadic() {
+    type Three = union![caps![A], caps![B], caps![C]];
+    assert!(has::<Three, A>() && has::<Three, B>() && has::<Three, C>());
+    caps_eq!(union![caps![A], caps![B], caps![C]], caps![A, B, C]);
+
+    type Four = union![caps![Read, Write], caps![Net], caps![Cache, Exec], caps![Audit, Read],];
+    assert!(same_set::<Four, caps![Read, Write, Net, Cache, Exec, Audit]>());
+    assert!(same_set::<union![caps![A], caps![B],], union![caps![A], caps![B]]>());
+}
+
+// =============================================================================
+// SetIntersect
+// =============================================================================
+
+fn requires_shared<S: Evaluate<Has<Read>, Out = Present>>() {}
+
+#[test]
+fn test_intersect_node16_with_node16() {
+    type Left = caps![Read, Write, Net];
+    type Right = caps![Read, Exec, Audit];
+    type Common = intersect![Left, Right];
+
+    requires_shared::<Common>();
+    assert!(same_set::<Common, caps![Read]>());
+    assert!(has::<Common, Has<Read>>());
+    assert!(!has::<Common, Write>() && !has::<Common, Net>());
+    assert!(!has::<Common, Exec>() && !has::<Common, Audit>());
+}
+
+#[test]
+fn test_intersect_node16_disjoint_collapses_to_empty() {
+    type Left = caps![Read, Write, Net];
+    type Right = caps![Cache, Exec, Audit];
+    assert!(same_set::<intersect![Left, Right], Empty>());
+}
+
+#[test]
+fn test_intersect_leaf_with_node16() {
+    type Set = caps![Read, Write, Net];
+    assert!(same_set::<intersect![caps![Write], Set], caps![Write]>());
+    assert!(same_set::<intersect![Set, caps![Write]], caps![Write]>());
+    assert!(same_set::<intersect![Set, caps![Cache]], Empty>());
+}
+
+#[test]
+fn test_intersect_variadic() {
+    type Common = intersect![caps![Read, Write, Net], caps![Read, Net, Exec], caps![Net, Read, Audit]];
+    assert!(same_set::<Common, caps![Read, Net]>());
+    assert!(same_set::<intersect![caps![A, B], caps![B, C], caps![C, A]], Empty>());
+}
+
+// =============================================================================
+// Normalize (canonical shape after removal)
+// =============================================================================
+
+#[test]
+fn test_without_restores_canonical_shape() {
+    // Read/Write diverge at the first nibble
+    assert!(same_set::<tola_caps::without![caps![Read, Write], Write], caps![Read]>());
+    assert!(same_set::<tola_caps::without![caps![Read, Write], Read, Write], Empty>());
+    assert!(same_set::<tola_caps::without![caps![Read, Write, Net], Net], caps![Read, Write]>());
+
+    // Read/Cache share the first nibble, so they sit in a nested Node16
+    assert!(same_set::<tola_caps::without![caps![Read, Cache], Cache], caps![Read]>());
+    assert!(same_set::<tola_caps::without![caps![Read, Cache, Net], Cache], caps![Read, Net]>());
+}
+
+#[test]
+fn test_without_then_set_eq() {
+    type Removed = tola_caps::without![caps![Read, Cache, Write], Cache];
+    assert!(eq::<Removed, caps![Write, Read]>());
+    assert!(same_set::<diff![caps![A, B, C], caps![B, C]], caps![A]>());
+}
+
+// =============================================================================
+// SetDifference
+// =============================================================================
+
+#[test]
+fn test_difference_removes_other_members() {
+    type Downgraded = diff![CapSet3<A, B, C>, CapSet1<B>];
+    assert!(has::<Downgraded, A>());
+    assert!(has::<Downgraded, C>());
+    assert!(!has::<Downgraded, B>());
+}
+
+#[test]
+fn test_difference_edge_cases() {
+    type Set = caps![Read, Write, Net];
+    assert!(same_set::<diff![Set, Empty], Set>());
+    assert!(same_set::<diff![Empty, Set], Empty>());
+
+    type NoOverlap = diff![Set, caps![Cache, Exec]];
+    assert!(has::<NoOverlap, Read>() && has::<NoOverlap, Write>() && has::<NoOverlap, Net>());
+
+    type Stripped = diff![Set, caps![Read, Write, Net, Audit]];
+    assert!(!has::<Stripped, Read>() && !has::<Stripped, Write>() && !has::<Stripped, Net>());
+}
+
+// =============================================================================
+// SetEq
+// =============================================================================
+
+fn eq<L: SetEq<R>, R>() -> bool {
+    <<L as SetEq<R>>::Out as Bool>::VALUE
+}
+
+caps_eq!(caps![Read, Write, Net], caps![Net, Read, Write]);
+caps_eq!(union![caps![Read], caps![Write]], caps![Write, Read]);
+
+#[test]
+fn test_set_eq_ignores_order() {
+    assert!(eq::<caps![A, B, C], caps![C, A, B]>());
+    assert!(eq::<Empty, Empty>());
+}
+
+#[test]
+fn test_set_eq_detects_drift() {
+    assert!(!eq::<caps![A, B, C], caps![A, B]>());
+    assert!(!eq::<caps![A, B], caps![A, B, C]>());
+    assert!(!eq::<caps![A], caps![B]>());
+    assert!(!eq::<caps![A], Empty>());
+}
+
+#[test]
+fn test_set_eq_structurally_different_tries() {
+    // Sets computed by different operations are compared by membership,
+    // not by trie shape.
+    type Common = intersect![caps![Read, Write, Net], caps![Read, Exec, Audit]];
+    assert!(eq::<Common, caps![Read]>());
+    assert!(eq::<diff![caps![Read, Write], caps![Write]], caps![Read]>());
+}
+
+#[test]
+fn test_with_all_folds_onto_base() {
+    assert!(eq::<with_all!(caps![A]; B, C), caps![A, B, C]>());
+    assert!(eq::<with_all!(Empty; A, B, C,), caps![A, B, C]>());
+    assert!(eq::<with_all!(caps![A, B];), caps![A, B]>());
+    // Already-present capabilities are not duplicated
+    assert!(eq::<with_all!(caps![A]; A, B), caps![A, B]>());
+}
+
+caps_eq!(with_all!(caps![Read]; Write, Net), caps![Net, Write, Read]);
+
+// =============================================================================
+// Disjoint
+// =============================================================================
+
+fn disjoint<L: Disjoint<R>, R>() -> bool {
+    <<L as Disjoint<R>>::Out as Bool>::VALUE
+}
+
+fn merge_namespaces<L: AssertDisjoint<R>, R>() {}
+
+#[test]
+fn test_disjoint_sets() {
+    assert!(disjoint::<caps![Read, Write, Net], caps![Cache, Exec, Audit]>());
+    assert!(disjoint::<caps![A], caps![B]>());
+    assert!(disjoint::<Empty, caps![A]>());
+    merge_namespaces::<caps![Read, Write, Net], caps![Cache, Exec, Audit]>();
+}
+
+#[test]
+fn test_overlapping_sets() {
+    assert!(!disjoint::<caps![Read, Write, Net], caps![Cache, Write]>());
+    assert!(!disjoint::<caps![A], caps![A, B]>());
+    assert!(!disjoint::<caps![A, B, C], caps![C]>());
+}
+
+// =============================================================================
+// Count
+// =============================================================================
+
+#[derive(Capability)]
+struct D;
+
+#[test]
+fn test_count_cap_sets() {
+    assert_eq!(<CapSet0 as Cardinality>::N, 0);
+    assert_eq!(<CapSet1<A> as Cardinality>::N, 1);
+    assert_eq!(<CapSet4<A, B, C, D> as Cardinality>::N, 4);
+    assert_eq!(<CapSet4<A, B, C, A> as Cardinality>::N, 3);
+}
+
+#[test]
+fn test_count_is_peano() {
+    assert!(same_set::<<CapSet4<A, B, C, D> as Count>::Out, D4>());
+    assert!(same_set::<<Empty as Count>::Out, D0>());
+}
+
+#[test]
+fn test_count_nested_nodes() {
+    type Six = union![caps![Read, Write, Net], caps![Cache, Exec, Audit]];
+    assert_eq!(<Six as Cardinality>::N, 6);
+}
+
+#[test]
+fn test_with_limit_up_to_limit() {
+    // A set with 3 members may grow to 4 under a limit of 4.
+    type Four = <caps![A, B, C] as WithLimit<D, D4>>::Out;
+    assert!(same_set::<Four, CapSet4<A, B, C, D>>());
+    // The default limit is MaxCaps.
+    type Two = <caps![A] as WithLimit<B>>::Out;
+    assert_eq!(<Two as Cardinality>::N, 2);
+}
+
+#[test]
+fn test_with_limit_existing_cap_at_limit() {
+    // Re-adding a member of a full set is not growth.
+    type Same = <caps![A, B] as WithLimit<A, D2>>::Out;
+    assert!(same_set::<Same, CapSet2<A, B>>());
+}
+
+// =============================================================================
+// Partition
+// =============================================================================
+
+struct Security;
+
+impl CapPredicate<A> for Security {
+    type Out = Present;
+}
+impl CapPredicate<B> for Security {
+    type Out = Absent;
+}
+impl CapPredicate<C> for Security {
+    type Out = Present;
+}
+
+#[test]
+fn test_partition_splits_by_predicate() {
+    type Set = caps![A, B, C];
+    type Matching = <Set as Partition<Security>>::Matching;
+    type Rest = <Set as Partition<Security>>::Rest;
+
+    assert!(same_set::<Matching, caps![A, C]>());
+    assert!(same_set::<Rest, caps![B]>());
+
+    assert!(has::<Matching, A>() && has::<Matching, C>() && !has::<Matching, B>());
+    assert!(has::<Rest, B>() && !has::<Rest, A>() && !has::<Rest, C>());
+}
+
+#[test]
+fn test_partition_empty_halves() {
+    type OnlyB = caps![B];
+    assert!(same_set::<<OnlyB as Partition<Security>>::Matching, Empty>());
+    assert!(same_set::<<OnlyB as Partition<Security>>::Rest, OnlyB>());
+}
+
+// =============================================================================
+// RemoveIf
+// =============================================================================
+
+/// Marker for capabilities a sanitize step must strip.
+trait Dangerous {}
+impl Dangerous for Exec {}
+impl Dangerous for Net {}
+
+struct IsDangerous;
+
+impl<X: Dangerous> CapPredicate<X> for IsDangerous {
+    type Out = Present;
+}
+impl CapPredicate<Read> for IsDangerous {
+    type Out = Absent;
+}
+impl CapPredicate<Cache> for IsDangerous {
+    type Out = Absent;
+}
+
+#[test]
+fn test_remove_if_strips_matching() {
+    type Mixed = caps![Read, Exec, Cache, Net];
+    type Safe = <Mixed as RemoveIf<IsDangerous>>::Out;
+
+    caps_eq!(Safe, caps![Read, Cache]);
+    assert!(same_set::<Safe, caps![Read, Cache]>());
+    assert!(same_set::<<caps![Exec, Net] as RemoveIf<IsDangerous>>::Out, Empty>());
+    assert!(same_set::<<caps![Read] as RemoveIf<IsDangerous>>::Out, caps![Read]>());
+}
+
+#[test]
+fn test_remove_if_leaf_query() {
+    type Mixed = caps![Read, Write, Exec, Audit];
+    type Kept = <Mixed as RemoveIf<LeafQuery<Or<Exec, Write>>>>::Out;
+    assert!(same_set::<Kept, caps![Read, Audit]>());
+}
+
+// =============================================================================
+// ForEach
+// =============================================================================
+
+/// Marker trait a policy requires of every capability.
+trait Auditable {}
+impl Auditable for A {}
+impl Auditable for C {}
+
+struct IsAuditable;
+
+impl<X: Auditable> CapPredicate<X> for IsAuditable {
+    type Out = Present;
+}
+impl CapPredicate<B> for IsAuditable {
+    type Out = Absent;
+}
+
+fn all_auditable<S: ForEach<IsAuditable>>() -> bool {
+    <<S as ForEach<IsAuditable>>::Out as Bool>::VALUE
+}
+
+fn require_auditable<S: ForEach<IsAuditable, Out = Present>>() {}
+
+#[test]
+fn test_for_each_all_members() {
+    assert!(all_auditable::<caps![A, C]>());
+    assert!(all_auditable::<caps![A]>());
+    require_auditable::<caps![C, A]>();
+}
+
+#[test]
+fn test_for_each_one_member_fails() {
+    assert!(!all_auditable::<caps![A, B, C]>());
+    assert!(!all_auditable::<caps![B]>());
+    assert_eq!(
+        TypeId::of::<<caps![A, B, C] as ForEach<IsAuditable>>::Out>(),
+        TypeId::of::<Absent>()
+    );
+}
+
+#[test]
+fn test_for_each_empty_is_vacuous() {
+    assert!(all_auditable::<Empty>());
+}
+
+// =============================================================================
+// SetMap
+// =============================================================================
+
+struct Identity;
+
+impl<X: Capability> CapFn<X> for Identity {
+    type Out = X;
+}
+
+/// Swaps A and B, leaves C alone.
+struct SwapAB;
+
+impl CapFn<A> for SwapAB {
+    type Out = B;
+}
+impl CapFn<B> for SwapAB {
+    type Out = A;
+}
+impl CapFn<C> for SwapAB {
+    type Out = C;
+}
+
+/// Collapses everything onto A.
+struct ToA;
+
+impl<X> CapFn<X> for ToA {
+    type Out = A;
+}
+
+type Mapped<S, F> = <S as SetMap<F>>::Out;
+
+#[test]
+fn test_set_map_identity() {
+    assert!(eq::<Mapped<caps![A, B], Identity>, caps![A, B]>());
+    assert!(eq::<Mapped<caps![Read, Write, Net, Cache], Identity>, caps![Read, Write, Net, Cache]>());
+    assert!(eq::<Mapped<Empty, Identity>, Empty>());
+}
+
+#[test]
+fn test_set_map_swap() {
+    assert!(eq::<Mapped<caps![A, C], SwapAB>, caps![B, C]>());
+    assert!(eq::<Mapped<caps![A, B], SwapAB>, caps![A, B]>());
+    assert!(eq::<Mapped<caps![B], SwapAB>, caps![A]>());
+}
+
+#[test]
+fn test_set_map_merges_collisions() {
+    assert!(eq::<Mapped<caps![A, B, C], ToA>, caps![A]>());
+    assert_eq!(<Mapped<caps![A, B, C], ToA> as Cardinality>::N, 1);
+}
+
+// =============================================================================
+// caps_diff!
+// =============================================================================
+
+tola_caps::caps_diff!(caps![Read, Write], caps![Write, Read]);
+tola_caps::caps_diff!(CapSet3<A, B, C>, caps![C, B, A]);
+
+// =============================================================================
+// assert_subset!
+// =============================================================================
+
+tola_caps::assert_subset!(caps![Read], caps![Read, Write, Net]);
+tola_caps::assert_subset!(caps![], caps![Read]);
+tola_caps::assert_subset!(caps![Exec], caps![Exec]);
+tola_caps::assert_subset!(caps![Read, Net], caps![Net, Write, Read]);
+tola_caps::assert_subset!(caps![Read, Write, Net], caps![Read, Write, Net, Cache, Exec]);
+
+struct Doc<C>(std::marker::PhantomData<C>);
+
+/// Forget capabilities: only allowed when `Big` covers all of `Small`.
+fn forget<Small, Big: tola_caps::SupersetOf<Small>>(_: Doc<Big>) -> Doc<Small> {
+    Doc(std::marker::PhantomData)
+}
+
+#[test]
+fn test_superset_of_node16() {
+    type Big = caps![Read, Write, Net];
+    type Small = caps![Read, Net];
+    // Both sides are Node16 roots, not single leaves.
+    assert!(tola_caps::dump_trie!(Big).starts_with("Node16"));
+    assert!(tola_caps::dump_trie!(Small).starts_with("Node16"));
+    let _small: Doc<Small> = forget(Doc::<Big>(std::marker::PhantomData));
+}
+
+// =============================================================================
+// SortSet / CapabilityOrd
+// =============================================================================
+
+fn cmp<X: CapabilityOrd<Y>, Y: Capability>() -> std::cmp::Ordering {
+    <X::Out as Ordering3>::VALUE
+}
+
+fn sorted_names<T: SortSet>() -> Vec<&'static str>
+where
+    T::Out: InspectNames,
+{
+    <T::Out as InspectNames>::names()
+}
+
+#[test]
+fn test_capability_ord() {
+    use std::cmp::Ordering;
+    // Same module, so identities order by name.
+    assert_eq!(cmp::<Audit, Cache>(), Ordering::Less);
+    assert_eq!(cmp::<Write, Read>(), Ordering::Greater);
+    assert_eq!(cmp::<Net, Net>(), Ordering::Equal);
+}
+
+#[test]
+fn test_sort_set_names() {
+    type Fwd = caps![Write, Audit, Read, Cache];
+    type Rev = caps![Cache, Read, Audit, Write];
+    assert_eq!(sorted_names::<Fwd>(), ["Audit", "Cache", "Read", "Write"]);
+    assert_eq!(sorted_names::<Fwd>(), sorted_names::<Rev>());
+    // Trie order follows routing instead.
+    assert_ne!(Fwd::names(), sorted_names::<Fwd>());
+    assert!(sorted_names::<Empty>().is_empty());
+    assert_eq!(<<caps![Exec] as SortSet>::Out as InspectNames>::COUNT, 1);
+}
+
+// =============================================================================
+// select_set!
+// =============================================================================
+
+const NET_ON: bool = true;
+const EXEC_ON: bool = false;
+
+#[test]
+fn test_select_set_by_const_flag() {
+    type Base = caps![Read];
+    type WithNet = tola_caps::select_set!(NET_ON => union![Base, caps![Net]], else => Base);
+    type WithExec = tola_caps::select_set!(EXEC_ON => union![Base, caps![Exec]], else => Base);
+
+    caps_eq!(WithNet, caps![Read, Net]);
+    caps_eq!(WithExec, caps![Read]);
+    assert!(has::<WithNet, Net>() && !has::<WithExec, Exec>());
+    assert!(same_set::<tola_caps::select_set!(!NET_ON => caps![A], else => Empty), Empty>());
+}
+
+#[test]
+fn test_build_set_conditional() {
+    type Both = tola_caps::build_set! { Read; @if(NET_ON) Net; };
+    type ReadOnly = tola_caps::build_set! { Read; @if(EXEC_ON) Exec; };
+    caps_assert!(Both: Read & Net);
+    caps_assert!(ReadOnly: Read & !Exec);
+    caps_eq!(Both, caps![Read, Net]);
+    caps_eq!(ReadOnly, caps![Read]);
+
+    type Mixed = tola_caps::build_set! { @if(!EXEC_ON) A; Read; @if(NET_ON && EXEC_ON) Exec; B; };
+    caps_eq!(Mixed, caps![A, Read, B]);
+    assert!(same_set::<tola_caps::build_set! {}, Empty>());
+}
+
+#[test]
+fn test_with_if_absent() {
+    type Set = caps![Read, Write, Net];
+    type Again = <Set as WithIfAbsent<Write>>::Out;
+    assert!(eq::<Again, Set>());
+    assert!(same_set::<Again, Set>());
+    assert_eq!(tola_caps::dump_trie!(Again), tola_caps::dump_trie!(Set));
+
+    type Grown = <Set as WithIfAbsent<Exec>>::Out;
+    assert!(same_set::<Grown, <Set as tola_caps::With<Exec>>::Out>());
+    assert!(eq::<Grown, caps![Read, Write, Net, Exec]>());
+    assert!(same_set::<<Empty as WithIfAbsent<A>>::Out, caps![A]>());
+}
+
+#[test]
+fn test_complement_within_universe() {
+    type Universe = CapSet3<A, B, C>;
+    type NotB = Complement<Universe, CapSet1<B>>;
+    assert!(eq::<NotB, caps![A, C]>());
+    assert!(!eq::<NotB, caps![A, B, C]>());
+    caps_eq!(Complement<Universe, CapSet0>, caps![C, B, A]);
+    caps_eq!(Complement<Universe, Universe>, CapSet0);
+
+    assert!(has::<caps![C, A], ComplementOf<Universe, CapSet1<B>>>());
+    assert!(!has::<caps![A], ComplementOf<Universe, CapSet1<B>>>());
+    assert!(!has::<caps![A, B, C], ComplementOf<Universe, CapSet1<B>>>());
+    assert!(has::<caps![A, C], And<Has<A>, ComplementOf<Universe, CapSet1<B>>>>());
+}
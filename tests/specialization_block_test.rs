@@ -60,3 +60,276 @@ fn test_primitives() {
 }
 
 fn main() {}
+
+// ============================================================================
+// TEST 2: Associated const specialization
+// ============================================================================
+
+mod cost {
+    use tola_caps::specialization;
+
+    specialization! {
+        pub trait Cost {
+            const COST: u32;
+            const LABEL: &'static str = "any";
+        }
+
+        impl<T> Cost for T {
+            default const COST: u32 = 10;
+        }
+
+        impl<T: Copy> Cost for T {
+            const COST: u32 = 1;
+            const LABEL: &'static str = "copy";
+        }
+    }
+
+    // Same const names in a second trait of the same module
+    specialization! {
+        pub trait Weight {
+            const COST: u32;
+            const LABEL: &'static str = "heavy";
+        }
+
+        impl<T> Weight for T {
+            default const COST: u32 = 100;
+        }
+
+        impl<T: Copy> Weight for T {
+            const COST: u32 = 2;
+            const LABEL: &'static str = "light";
+        }
+    }
+}
+
+#[test]
+fn test_const_specialization() {
+    use cost::Cost;
+
+    assert_eq!(<i32 as Cost>::COST, 1);
+    assert_eq!(<String as Cost>::COST, 10);
+    assert_eq!(<CloneAndCopy as Cost>::COST, 1);
+    assert_eq!(<OnlyClone as Cost>::COST, 10);
+
+    // Falls back to the trait default
+    assert_eq!(<i32 as Cost>::LABEL, "copy");
+    assert_eq!(<String as Cost>::LABEL, "any");
+}
+
+#[test]
+fn test_const_specialization_same_names() {
+    use cost::{Cost, Weight};
+
+    assert_eq!(<i32 as Weight>::COST, 2);
+    assert_eq!(<String as Weight>::COST, 100);
+    assert_eq!(<i32 as Weight>::LABEL, "light");
+    assert_eq!(<String as Weight>::LABEL, "heavy");
+    assert_eq!(<String as Cost>::LABEL, "any");
+}
+
+#[test]
+fn test_const_specialization_in_const_context() {
+    use cost::Cost;
+
+    const COPY_COST: u32 = <u8 as Cost>::COST;
+    assert_eq!(COPY_COST, 1);
+}
+
+// ============================================================================
+// TEST 3: Methods with extra parameters
+// ============================================================================
+
+mod combine {
+    use tola_caps::specialization;
+
+    fn helper() -> u32 {
+        7
+    }
+
+    specialization! {
+        pub trait Combine {
+            fn combine(&self, other: &Self) -> Self;
+            fn weight(base: u32, extra: u32) -> u32;
+            fn tag(&self) -> u32;
+        }
+
+        impl<T: Default> Combine for T {
+            default fn combine(&self, _other: &Self) -> Self { Self::default() }
+            default fn weight(base: u32, _extra: u32) -> u32 { base }
+            default fn tag(&self) -> u32 { 0 }
+        }
+
+        impl<T: Default + Clone> Combine for T {
+            fn combine(&self, other: &Self) -> Self { other.clone() }
+            fn weight(base: u32, extra: u32) -> u32 { base + extra }
+            // `self::` is a module path, not the receiver
+            fn tag(&self) -> u32 { let _ = self.clone(); self::helper() }
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq, tola_caps::AutoCaps)]
+struct Counter(u32);
+
+#[test]
+fn test_multi_param_method() {
+    use combine::Combine;
+
+    // Clone: takes `other`
+    assert_eq!(1i32.combine(&2), 2);
+    assert_eq!(String::from("a").combine(&String::from("b")), "b");
+
+    // Not Clone: falls back to Default
+    assert_eq!(Counter(1).combine(&Counter(2)), Counter(0));
+}
+
+#[test]
+fn test_self_path_in_method_body() {
+    use combine::Combine;
+
+    assert_eq!(1i32.tag(), 7);
+    assert_eq!(Counter(1).tag(), 0);
+}
+
+#[test]
+fn test_multi_param_static_method() {
+    use combine::Combine;
+
+    assert_eq!(<i32 as Combine>::weight(1, 2), 3);
+    assert_eq!(<Counter as Combine>::weight(1, 2), 1);
+}
+
+// ============================================================================
+// TEST 4: Negative capability bounds (NotClone)
+// ============================================================================
+
+mod kind {
+    use tola_caps::specialization;
+
+    specialization! {
+        pub trait Kind {
+            fn kind(&self) -> &'static str;
+            fn static_kind() -> &'static str;
+        }
+
+        impl<T: Clone> Kind for T {
+            fn kind(&self) -> &'static str { "clone" }
+            fn static_kind() -> &'static str { "clone" }
+        }
+
+        // Disjoint from `T: Clone`, so not ambiguous
+        impl<T: NotClone> Kind for T {
+            fn kind(&self) -> &'static str { "not clone" }
+            fn static_kind() -> &'static str { "not clone" }
+        }
+    }
+}
+
+#[test]
+fn test_not_clone_branch() {
+    use kind::Kind;
+
+    assert_eq!(String::new().kind(), "clone");
+    assert_eq!(NoTraits.kind(), "not clone");
+    assert_eq!(<String as Kind>::static_kind(), "clone");
+    assert_eq!(<NoTraits as Kind>::static_kind(), "not clone");
+}
+
+#[test]
+fn test_not_clone_select_cap() {
+    use std::any::TypeId;
+    use tola_caps::detect::IsNotClone;
+    use tola_caps::spec::dispatch::SelectCap;
+    use tola_caps::std_caps::Cap;
+
+    struct Then;
+    struct Else;
+
+    assert_eq!(
+        TypeId::of::<<Cap<NoTraits> as SelectCap<IsNotClone, Then, Else>>::Out>(),
+        TypeId::of::<Then>()
+    );
+    assert_eq!(
+        TypeId::of::<<Cap<String> as SelectCap<IsNotClone, Then, Else>>::Out>(),
+        TypeId::of::<Else>()
+    );
+}
+
+// ============================================================================
+// TEST 5: Explicit priorities for equally specific impls
+// ============================================================================
+
+mod show {
+    use tola_caps::specialization;
+
+    #[derive(Debug, tola_caps::AutoCaps)]
+    pub struct OnlyDebug;
+
+    specialization! {
+        pub trait Show {
+            fn show() -> &'static str;
+        }
+
+        impl<T> Show for T {
+            default fn show() -> &'static str { "opaque" }
+        }
+
+        // One bound each: ambiguous without priorities. Lower wins.
+        #[specialize(priority = 10)]
+        impl<T: Clone> Show for T {
+            default fn show() -> &'static str { "clone" }
+        }
+
+        #[specialize(priority = 20)]
+        impl<T: Debug> Show for T {
+            default fn show() -> &'static str { "debug" }
+        }
+    }
+}
+
+#[test]
+fn test_priority_orders_equal_impls() {
+    use show::{OnlyDebug, Show};
+
+    // Clone and Debug: priority 10 wins
+    assert_eq!(<String as Show>::show(), "clone");
+    assert_eq!(<OnlyClone as Show>::show(), "clone");
+    assert_eq!(<OnlyDebug as Show>::show(), "debug");
+    assert_eq!(<NoTraits as Show>::show(), "opaque");
+}
+
+// ============================================================================
+// TEST 6: Associated type falls back to the trait's declared default
+// ============================================================================
+
+mod output {
+    use tola_caps::specialization;
+
+    pub struct DefaultThing;
+
+    specialization! {
+        pub trait Produce {
+            type Output = DefaultThing;
+            fn label() -> &'static str;
+        }
+
+        impl<T> Produce for T {
+            default fn label() -> &'static str { "default" }
+        }
+
+        impl<T: Clone> Produce for T {
+            type Output = Vec<u8>;
+            fn label() -> &'static str { "clone" }
+        }
+    }
+}
+
+#[test]
+fn test_associated_type_default_flows_through() {
+    use output::{DefaultThing, Produce};
+    use std::any::TypeId;
+
+    assert_eq!(TypeId::of::<<String as Produce>::Output>(), TypeId::of::<Vec<u8>>());
+    assert_eq!(TypeId::of::<<NoTraits as Produce>::Output>(), TypeId::of::<DefaultThing>());
+    assert_eq!(<NoTraits as Produce>::label(), "default");
+}
@@ -14,11 +14,10 @@
 //! - `mod edge_cases` - Boundary conditions and complex scenarios
 
 use std::fmt::Debug;
-use tola_caps::prelude::*;
 use tola_caps::caps_check;
-use tola_caps::std_caps::{AutoCaps, AutoCapSet, Cap, IsClone, IsCopy, IsDebug, IsDefault, IsSend, IsSync};
+use tola_caps::std_caps::{AutoCaps, AutoCapSet, Cap, IsClone, IsCopy};
 use tola_caps::{Evaluate, Present, Absent};
-use tola_caps::trie::{And, Not, Or};
+use tola_caps::trie::{And, Not};
 
 // ============================================================================
 // Helper Macros
@@ -64,6 +63,7 @@ struct NoTraits;
 #[derive(Copy, Clone, tola_caps::AutoCaps)]
 struct CopyOnly;
 
+#[allow(dead_code)]
 #[derive(Clone, Default, tola_caps::AutoCaps)]
 struct CloneDefault;
 
@@ -385,6 +385,7 @@ mod custom_trait_integration {
     use super::*;
 
     // Define a custom trait and register it
+    #[allow(dead_code)]
     trait Serializable {
         fn serialize(&self) -> Vec<u8>;
     }
@@ -450,20 +451,24 @@ mod performance_patterns {
     }
 
     /// Type-level strategy with zero runtime dispatch
+    #[allow(dead_code)]
     trait Strategy {
         fn name() -> &'static str;
     }
 
+    #[allow(dead_code)]
     struct MemcpyStrategy;
     impl Strategy for MemcpyStrategy {
         fn name() -> &'static str { "memcpy" }
     }
 
+    #[allow(dead_code)]
     struct CloneStrategy;
     impl Strategy for CloneStrategy {
         fn name() -> &'static str { "clone" }
     }
 
+    #[allow(dead_code)]
     struct MoveStrategy;
     impl Strategy for MoveStrategy {
         fn name() -> &'static str { "move" }
@@ -490,3 +495,113 @@ mod performance_patterns {
         assert_eq!(get_strategy::<NoTraits>(), "move");
     }
 }
+
+// ============================================================================
+// PART 7: #[specialize] Where-Clause Preservation
+// ============================================================================
+
+mod where_clause_preservation {
+    use tola_caps::specialize;
+
+    trait Describe {
+        fn describe(&self) -> &'static str;
+    }
+
+    trait Borrowing<'a> {}
+    impl<T> Borrowing<'_> for T {}
+
+    struct Wrapper<T>(T);
+
+    // Lifetime and higher-ranked predicates (with a trailing comma) are kept
+    // verbatim; the capability bounds are appended after them.
+    #[specialize(for Clone)]
+    impl<T> Describe for Wrapper<T>
+    where
+        T: 'static,
+        for<'a> T: Borrowing<'a>,
+    {
+        fn describe(&self) -> &'static str {
+            "static clone"
+        }
+    }
+
+    #[test]
+    fn lifetime_bound_specialized_impl() {
+        assert_eq!(Wrapper(1i32).describe(), "static clone");
+        assert_eq!(Wrapper(String::new()).describe(), "static clone");
+    }
+}
+
+// ============================================================================
+// PART 8: #[specialize(for NotXxx)]
+// ============================================================================
+
+mod negative_attribute {
+    use tola_caps::specialize;
+
+    trait MustCopy {
+        fn how(&self) -> &'static str;
+    }
+
+    struct Handle<T>(T);
+
+    #[specialize(for NotClone)]
+    impl<T> MustCopy for Handle<T> {
+        fn how(&self) -> &'static str {
+            "moved"
+        }
+    }
+
+    #[test]
+    fn not_clone_attribute() {
+        assert_eq!(Handle(super::NoTraits).how(), "moved");
+        // Handle(String::new()).how() does not compile: String is Clone
+    }
+}
+
+// ============================================================================
+// PART 9: #[specialize(for IteratorItemIs<T>)]
+// ============================================================================
+
+mod iterator_item {
+    use tola_caps::caps_check;
+    use tola_caps::detect::IteratorItemIs;
+    use tola_caps::specialize;
+
+    trait Checksum {
+        fn checksum(self) -> u32;
+    }
+
+    struct Bytes<I>(I);
+
+    #[specialize(for IteratorItemIs<u8>)]
+    impl<T> Checksum for Bytes<T> {
+        fn checksum(self) -> u32 {
+            self.0.fold(0u32, |acc, b| acc.wrapping_add(b as u32))
+        }
+    }
+
+    #[test]
+    fn byte_iterator_attribute() {
+        let data = [1u8, 2, 3];
+        assert_eq!(Bytes(data.iter().copied()).checksum(), 6);
+        // Bytes("abc".chars()).checksum() does not compile: Item is char
+    }
+
+    #[test]
+    fn item_type_detection() {
+        assert!(caps_check!(std::slice::Iter<'static, u8>: IteratorItemIs[&'static u8]));
+        assert!(caps_check!(std::vec::IntoIter<u8>: IteratorItemIs[u8]));
+        assert!(!caps_check!(std::vec::IntoIter<u8>: IteratorItemIs[char]));
+        assert!(!caps_check!(Vec<u8>: IteratorItemIs[u8]));
+    }
+
+    #[test]
+    fn byte_path_selected() {
+        fn path(bytes: bool) -> &'static str {
+            if bytes { "bytes" } else { "generic" }
+        }
+        assert_eq!(path(caps_check!(std::iter::Copied<std::slice::Iter<'static, u8>>: IteratorItemIs[u8])), "bytes");
+        assert_eq!(path(caps_check!(std::str::Chars<'static>: IteratorItemIs[u8])), "generic");
+    }
+}
@@ -56,3 +56,33 @@ fn test_std_types_caps() {
         assert!(caps_check!(TcpStream: std::io::Write));
     }
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_feature_gated_traits_in_cap_set() {
+    use std::fs::File;
+    use std::io::Read;
+    use tola_caps::detect::{Cap, IsIoWrite, IsRead, IsSeek, IsToString};
+    use tola_caps::trie::Evaluate;
+    use tola_caps::AutoCaps;
+
+    fn has<S: Evaluate<Q>, Q>() -> bool {
+        <S as Evaluate<Q>>::RESULT
+    }
+
+    #[derive(AutoCaps)]
+    struct FileWrapper(File);
+
+    impl Read for FileWrapper {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    assert!(has::<Cap<File>, IsRead>());
+    assert!(has::<Cap<File>, IsSeek>());
+    assert!(has::<Cap<FileWrapper>, IsRead>());
+    assert!(!has::<Cap<FileWrapper>, IsIoWrite>());
+    assert!(has::<Cap<String>, IsToString>());
+    assert!(!has::<Cap<File>, IsToString>());
+}
@@ -0,0 +1,8 @@
+//! Compile-fail diagnostics checked with trybuild.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,10 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+struct CanRead;
+
+struct CanWrite;
+
+tola_caps::assert_capability!(CanRead, CanWrite);
+
+fn main() {}
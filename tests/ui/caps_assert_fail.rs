@@ -0,0 +1,13 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanAdmin;
+
+caps_assert!(caps![CanRead]: CanRead & CanAdmin);
+
+fn main() {}
@@ -0,0 +1,29 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+
+// Explicit streams keep the rendered sets independent of the crate name.
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct Signed;
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct Sealed;
+
+#[caps_bound(conflicts_any = [Signed, Sealed])]
+fn not_both<C>(_: PhantomData<C>) {}
+
+#[caps_bound(conflicts_all = [Signed, Sealed])]
+fn none_of<C>(_: PhantomData<C>) {}
+
+// Negates the whole expression: neither may be present
+#[caps_bound(conflicts = Signed | Sealed)]
+fn neither<C>(_: PhantomData<C>) {}
+
+fn main() {
+    // Both present: rejected by conflicts_any
+    not_both(PhantomData::<caps![Signed, Sealed]>);
+    // One present: rejected by conflicts_all
+    none_of(PhantomData::<caps![Sealed]>);
+    // One present: rejected by conflicts = A | B
+    neither(PhantomData::<caps![Signed]>);
+}
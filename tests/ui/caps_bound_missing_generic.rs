@@ -0,0 +1,14 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+struct CanRead;
+
+// No generic, no `transparent`, no `target`: nothing to bound.
+#[caps_bound(requires = CanRead)]
+fn read(x: u8) -> u8 {
+    x
+}
+
+fn main() {
+    read(1);
+}
@@ -0,0 +1,12 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+struct Linked;
+
+#[caps_bound(requires = Linked, note = "call `resolve_links()` first")]
+fn render<C>(_: PhantomData<C>) {}
+
+fn main() {
+    render(PhantomData::<caps![]>);
+}
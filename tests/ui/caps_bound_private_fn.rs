@@ -0,0 +1,21 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct Signed;
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct Sealed;
+
+// Private and public free fns get the same per-predicate diagnostic
+#[caps_bound(requires = Signed & !Sealed)]
+fn private_sign<C>(_: PhantomData<C>) {}
+
+#[caps_bound(requires = Signed & !Sealed)]
+pub fn public_sign<C>(_: PhantomData<C>) {}
+
+fn main() {
+    private_sign(PhantomData::<caps![Signed, Sealed]>);
+    public_sign(PhantomData::<caps![Signed, Sealed]>);
+}
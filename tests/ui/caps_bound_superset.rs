@@ -0,0 +1,20 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanAdmin;
+
+type AdminCaps = caps![CanRead, CanAdmin];
+
+#[caps_bound(requires = Superset<AdminCaps>)]
+fn admin_only<C>(_: PhantomData<C>) {}
+
+fn main() {
+    // Missing CanAdmin: not a superset of AdminCaps.
+    admin_only(PhantomData::<caps![CanRead]>);
+}
@@ -0,0 +1,23 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+struct CanRead;
+
+#[derive(Capability)]
+struct CanWrite;
+
+#[caps_bound(requires = CanRead, target = [A, B])]
+fn merge<A, B>(_: PhantomData<A>, _: PhantomData<B>) {}
+
+#[caps_bound(A: CanRead, B: CanWrite)]
+fn copy<A, B>(_: PhantomData<A>, _: PhantomData<B>) {}
+
+fn main() {
+    // Left side lacks CanRead
+    merge(PhantomData::<caps![CanWrite]>, PhantomData::<caps![CanRead]>);
+    // Right side lacks CanRead
+    merge(PhantomData::<caps![CanRead]>, PhantomData::<caps![CanWrite]>);
+    // Right side lacks CanWrite; CanRead is only required of the left
+    copy(PhantomData::<caps![CanRead]>, PhantomData::<caps![CanRead]>);
+}
@@ -0,0 +1,16 @@
+use tola_caps::caps_check_generic;
+
+trait Custom {}
+
+fn unbounded<T>() -> bool {
+    caps_check_generic!(T: Clone)
+}
+
+fn custom<T: tola_caps::std_caps::AutoCaps>() -> bool {
+    caps_check_generic!(T: Custom)
+}
+
+fn main() {
+    let _ = unbounded::<String>();
+    let _ = custom::<String>();
+}
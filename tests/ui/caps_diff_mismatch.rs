@@ -0,0 +1,15 @@
+use tola_caps::prelude::*;
+
+mod m {
+    use tola_caps::prelude::*;
+
+    #[derive(Capability)]
+    pub struct Read;
+}
+
+#[derive(Capability)]
+struct Write;
+
+tola_caps::caps_diff!(caps![m::Read, Write], caps![m::Read]);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct CanLog;
+
+#[derive(Capability)]
+#[capability(stream = "X4")]
+struct CanSend;
+
+#[derive(Capability)]
+#[capability(stream = "X5")]
+struct CanAdmin;
+
+type TooMany = caps_limited![4; CanRead, CanWrite, CanLog, CanSend, CanAdmin];
+
+fn main() {}
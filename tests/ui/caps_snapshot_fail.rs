@@ -0,0 +1,23 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct CanAdmin;
+
+type Editor = caps![CanRead, CanWrite, CanAdmin];
+
+// CanAdmin was added to the set
+caps_snapshot!(Editor => ["CanRead", "CanWrite"]);
+
+// CanWrite was removed from the set
+caps_snapshot!(caps![CanRead] => ["CanRead", "CanWrite"]);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use tola_caps::prelude::*;
+use tola_caps::define_capabilities;
+
+define_capabilities! {
+    SvgOptimized => "SVG content has been optimized",
+    SvgRaw => "SVG content is kept raw",
+    conflicts SvgMode(SvgOptimized, SvgRaw),
+}
+
+fn render_optimized<C: HasSvgOptimizedCap>() {}
+fn svg_exclusive<C: SvgModeExclusive>() {}
+
+fn main() {
+    // Naming a set with both members compiles: the group is only checked
+    // where a `HasXxxCap` or `SvgModeExclusive` bound is required.
+    let _unchecked: Option<caps![SvgRawCap, SvgOptimizedCap]> = None;
+
+    render_optimized::<caps![SvgRawCap, SvgOptimizedCap]>();
+    svg_exclusive::<caps![SvgRawCap, SvgOptimizedCap]>();
+}
@@ -0,0 +1,19 @@
+use tola_caps::prelude::*;
+use tola_caps::define_capabilities;
+
+define_capabilities! {
+    LinksChecked => "Links have been checked",
+    LinksResolved => "Links have been resolved" requires LinksChecked,
+    AssetsBundled => "Assets have been bundled",
+    Published => "Site has been published" requires (LinksResolved, AssetsBundled),
+}
+
+fn resolved<C: HasLinksResolvedCap>() {}
+fn published_deps<C: PublishedCapDeps>() {}
+
+fn main() {
+    resolved::<caps![LinksResolvedCap]>();
+    published_deps::<caps![PublishedCap]>();
+    // Only the prerequisite actually missing is reported.
+    published_deps::<caps![PublishedCap, LinksResolvedCap]>();
+}
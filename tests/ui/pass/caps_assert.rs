@@ -0,0 +1,24 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct CanAdmin;
+
+type Editor = caps![CanRead, CanWrite];
+
+caps_assert!(Editor: CanRead & (CanWrite | CanAdmin));
+caps_assert!(Editor: CanRead => CanWrite, caps![]: !CanRead);
+caps_assert!(Editor: Superset<caps![CanWrite]>);
+
+caps_refute!(Editor: CanAdmin);
+caps_refute!(Editor: CanRead ^ CanWrite);
+
+fn main() {}
@@ -0,0 +1,19 @@
+use tola_caps::prelude::*;
+
+mod m {
+    use tola_caps::prelude::*;
+
+    #[derive(Capability)]
+    pub struct Read;
+}
+
+use m::Read;
+
+#[derive(Capability)]
+struct Write;
+
+// Spelled differently, same members
+tola_caps::caps_diff!(caps![m::Read], caps![Read]);
+tola_caps::caps_diff!(caps![crate::m::Read, Write], caps![Write, Read]);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct CanLog;
+
+type Embedded = caps_limited![4; CanRead, CanWrite, CanLog];
+// Exactly at the limit
+type Full = caps_limited![3; CanRead, CanWrite, CanLog];
+
+caps_assert!(Embedded: CanRead & CanWrite & CanLog);
+caps_assert!(Full: CanLog);
+
+fn main() {
+    assert_eq!(<Embedded as InspectNames>::COUNT, 3);
+    assert_eq!(<caps_limited![0;] as InspectNames>::COUNT, 0);
+}
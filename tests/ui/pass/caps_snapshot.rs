@@ -0,0 +1,23 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+#[derive(Capability)]
+#[capability(stream = "X1 X3")]
+struct CanAdmin;
+
+type Editor = caps![CanRead, CanWrite];
+
+caps_snapshot!(Editor => ["CanRead", "CanWrite"]);
+// Order does not matter
+caps_snapshot!(caps![CanWrite, CanRead] => ["CanWrite", "CanRead"]);
+caps_snapshot!(caps![CanRead, CanAdmin, CanWrite] => ["CanAdmin", "CanWrite", "CanRead"]);
+caps_snapshot!(caps![] => []);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use tola_caps::prelude::*;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct CanWrite;
+
+fn copy_file<C: RequireAll<CanRead, CanWrite>>() {}
+
+fn main() {
+    copy_file::<caps![CanRead]>();
+}
@@ -0,0 +1,21 @@
+use tola_caps::prelude::*;
+use tola_caps::{hlist, Has, RequireEach};
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct A;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct B;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct C;
+
+fn needs_abc<S: RequireEach<hlist![Has<A>, Has<B>, Has<C>]>>() {}
+
+fn main() {
+    // Only `B` is missing, so the error names `Has<B>`.
+    needs_abc::<caps![A, C]>();
+}
@@ -0,0 +1,22 @@
+use tola_caps::specialization;
+
+specialization! {
+    trait Show {
+        fn show() -> &'static str;
+    }
+
+    impl<T> Show for T {
+        default fn show() -> &'static str { "opaque" }
+    }
+
+    impl<T: Clone> Show for T {
+        default fn show() -> &'static str { "clone" }
+    }
+
+    // Same specificity as `T: Clone`; needs `#[specialize(priority = N)]`
+    impl<T: Debug> Show for T {
+        default fn show() -> &'static str { "debug" }
+    }
+}
+
+fn main() {}
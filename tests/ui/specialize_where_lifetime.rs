@@ -0,0 +1,23 @@
+use tola_caps::specialize;
+
+trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+struct Wrapper<T>(T);
+
+#[specialize(for Clone)]
+impl<T> Describe for Wrapper<T>
+where
+    T: 'static,
+{
+    fn describe(&self) -> &'static str {
+        "static clone"
+    }
+}
+
+fn main() {
+    // `T: 'static` is kept on the generated impl, so a borrowed `T` is rejected.
+    let owned = String::new();
+    Wrapper(&owned).describe();
+}
@@ -0,0 +1,12 @@
+use tola_caps::{Evaluate, Present};
+
+struct MyQuery;
+
+// Both types are local, so only the sealed TrieNode bound rejects this.
+struct MyWrapper;
+
+impl Evaluate<MyQuery> for MyWrapper {
+    type Out = Present;
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use tola_caps::prelude::*;
+use tola_caps::{EvalAt, Has, Leaf, Present};
+use tola_caps::primitives::stream::D0;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+// Query traits cannot be implemented for a type outside the trie.
+struct MyLeaf(Leaf<CanRead>);
+
+impl EvalAt<Has<CanRead>, D0> for MyLeaf {
+    type Out = Present;
+}
+
+fn main() {}
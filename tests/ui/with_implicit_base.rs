@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+use tola_caps::prelude::*;
+use tola_caps::with;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct CanRead;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct Validated;
+
+struct Doc<C>(PhantomData<C>);
+
+// No `#[caps_bound(transparent)]`, so there is no set to add to.
+type Added = with![CanRead];
+
+// Only the signature is rewritten; the body has to name `__C`.
+#[caps_bound(requires = CanRead, with = Validated, transparent)]
+fn validate(doc: Doc) -> Doc<with![Validated]> {
+    let _ = doc;
+    let _: Option<Doc<with![Validated]>> = None;
+    Doc(PhantomData)
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use tola_caps::prelude::*;
+use tola_caps::primitives::stream::D2;
+use tola_caps::WithLimit;
+
+#[derive(Capability)]
+#[capability(stream = "X1")]
+struct A;
+
+#[derive(Capability)]
+#[capability(stream = "X2")]
+struct B;
+
+#[derive(Capability)]
+#[capability(stream = "X3")]
+struct C;
+
+fn main() {
+    // Already at the limit of 2: adding a third capability is rejected.
+    let _: Option<<caps![A, B] as WithLimit<C, D2>>::Out> = None;
+}